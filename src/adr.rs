@@ -0,0 +1,184 @@
+//! SNR-based data rate adaptation, for point-to-point links that want the
+//! behavior of LoRaWAN ADR without the network server.
+//!
+//! [`Adr`] collects the SNR and RSSI of the last `N` packets received from
+//! the peer, e.g. from [`GetPacketStatusLora`]. Once `N` packets are in, it
+//! compares the best SNR with the demodulation floor of the current SF plus
+//! a link margin: every 2.5 dB above the margin, beyond the hysteresis,
+//! lowers the SF by one, and every 2.5 dB missing raises it by one. A
+//! change clears the history, so the next decision only sees packets sent
+//! with the new settings.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::adr::Adr;
+//! use sx126x_spi_buffers::commands::{Bw, Cr, Sf};
+//!
+//! let mut adr: Adr<4> = Adr::new(Sf::Sf12, Bw::Bw125, Cr::Cr4_5);
+//! assert!(adr.record(20, -1100).is_none());
+//! assert!(adr.record(50, -1050).is_none());
+//! assert!(adr.record(30, -1080).is_none());
+//! assert_eq!(adr.rssi_average_deci_db(), Some(-1076));
+//!
+//! // Best SNR 5 dB, 25 dB above the SF12 floor: 15 dB over the 10 dB margin
+//! let set_modulation_params = adr.record(40, -1070).unwrap();
+//! assert_eq!(set_modulation_params.tx_buf, [0x8B, 0x07, 0x04, 0x01, 0]);
+//! assert_eq!(adr.sf() as u8, Sf::Sf7 as u8);
+//! assert_eq!(adr.snr_max_deci_db(), None);
+//!
+//! // Best SNR -3 dB, 5.5 dB short of the margin at SF7
+//! for _ in 0..3 {
+//!     assert!(adr.record(-30, -1200).is_none());
+//! }
+//! let set_modulation_params = adr.record(-30, -1200).unwrap();
+//! assert_eq!(set_modulation_params.tx_buf, [0x8B, 0x0A, 0x04, 0x01, 0]);
+//! ```
+//!
+//! [`GetPacketStatusLora`]: crate::commands::GetPacketStatusLora
+
+use super::commands::{Bw, Cr, SetModulationParamsLora, Sf};
+
+/// SNR change per SF step, in tenths of dB.
+const STEP_DECI_DB: i16 = 25;
+
+/// Lowest SNR at which `sf` still demodulates, in tenths of dB: -7.5 dB at
+/// SF7, 2.5 dB lower per SF above.
+#[inline(always)]
+pub const fn demodulation_floor_deci_db(sf: Sf) -> i16 {
+    -((sf as u8 as i16 - 4) * STEP_DECI_DB)
+}
+
+/// # ADR policy
+/// SNR and RSSI history of the last `N` packets and the modulation
+/// parameters recommended from it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Adr<const N: usize> {
+    snr_deci_db: [i16; N],
+    rssi_deci_db: [i16; N],
+    len: usize,
+    next: usize,
+    sf: u8,
+    bw: u8,
+    cr: u8,
+    min_sf: u8,
+    max_sf: u8,
+    margin_deci_db: i16,
+    hysteresis_deci_db: i16,
+}
+impl<const N: usize> Adr<N> {
+    /// Starts from `sf`, moving between SF7 and SF12 with a 10 dB margin,
+    /// the LoRaWAN default, and a 2.5 dB hysteresis.
+    #[inline(always)]
+    pub const fn new(sf: Sf, bw: Bw, cr: Cr) -> Self {
+        Self {
+            snr_deci_db: [0; N],
+            rssi_deci_db: [0; N],
+            len: 0,
+            next: 0,
+            sf: sf as u8,
+            bw: bw as u8,
+            cr: cr as u8,
+            min_sf: Sf::Sf7 as u8,
+            max_sf: Sf::Sf12 as u8,
+            margin_deci_db: 100,
+            hysteresis_deci_db: STEP_DECI_DB,
+        }
+    }
+    /// SNR kept above the demodulation floor, in tenths of dB.
+    #[inline(always)]
+    pub const fn with_margin(mut self, margin_deci_db: i16) -> Self {
+        self.margin_deci_db = margin_deci_db;
+        self
+    }
+    /// SNR excess or shortfall tolerated before changing the SF, in tenths
+    /// of dB.
+    #[inline(always)]
+    pub const fn with_hysteresis(mut self, hysteresis_deci_db: i16) -> Self {
+        self.hysteresis_deci_db = hysteresis_deci_db;
+        self
+    }
+    /// Limits the recommendations to `min_sf..=max_sf`.
+    #[inline(always)]
+    pub const fn with_sf_range(mut self, min_sf: Sf, max_sf: Sf) -> Self {
+        self.min_sf = min_sf as u8;
+        self.max_sf = max_sf as u8;
+        self
+    }
+    /// Current spreading factor.
+    #[inline(always)]
+    pub const fn sf(&self) -> Sf {
+        Sf::from_bits_truncate(self.sf)
+    }
+    /// SetModulationParamsLora for the current settings, LDRO included.
+    #[inline(always)]
+    pub const fn modulation_params(&self) -> SetModulationParamsLora {
+        SetModulationParamsLora::new_auto_ldro(
+            Sf::from_bits_truncate(self.sf),
+            Bw::from_bits_truncate(self.bw),
+            Cr::from_bits_truncate(self.cr),
+        )
+    }
+    /// Best SNR of the history, `None` if it is empty.
+    pub fn snr_max_deci_db(&self) -> Option<i16> {
+        self.snr_deci_db[..self.len].iter().copied().max()
+    }
+    /// Mean RSSI of the history, `None` if it is empty.
+    pub fn rssi_average_deci_db(&self) -> Option<i16> {
+        if self.len == 0 {
+            return None;
+        }
+        let sum: i32 = self.rssi_deci_db[..self.len]
+            .iter()
+            .map(|&rssi| rssi as i32)
+            .sum();
+        Some((sum / self.len as i32) as i16)
+    }
+    /// Records a received packet. Returns the parameters to switch to when
+    /// the history is full and calls for another SF.
+    pub fn record(
+        &mut self,
+        snr_deci_db: i16,
+        rssi_deci_db: i16,
+    ) -> Option<SetModulationParamsLora> {
+        if N == 0 {
+            return None;
+        }
+        self.snr_deci_db[self.next] = snr_deci_db;
+        self.rssi_deci_db[self.next] = rssi_deci_db;
+        self.next = (self.next + 1) % N;
+        self.len = (self.len + 1).min(N);
+        if self.len < N {
+            return None;
+        }
+
+        let snr = self.snr_max_deci_db()? as i32;
+        let floor = demodulation_floor_deci_db(Sf::from_bits_truncate(self.sf)) as i32;
+        let margin = snr - floor - self.margin_deci_db as i32;
+        let (hysteresis, step) = (self.hysteresis_deci_db as i32, STEP_DECI_DB as i32);
+        let sf = if margin > hysteresis {
+            let steps = (margin - hysteresis) / step;
+            self.sf
+                .saturating_sub(steps.min(0x0F) as u8)
+                .max(self.min_sf)
+        } else if margin < -hysteresis {
+            let steps = (-margin + step - 1) / step;
+            self.sf
+                .saturating_add(steps.min(0x0F) as u8)
+                .min(self.max_sf)
+        } else {
+            self.sf
+        };
+        if sf == self.sf {
+            return None;
+        }
+        self.sf = sf;
+        self.reset();
+        Some(self.modulation_params())
+    }
+    /// Clears the history, e.g. after the peer changed its settings.
+    #[inline(always)]
+    pub const fn reset(&mut self) {
+        self.len = 0;
+        self.next = 0;
+    }
+}
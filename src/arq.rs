@@ -0,0 +1,286 @@
+//! Stop-and-wait retransmission on top of the typestate [`Radio`].
+
+use super::radio::{self, Radio, Timeout, state::StandbyRc};
+use super::rf_switch::RfSwitch;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::InputPin;
+use embedded_hal::spi::SpiDevice;
+
+/// Delay between a missing acknowledgement and the next transmission.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backoff {
+    /// The same delay before every retry.
+    Fixed { delay_us: u32 },
+    /// `base_us` before the first retry, doubled for each further retry up
+    /// to `max_us`.
+    Exponential { base_us: u32, max_us: u32 },
+}
+impl Backoff {
+    /// Delay before retry number `retry`, starting at 0.
+    #[inline(always)]
+    pub const fn delay_us(&self, retry: u8) -> u32 {
+        match *self {
+            Self::Fixed { delay_us } => delay_us,
+            Self::Exponential { base_us, max_us } => {
+                let delay_us = match base_us.checked_shl(retry as u32) {
+                    Some(delay_us) if delay_us >> retry == base_us => delay_us,
+                    _ => u32::MAX,
+                };
+                if delay_us < max_us { delay_us } else { max_us }
+            }
+        }
+    }
+}
+
+/// Errors of [`Arq::send`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error<SpiE, PinE> {
+    Radio(radio::Error<SpiE, PinE>),
+    /// No acknowledgement after the last retry.
+    NoAck,
+}
+impl<SpiE, PinE> From<radio::Error<SpiE, PinE>> for Error<SpiE, PinE> {
+    fn from(error: radio::Error<SpiE, PinE>) -> Self {
+        Self::Radio(error)
+    }
+}
+
+/// # Arq
+/// Sends a packet with [`Radio::transmit`] and listens for the
+/// acknowledgement with [`Radio::receive`], retransmitting up to
+/// `max_retries` times. A receive timeout, a CRC or header error, or a
+/// packet rejected by the `is_ack` callback all count as a missing
+/// acknowledgement; SPI and BUSY errors are returned immediately.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::arq::{Arq, Backoff};
+/// use sx126x_spi_buffers::radio::Timeout;
+///
+/// let arq = Arq::new(3, Backoff::Exponential { base_us: 10_000, max_us: 40_000 }, Timeout::Ticks(6400))
+///     .with_jitter(5_000, 0xDEADBEEF);
+/// assert_eq!(arq.backoff().delay_us(0), 10_000);
+/// assert_eq!(arq.backoff().delay_us(1), 20_000);
+/// assert_eq!(arq.backoff().delay_us(5), 40_000);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Arq {
+    max_retries: u8,
+    backoff: Backoff,
+    ack_timeout: Timeout,
+    jitter_us: u32,
+    rng: u32,
+}
+impl Arq {
+    #[inline(always)]
+    pub const fn new(max_retries: u8, backoff: Backoff, ack_timeout: Timeout) -> Self {
+        Self {
+            max_retries,
+            backoff,
+            ack_timeout,
+            jitter_us: 0,
+            rng: 1,
+        }
+    }
+    /// Adds a random delay below `jitter_us` to every backoff, so that nodes
+    /// that collided do not retry in lockstep. `seed` feeds a xorshift
+    /// generator and should differ between nodes, e.g. taken from
+    /// [`GetRandomNumber`](crate::commands::GetRandomNumber).
+    #[inline(always)]
+    pub const fn with_jitter(mut self, jitter_us: u32, seed: u32) -> Self {
+        self.jitter_us = jitter_us;
+        self.rng = if seed == 0 { 1 } else { seed };
+        self
+    }
+    #[inline(always)]
+    pub const fn max_retries(&self) -> u8 {
+        self.max_retries
+    }
+    #[inline(always)]
+    pub const fn backoff(&self) -> Backoff {
+        self.backoff
+    }
+    /// Sends `data` until a packet accepted by `is_ack` is received into
+    /// `ack_buf`, and returns the number of transmissions.
+    pub fn send<SPI, BUSY, D, SW>(
+        &mut self,
+        radio: &mut Radio<SPI, BUSY, D, StandbyRc, SW>,
+        data: &[u8],
+        ack_buf: &mut [u8],
+        mut is_ack: impl FnMut(&[u8]) -> bool,
+    ) -> Result<u16, Error<SPI::Error, BUSY::Error>>
+    where
+        SPI: SpiDevice,
+        BUSY: InputPin,
+        D: DelayNs,
+        SW: RfSwitch<BUSY::Error>,
+    {
+        for retry in 0..=self.max_retries {
+            if retry > 0 {
+                let delay_us = self
+                    .backoff
+                    .delay_us(retry - 1)
+                    .saturating_add(self.jitter());
+                radio.delay_us(delay_us);
+            }
+            radio.transmit(data, Timeout::Disabled)?;
+            match radio.receive(ack_buf, self.ack_timeout) {
+                Ok(length) if is_ack(&ack_buf[..length]) => return Ok(retry as u16 + 1),
+                Ok(_)
+                | Err(radio::Error::Timeout | radio::Error::CrcErr | radio::Error::HeaderErr) => {}
+                Err(error) => return Err(error.into()),
+            }
+        }
+        Err(Error::NoAck)
+    }
+
+    fn jitter(&mut self) -> u32 {
+        if self.jitter_us == 0 {
+            return 0;
+        }
+        self.rng ^= self.rng << 13;
+        self.rng ^= self.rng >> 17;
+        self.rng ^= self.rng << 5;
+        self.rng % self.jitter_us
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::blocking::BlockingExecutor;
+    use crate::commands::{CrcType, HeaderType, InvertIq, SetPacketParams};
+    use crate::radio::tests::command;
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+    use std::{vec, vec::Vec};
+
+    fn start(irqs: [u8; 2], command_tx: Vec<u8>) -> Vec<SpiTransaction<u8>> {
+        let length = command_tx.len();
+        [
+            command(
+                vec![0x08, irqs[0], irqs[1], irqs[0], irqs[1], 0, 0, 0, 0],
+                vec![0; 9],
+            ),
+            command(vec![0x02, 0x43, 0xFF], vec![0; 3]),
+            command(command_tx, vec![0; length]),
+        ]
+        .concat()
+    }
+    fn set_packet_params<SPI, BUSY, D, SW>(radio: &mut Radio<SPI, BUSY, D, StandbyRc, SW>)
+    where
+        SPI: SpiDevice,
+        BUSY: InputPin,
+        D: DelayNs,
+    {
+        let packet_params = SetPacketParams::new(
+            8,
+            HeaderType::VariableLength,
+            4,
+            CrcType::On,
+            InvertIq::Standard,
+        );
+        radio.set_packet_params(packet_params).unwrap();
+    }
+    fn transmit(data: &[u8]) -> Vec<SpiTransaction<u8>> {
+        let mut expectations = command(vec![0x8F, 0, 0], vec![0; 3]).to_vec();
+        expectations.extend([
+            SpiTransaction::transaction_start(),
+            SpiTransaction::transfer(vec![0x0E, 0], vec![0; 2]),
+            SpiTransaction::write_vec(data.to_vec()),
+            SpiTransaction::transaction_end(),
+        ]);
+        expectations.extend(start([0x02, 0x63], vec![0x83, 0, 0, 0]));
+        expectations.extend(command(vec![0x12, 0, 0, 0], vec![0, 0, 0, 0x01]));
+        expectations.extend(command(vec![0x02, 0x43, 0xFF], vec![0; 3]));
+        expectations
+    }
+    fn receive(irq: [u8; 2]) -> Vec<SpiTransaction<u8>> {
+        let mut expectations = command(vec![0x8F, 0, 0], vec![0; 3]).to_vec();
+        expectations.extend(start([0x02, 0x63], vec![0x82, 0, 0x19, 0]));
+        expectations.extend(command(vec![0x12, 0, 0, 0], vec![0, 0, irq[0], irq[1]]));
+        expectations.extend(command(vec![0x02, 0x43, 0xFF], vec![0; 3]));
+        expectations
+    }
+
+    #[test]
+    fn test_retransmit_until_ack() {
+        let mut expectations = command(vec![0x8C, 0, 8, 0, 4, 1, 0], vec![0; 7]).to_vec();
+        expectations.extend(transmit(b"ping"));
+        expectations.extend(receive([0x02, 0x00]));
+        expectations.extend(transmit(b"ping"));
+        expectations.extend(receive([0x00, 0x02]));
+        expectations.extend(command(vec![0x13, 0, 0, 0], vec![0, 0, 3, 0x10]));
+        expectations.extend([
+            SpiTransaction::transaction_start(),
+            SpiTransaction::write_vec(vec![0x1E, 0x10, 0]),
+            SpiTransaction::read_vec(b"ack".to_vec()),
+            SpiTransaction::transaction_end(),
+        ]);
+        let transfers = expectations
+            .iter()
+            .filter(|t| **t == SpiTransaction::transaction_start())
+            .count();
+        let mut spi = SpiMock::new(&expectations);
+        let mut busy = PinMock::new(&vec![PinTransaction::get(State::Low); transfers]);
+        let mut radio = Radio::new(BlockingExecutor::new(
+            spi.clone(),
+            busy.clone(),
+            NoopDelay::new(),
+        ));
+        set_packet_params(&mut radio);
+
+        let mut arq = Arq::new(
+            2,
+            Backoff::Fixed { delay_us: 1_000 },
+            Timeout::Ticks(0x1900),
+        );
+        let mut ack_buf = [0; 8];
+        assert_eq!(
+            arq.send(&mut radio, b"ping", &mut ack_buf, |ack| ack == b"ack"),
+            Ok(2)
+        );
+        spi.done();
+        busy.done();
+    }
+
+    #[test]
+    fn test_no_ack() {
+        let mut expectations = command(vec![0x8C, 0, 8, 0, 4, 1, 0], vec![0; 7]).to_vec();
+        for _ in 0..2 {
+            expectations.extend(transmit(b"ping"));
+            expectations.extend(receive([0x02, 0x00]));
+        }
+        let transfers = expectations
+            .iter()
+            .filter(|t| **t == SpiTransaction::transaction_start())
+            .count();
+        let mut spi = SpiMock::new(&expectations);
+        let mut busy = PinMock::new(&vec![PinTransaction::get(State::Low); transfers]);
+        let mut radio = Radio::new(BlockingExecutor::new(
+            spi.clone(),
+            busy.clone(),
+            NoopDelay::new(),
+        ));
+        set_packet_params(&mut radio);
+
+        let mut arq = Arq::new(
+            1,
+            Backoff::Exponential {
+                base_us: 1_000,
+                max_us: 8_000,
+            },
+            Timeout::Ticks(0x1900),
+        )
+        .with_jitter(500, 42);
+        assert_eq!(
+            arq.send(&mut radio, b"ping", &mut [0; 8], |_| true),
+            Err(Error::NoAck)
+        );
+        spi.done();
+        busy.done();
+    }
+}
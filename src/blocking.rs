@@ -0,0 +1,357 @@
+//! Blocking executor on top of `embedded-hal` 1.0.
+
+use super::busy::{BusyError, BusyGate};
+use super::commands::{
+    AnyCommand, DEFAULT_BUSY_TIMEOUT_US, Response, SpiDescriptor, TransferStatus,
+};
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::InputPin;
+use embedded_hal::spi::{Operation, SpiDevice};
+
+/// Errors of the [`BlockingExecutor`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error<SpiE, PinE> {
+    Spi(SpiE),
+    Busy(PinE),
+    /// BUSY stayed high longer than the previous command allows.
+    BusyTimeout,
+}
+impl<SpiE, PinE> From<BusyError<PinE>> for Error<SpiE, PinE> {
+    fn from(error: BusyError<PinE>) -> Self {
+        match error {
+            BusyError::Pin(error) => Self::Busy(error),
+            BusyError::Timeout => Self::BusyTimeout,
+        }
+    }
+}
+
+/// # BlockingExecutor
+/// Runs commands and descriptors on an [`SpiDevice`], which frames every
+/// transfer with NSS. Before each transfer it waits for BUSY to go low, for
+/// at most the [`busy_timeout_us`](SpiDescriptor::busy_timeout_us) of the
+/// previous transfer, see [`BusyGate`].
+///
+/// ## Example
+/// ```
+/// use embedded_hal_mock::eh1::delay::NoopDelay;
+/// use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+/// use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+/// use sx126x_spi_buffers::blocking::BlockingExecutor;
+/// use sx126x_spi_buffers::commands::GetStatus;
+///
+/// let spi = SpiMock::new(&[
+///     SpiTransaction::transaction_start(),
+///     SpiTransaction::transfer(vec![0xC0, 0], vec![0xA2, 0x22]),
+///     SpiTransaction::transaction_end(),
+/// ]);
+/// let busy = PinMock::new(&[PinTransaction::get(State::High), PinTransaction::get(State::Low)]);
+/// let mut executor = BlockingExecutor::new(spi, busy, NoopDelay::new());
+///
+/// let mut get_status = GetStatus::new();
+/// executor.run(&mut get_status).unwrap();
+/// assert_eq!(get_status.rx_buf, [0xA2, 0x22]);
+///
+/// let (mut spi, mut busy, _) = executor.release();
+/// spi.done();
+/// busy.done();
+/// ```
+pub struct BlockingExecutor<SPI, BUSY, D> {
+    spi: SPI,
+    busy: BUSY,
+    delay: D,
+    gate: BusyGate,
+}
+impl<SPI, BUSY, D> BlockingExecutor<SPI, BUSY, D>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin,
+    D: DelayNs,
+{
+    pub fn new(spi: SPI, busy: BUSY, delay: D) -> Self {
+        Self {
+            spi,
+            busy,
+            delay,
+            gate: BusyGate::new(),
+        }
+    }
+    pub fn release(self) -> (SPI, BUSY, D) {
+        (self.spi, self.busy, self.delay)
+    }
+    /// Transfers `command` and waits for it to complete.
+    pub fn run<C: AnyCommand + ?Sized>(
+        &mut self,
+        command: &mut C,
+    ) -> Result<(), Error<SPI::Error, BUSY::Error>> {
+        let mut desc = SpiDescriptor::from_command(command);
+        // SAFETY: `command` is borrowed for the whole transfer.
+        unsafe { self.execute(&mut desc) }
+    }
+    /// Transfers `command` and returns its parsed response.
+    ///
+    /// ## Example
+    /// ```
+    /// use embedded_hal_mock::eh1::delay::NoopDelay;
+    /// use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    /// use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+    /// use sx126x_spi_buffers::blocking::BlockingExecutor;
+    /// use sx126x_spi_buffers::commands::{GetIrqStatus, Irq};
+    ///
+    /// let spi = SpiMock::new(&[
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x12, 0, 0, 0], vec![0xA2, 0xA2, 0x02, 0x01]),
+    ///     SpiTransaction::transaction_end(),
+    /// ]);
+    /// let busy = PinMock::new(&[PinTransaction::get(State::Low)]);
+    /// let mut executor = BlockingExecutor::new(spi, busy, NoopDelay::new());
+    ///
+    /// let irq = executor.query(&mut GetIrqStatus::new()).unwrap();
+    /// assert_eq!(irq, Irq::new().with_tx_done(true).with_timeout(true));
+    ///
+    /// let (mut spi, mut busy, _) = executor.release();
+    /// spi.done();
+    /// busy.done();
+    /// ```
+    pub fn query<C: Response>(
+        &mut self,
+        command: &mut C,
+    ) -> Result<C::Output, Error<SPI::Error, BUSY::Error>> {
+        self.run(command)?;
+        Ok(command.parse())
+    }
+    /// Runs the commands in order, stopping at the first error.
+    ///
+    /// ## Example
+    /// ```
+    /// use embedded_hal_mock::eh1::delay::NoopDelay;
+    /// use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    /// use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+    /// use sx126x_spi_buffers::blocking::BlockingExecutor;
+    /// use sx126x_spi_buffers::commands::{GetStatus, SetStandby, StdbyConfig};
+    ///
+    /// let spi = SpiMock::new(&[
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x80, 0], vec![0; 2]),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0xC0, 0], vec![0xA2, 0x22]),
+    ///     SpiTransaction::transaction_end(),
+    /// ]);
+    /// let busy = PinMock::new(&vec![PinTransaction::get(State::Low); 2]);
+    /// let mut executor = BlockingExecutor::new(spi, busy, NoopDelay::new());
+    ///
+    /// let mut set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+    /// let mut get_status = GetStatus::new();
+    /// executor.run_all(&mut [&mut set_standby, &mut get_status]).unwrap();
+    /// assert_eq!(get_status.rx_buf, [0xA2, 0x22]);
+    ///
+    /// let (mut spi, mut busy, _) = executor.release();
+    /// spi.done();
+    /// busy.done();
+    /// ```
+    pub fn run_all(
+        &mut self,
+        commands: &mut [&mut dyn AnyCommand],
+    ) -> Result<(), Error<SPI::Error, BUSY::Error>> {
+        commands
+            .iter_mut()
+            .try_for_each(|command| self.run(*command))
+    }
+    /// Transfers one descriptor: resolves a deferred length, waits for BUSY,
+    /// runs the transfer with its payload region in the same NSS frame, then
+    /// calls [`SpiDescriptor::complete`]. The descriptor status reflects the
+    /// outcome.
+    ///
+    /// # Safety
+    /// The buffers the descriptor points to must be valid and not be
+    /// accessed elsewhere until this returns.
+    pub unsafe fn execute(
+        &mut self,
+        desc: &mut SpiDescriptor,
+    ) -> Result<(), Error<SPI::Error, BUSY::Error>> {
+        // SAFETY: upheld by the caller.
+        unsafe { desc.resolve_length() };
+        if let Err(error) = self.wait_busy() {
+            desc.set_status(TransferStatus::SpiError);
+            return Err(error);
+        }
+        #[cfg(feature = "trace")]
+        // SAFETY: upheld by the caller.
+        unsafe {
+            desc.trace_tx()
+        };
+        desc.set_status(TransferStatus::InFlight);
+        let length = desc.transfer_length() as usize;
+        // SAFETY: upheld by the caller; the tx and rx buffers of a command
+        // never overlap.
+        let (tx_buf, payload) = unsafe {
+            (
+                core::slice::from_raw_parts(desc.tx_buf_ptr(), length),
+                match desc.payload_ptr().is_null() {
+                    true => &[][..],
+                    false => core::slice::from_raw_parts(
+                        desc.payload_ptr(),
+                        desc.payload_length() as usize,
+                    ),
+                },
+            )
+        };
+        let operations = if payload.is_empty() { 1 } else { 2 };
+        let result = if desc.is_tx_only() {
+            self.spi.transaction(
+                &mut [Operation::Write(tx_buf), Operation::Write(payload)][..operations],
+            )
+        } else {
+            // SAFETY: as above.
+            let rx_buf = unsafe { core::slice::from_raw_parts_mut(desc.rx_buf_ptr(), length) };
+            self.spi.transaction(
+                &mut [
+                    Operation::Transfer(rx_buf, tx_buf),
+                    Operation::Write(payload),
+                ][..operations],
+            )
+        };
+        if let Err(error) = result {
+            desc.set_status(TransferStatus::SpiError);
+            return Err(Error::Spi(error));
+        }
+        self.gate.sent(desc);
+        #[cfg(feature = "trace")]
+        // SAFETY: upheld by the caller.
+        unsafe {
+            desc.trace_rx()
+        };
+        desc.complete();
+        Ok(())
+    }
+    /// Reads `buf.len()` bytes of the data buffer starting at `offset` with a
+    /// ReadBuffer command, straight into `buf`.
+    pub fn read_buffer(
+        &mut self,
+        offset: u8,
+        buf: &mut [u8],
+    ) -> Result<(), Error<SPI::Error, BUSY::Error>> {
+        self.wait_busy()?;
+        self.spi
+            .transaction(&mut [Operation::Write(&[0x1E, offset, 0]), Operation::Read(buf)])
+            .map_err(Error::Spi)?;
+        self.gate.sent_raw(DEFAULT_BUSY_TIMEOUT_US);
+        Ok(())
+    }
+    /// Blocks for `us` microseconds, e.g. between polls of the IRQ status.
+    pub fn delay_us(&mut self, us: u32) {
+        self.delay.delay_us(us);
+    }
+    /// Transfers the descriptors in order, stopping at the first error.
+    ///
+    /// # Safety
+    /// Same as [`execute`](Self::execute), for every descriptor.
+    pub unsafe fn execute_all(
+        &mut self,
+        descs: &mut [SpiDescriptor],
+    ) -> Result<(), Error<SPI::Error, BUSY::Error>> {
+        descs
+            .iter_mut()
+            // SAFETY: upheld by the caller.
+            .try_for_each(|desc| unsafe { self.execute(desc) })
+    }
+
+    fn wait_busy(&mut self) -> Result<(), Error<SPI::Error, BUSY::Error>> {
+        let (busy, delay) = (&mut self.busy, &mut self.delay);
+        self.gate
+            .wait(|| busy.is_high(), |us| delay.delay_us(us))
+            .map_err(Error::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::commands::{
+        SetSleep, SetStandby, SleepConfig, StdbyConfig, TxOnly, WriteBufferHeader,
+    };
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+    use std::vec;
+
+    #[test]
+    fn test_execute_descriptors() {
+        static PAYLOAD: [u8; 3] = [1, 2, 3];
+        let mut spi = SpiMock::new(&[
+            SpiTransaction::transaction_start(),
+            SpiTransaction::transfer(vec![0x0E, 0x10], vec![0, 0]),
+            SpiTransaction::write_vec(vec![1, 2, 3]),
+            SpiTransaction::transaction_end(),
+            SpiTransaction::transaction_start(),
+            SpiTransaction::transfer(vec![0x84, 0x04], vec![0, 0]),
+            SpiTransaction::transaction_end(),
+            SpiTransaction::transaction_start(),
+            SpiTransaction::write_vec(vec![0x80, 0x00]),
+            SpiTransaction::transaction_end(),
+        ]);
+        // No BUSY read after SetSleep.
+        let mut busy = PinMock::new(&[
+            PinTransaction::get(State::Low),
+            PinTransaction::get(State::Low),
+        ]);
+        let mut executor = BlockingExecutor::new(spi.clone(), busy.clone(), NoopDelay::new());
+
+        let mut header = WriteBufferHeader::new(0x10);
+        let mut set_sleep = SetSleep::new(SleepConfig::new().with_warm_start(true));
+        let mut set_standby = TxOnly::new(SetStandby::new(StdbyConfig::StdbyRc).tx_buf);
+        let mut descs = [
+            header.scatter_descriptor(&PAYLOAD),
+            set_sleep.descriptor(),
+            set_standby.descriptor(),
+        ];
+        unsafe { executor.execute_all(&mut descs) }.unwrap();
+        assert!(
+            descs
+                .iter()
+                .all(|desc| desc.status() == TransferStatus::Done)
+        );
+        spi.done();
+        busy.done();
+    }
+
+    #[test]
+    fn test_tx_only_busy_timeout() {
+        let mut spi = SpiMock::new(&[
+            SpiTransaction::transaction_start(),
+            SpiTransaction::write_vec(vec![0x84, 0x00]),
+            SpiTransaction::transaction_end(),
+            SpiTransaction::transaction_start(),
+            SpiTransaction::write_vec(vec![0x80, 0x00]),
+            SpiTransaction::transaction_end(),
+        ]);
+        // BUSY stays high in sleep and is not read before the wake-up.
+        let mut busy = PinMock::new(&[PinTransaction::get(State::Low)]);
+        let mut executor = BlockingExecutor::new(spi.clone(), busy.clone(), NoopDelay::new());
+
+        let mut set_sleep = SetSleep::new(SleepConfig::new()).to_tx_only();
+        let mut set_standby = SetStandby::new(StdbyConfig::StdbyRc).to_tx_only();
+        executor.run(&mut set_sleep).unwrap();
+        unsafe { executor.execute(&mut set_standby.descriptor()) }.unwrap();
+        assert_eq!(executor.gate.timeout_us(), 5_000);
+        spi.done();
+        busy.done();
+    }
+
+    #[test]
+    fn test_busy_timeout() {
+        let mut spi = SpiMock::new(&[]);
+        let mut busy = PinMock::new(&vec![
+            PinTransaction::get(State::High);
+            DEFAULT_BUSY_TIMEOUT_US as usize + 1
+        ]);
+        let mut executor = BlockingExecutor::new(spi.clone(), busy.clone(), NoopDelay::new());
+
+        let mut set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+        assert_eq!(executor.run(&mut set_standby), Err(Error::BusyTimeout));
+        spi.done();
+        busy.done();
+    }
+}
@@ -0,0 +1,101 @@
+//! BUSY handshake for executors.
+//!
+//! A command sent while BUSY is high is lost and can corrupt the one still
+//! being processed, so every transfer has to wait for BUSY to go low first.
+//! How long that may take depends on the command sent before, see
+//! [`SpiDescriptor::busy_timeout_us`]. [`BusyGate`] keeps track of it for any
+//! executor, whether it polls, runs from interrupts or drives DMA.
+
+use super::commands::{DEFAULT_BUSY_TIMEOUT_US, SpiDescriptor};
+
+/// Errors of [`BusyGate::wait`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BusyError<E> {
+    /// Reading the BUSY pin failed.
+    Pin(E),
+    /// BUSY stayed high longer than the previous command allows.
+    Timeout,
+}
+
+/// # BusyGate
+/// Waits for BUSY to go low before a transfer, for at most the
+/// [`busy_timeout_us`](SpiDescriptor::busy_timeout_us) of the previous
+/// transfer. Call [`wait`](Self::wait) before and [`sent`](Self::sent) after
+/// every transfer.
+///
+/// ## Example
+/// ```
+/// use core::convert::Infallible;
+/// use sx126x_spi_buffers::busy::{BusyError, BusyGate};
+/// use sx126x_spi_buffers::commands::{SetSleep, SetStandby, SleepConfig, StdbyConfig};
+///
+/// let mut gate = BusyGate::new();
+/// let mut polls = 0;
+/// let mut is_busy = || -> Result<bool, Infallible> { polls += 1; Ok(polls < 3) };
+/// gate.wait(&mut is_busy, |_| {}).unwrap();
+///
+/// gate.sent(&SetStandby::new(StdbyConfig::StdbyRc).descriptor());
+/// assert_eq!(gate.timeout_us(), 5_000);
+/// assert_eq!(gate.wait(|| Ok::<_, Infallible>(true), |_| {}), Err(BusyError::Timeout));
+///
+/// // BUSY stays high in sleep; the next NSS edge wakes the chip.
+/// gate.sent(&SetSleep::new(SleepConfig::new()).descriptor());
+/// gate.wait(|| Ok::<_, Infallible>(true), |_| {}).unwrap();
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BusyGate {
+    timeout_us: u32,
+}
+impl BusyGate {
+    /// Interval between two reads of the BUSY pin.
+    pub const POLL_INTERVAL_US: u32 = 1;
+
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            timeout_us: DEFAULT_BUSY_TIMEOUT_US,
+        }
+    }
+    /// Maximum wait before the next transfer.
+    #[inline(always)]
+    pub const fn timeout_us(&self) -> u32 {
+        self.timeout_us
+    }
+    /// Records that `desc` has been transferred.
+    #[inline(always)]
+    pub const fn sent(&mut self, desc: &SpiDescriptor) {
+        self.timeout_us = desc.busy_timeout_us();
+    }
+    /// Records a transfer not described by a descriptor, with its maximum
+    /// BUSY time.
+    #[inline(always)]
+    pub const fn sent_raw(&mut self, busy_timeout_us: u32) {
+        self.timeout_us = busy_timeout_us;
+    }
+    /// Polls `is_busy` until it returns `false`, calling `delay_us` between
+    /// polls. Returns immediately if the previous transfer has no timeout.
+    pub fn wait<E>(
+        &self,
+        mut is_busy: impl FnMut() -> Result<bool, E>,
+        mut delay_us: impl FnMut(u32),
+    ) -> Result<(), BusyError<E>> {
+        if self.timeout_us == 0 {
+            return Ok(());
+        }
+        let mut waited_us = 0;
+        while is_busy().map_err(BusyError::Pin)? {
+            if waited_us >= self.timeout_us {
+                return Err(BusyError::Timeout);
+            }
+            delay_us(Self::POLL_INTERVAL_US);
+            waited_us += Self::POLL_INTERVAL_US;
+        }
+        Ok(())
+    }
+}
+
+impl Default for BusyGate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,133 @@
+//! Regional channel plans, after the LoRaWAN Regional Parameters, as
+//! frequencies in Hz for [`SetRfFrequency::from_hz`].
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::channels::{self, eu868, us915};
+//! use sx126x_spi_buffers::commands::SetRfFrequency;
+//!
+//! let set_rf_frequency = SetRfFrequency::from_hz(eu868::DEFAULT[0]);
+//! assert_eq!(set_rf_frequency.tx_buf, [0x86, 0x36, 0x41, 0x99, 0x9A]);
+//!
+//! // Sub-band 2 of the US915 plan, used by most networks
+//! let sub_band = us915::sub_band(2).unwrap();
+//! assert_eq!(sub_band[0], 903_900_000);
+//! assert_eq!(sub_band[7], 905_300_000);
+//! assert_eq!(us915::uplink_500(us915::sub_band_500(2).unwrap()), Some(904_600_000));
+//! assert_eq!(channels::ism433::CHANNELS.len(), 69);
+//! ```
+//!
+//! [`SetRfFrequency::from_hz`]: crate::commands::SetRfFrequency::from_hz
+
+/// Frequency of channel `index` of a plan of `count` channels evenly
+/// spaced from `first_hz`.
+#[inline(always)]
+const fn channel(first_hz: u32, spacing_hz: u32, count: u8, index: u8) -> Option<u32> {
+    if index < count {
+        Some(first_hz + spacing_hz * index as u32)
+    } else {
+        None
+    }
+}
+
+/// Europe, 863 to 870 MHz.
+pub mod eu868 {
+    /// Channels every device supports, at 125 kHz.
+    pub const DEFAULT: [u32; 3] = [868_100_000, 868_300_000, 868_500_000];
+    /// RX2 window and join accept frequency.
+    pub const RX2: u32 = 869_525_000;
+}
+
+/// United States, 902 to 928 MHz.
+pub mod us915 {
+    use super::channel;
+
+    /// 125 kHz uplink channel `index`, 0 to 63: 902.3 MHz + 200 kHz steps.
+    #[inline(always)]
+    pub const fn uplink_125(index: u8) -> Option<u32> {
+        channel(902_300_000, 200_000, 64, index)
+    }
+    /// 500 kHz uplink channel `index`, 0 to 7: 903.0 MHz + 1.6 MHz steps.
+    #[inline(always)]
+    pub const fn uplink_500(index: u8) -> Option<u32> {
+        channel(903_000_000, 1_600_000, 8, index)
+    }
+    /// 500 kHz downlink channel `index`, 0 to 7: 923.3 MHz + 600 kHz steps.
+    #[inline(always)]
+    pub const fn downlink(index: u8) -> Option<u32> {
+        channel(923_300_000, 600_000, 8, index)
+    }
+    /// The eight 125 kHz uplink channels of sub-band 1 to 8.
+    #[inline(always)]
+    pub const fn sub_band(sub_band: u8) -> Option<[u32; 8]> {
+        if sub_band < 1 || sub_band > 8 {
+            return None;
+        }
+        let mut channels = [0; 8];
+        let mut i = 0;
+        while i < 8 {
+            channels[i] = 902_300_000 + 200_000 * ((sub_band as u32 - 1) * 8 + i as u32);
+            i += 1;
+        }
+        Some(channels)
+    }
+    /// Index of the 500 kHz uplink channel of sub-band 1 to 8.
+    #[inline(always)]
+    pub const fn sub_band_500(sub_band: u8) -> Option<u8> {
+        match sub_band {
+            1..=8 => Some(sub_band - 1),
+            _ => None,
+        }
+    }
+    /// RX2 window frequency.
+    pub const RX2: u32 = 923_300_000;
+}
+
+/// Australia, 915 to 928 MHz.
+pub mod au915 {
+    use super::channel;
+
+    /// 125 kHz uplink channel `index`, 0 to 63: 915.2 MHz + 200 kHz steps.
+    #[inline(always)]
+    pub const fn uplink_125(index: u8) -> Option<u32> {
+        channel(915_200_000, 200_000, 64, index)
+    }
+    /// 500 kHz uplink channel `index`, 0 to 7: 915.9 MHz + 1.6 MHz steps.
+    #[inline(always)]
+    pub const fn uplink_500(index: u8) -> Option<u32> {
+        channel(915_900_000, 1_600_000, 8, index)
+    }
+    /// 500 kHz downlink channel `index`, 0 to 7: 923.3 MHz + 600 kHz steps.
+    #[inline(always)]
+    pub const fn downlink(index: u8) -> Option<u32> {
+        channel(923_300_000, 600_000, 8, index)
+    }
+    /// RX2 window frequency.
+    pub const RX2: u32 = 923_300_000;
+}
+
+/// Asia, 915 to 928 MHz, group AS923-1.
+pub mod as923 {
+    /// Channels every device supports, at 125 kHz.
+    pub const DEFAULT: [u32; 2] = [923_200_000, 923_400_000];
+    /// RX2 window frequency.
+    pub const RX2: u32 = 923_200_000;
+}
+
+/// 433 MHz ISM band, 433.05 to 434.79 MHz.
+pub mod ism433 {
+    /// Default LoRaWAN channels of the EU433 plan, at 125 kHz.
+    pub const DEFAULT: [u32; 3] = [433_175_000, 433_375_000, 433_575_000];
+    /// RX2 window frequency of the EU433 plan.
+    pub const RX2: u32 = 434_665_000;
+    /// The 69 channels of 25 kHz of the band, 433.075 MHz + 25 kHz steps.
+    pub const CHANNELS: [u32; 69] = {
+        let mut channels = [0; 69];
+        let mut i = 0;
+        while i < 69 {
+            channels[i] = 433_075_000 + 25_000 * i as u32;
+            i += 1;
+        }
+        channels
+    };
+}
@@ -1,17 +1,998 @@
 //! Generate SPI buffers for Semtech SX126x SPI commands.
 #![allow(clippy::new_without_default)]
 
-use super::registers::Register;
+use super::registers::{RandomNumberGen0, RawRegister, Register, Register16, SyncWord0, addresses};
+#[cfg(feature = "critical-section")]
+use super::signal::TransferSignal;
 use bitfield_struct::bitfield;
 use core::marker::PhantomData;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// # SPI descriptor
+/// One full-duplex SPI transfer, framed by NSS: `transfer_length` bytes are
+/// clocked out of the tx buffer while the same number of bytes is clocked
+/// into the rx buffer. This is the unit handed to a DMA driver.
+///
+/// A descriptor holds raw pointers into its command and borrows nothing.
+/// When obtained from a command's `descriptor()` method, the command must
+/// stay in place and must not be accessed until the transfer completes.
+/// [`SpiDescriptor::new`] takes the command as `&'static mut` instead, which
+/// guarantees both.
+///
+/// Descriptors and commands are `#[repr(C)]`. With the `cache-line-aligned`
+/// feature every command is also aligned to, and padded to a multiple of,
+/// 32 bytes, so cleaning and invalidating the lines covering a command
+/// never touches unrelated data on cached cores such as the Cortex-M7.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{SpiDescriptor, SetStandby, StdbyConfig};
+///
+/// // In firmware this would typically come from a `StaticCell`.
+/// let set_standby: &'static mut SetStandby =
+///     Box::leak(Box::new(SetStandby::new(StdbyConfig::StdbyXosc)));
+/// let desc = SpiDescriptor::new(set_standby);
+/// assert_eq!(desc.transfer_length(), 2);
+/// assert_eq!(unsafe { *desc.tx_buf_ptr() }, 0x80);
+/// ```
+///
+/// ## Completion callback
+/// ```
+/// use core::sync::atomic::{AtomicU16, Ordering};
+/// use sx126x_spi_buffers::commands::{SpiDescriptor, GetIrqStatus, TransferStatus};
+///
+/// static IRQ_STATUS: AtomicU16 = AtomicU16::new(0);
+///
+/// fn on_irq_status(desc: &mut SpiDescriptor) {
+///     let rx_buf = unsafe { desc.rx_slice_mut() };
+///     IRQ_STATUS.store(u16::from_be_bytes([rx_buf[2], rx_buf[3]]), Ordering::Relaxed);
+/// }
+///
+/// let get_irq_status: &'static mut GetIrqStatus = Box::leak(Box::new(GetIrqStatus::new()));
+/// let mut desc = SpiDescriptor::new(get_irq_status).with_on_complete(on_irq_status);
+/// unsafe { *desc.rx_buf_ptr().add(3) = 0x02 }; // filled in by DMA
+/// desc.complete();
+/// assert_eq!(IRQ_STATUS.load(Ordering::Relaxed), 0x0002);
+/// assert_eq!(desc.status(), TransferStatus::Done);
+/// ```
+///
+/// ## Correlation ID
+/// ```
+/// use sx126x_spi_buffers::commands::{SpiDescriptor, GetIrqStatus};
+///
+/// let first: &'static mut GetIrqStatus = Box::leak(Box::new(GetIrqStatus::new()));
+/// let second: &'static mut GetIrqStatus = Box::leak(Box::new(GetIrqStatus::new()));
+/// let in_flight = [
+///     SpiDescriptor::new(first).with_id(41),
+///     SpiDescriptor::new(second).with_id(42),
+/// ];
+/// // DMA ISR hands back the completed descriptor
+/// let completed = in_flight[1];
+/// assert_eq!(completed.id(), Some(42));
+/// ```
+///
+/// ## Multiple radios
+/// ```
+/// use sx126x_spi_buffers::commands::{SpiDescriptor, GetStatus};
+///
+/// let get_status: &'static mut GetStatus = Box::leak(Box::new(GetStatus::new()));
+/// let desc = SpiDescriptor::new(get_status).with_radio(1);
+/// let nss_pins = ["PA4", "PB12"];
+/// assert_eq!(nss_pins[desc.radio() as usize], "PB12");
+/// ```
+#[repr(C)]
+#[derive(Copy, Clone, Debug)]
 pub struct SpiDescriptor {
-    pub tx_buf_ptr: *const u8,
-    pub rx_buf_ptr: *mut u8,
-    pub transfer_length: u16,
+    tx_buf_ptr: *const u8,
+    rx_buf_ptr: *mut u8,
+    transfer_length: u16,
+    payload_ptr: *const u8,
+    payload_length: u16,
+    busy_timeout_us: u32,
+    on_complete: Option<fn(&mut SpiDescriptor)>,
+    id: Option<u16>,
+    radio: u8,
+    tx_only: bool,
+    status: TransferStatus,
+    deferred: Option<DeferredLength>,
+    #[cfg(feature = "critical-section")]
+    signal: Option<&'static TransferSignal>,
+}
+impl SpiDescriptor {
+    #[inline(always)]
+    pub fn new<C: AnyCommand + ?Sized>(command: &'static mut C) -> Self {
+        Self::from_command(command)
+    }
+    /// Like [`new`](Self::new) for a command that is only borrowed; the
+    /// caller has to finish the transfer while the borrow lasts.
+    #[inline(always)]
+    pub(crate) fn from_command<C: AnyCommand + ?Sized>(command: &mut C) -> Self {
+        let tx_buf_ptr = command.tx_bytes().as_ptr();
+        let transfer_length = command.transfer_len();
+        let busy_timeout_us = command.busy_timeout_us();
+        let rx_buf = command.rx_bytes_mut();
+        let rx_buf_ptr = if rx_buf.is_empty() {
+            core::ptr::null_mut()
+        } else {
+            rx_buf.as_mut_ptr()
+        };
+        Self::raw(tx_buf_ptr, rx_buf_ptr, transfer_length).with_busy_timeout_us(busy_timeout_us)
+    }
+    /// A null `rx_buf_ptr` makes a TX-only descriptor.
+    #[inline(always)]
+    const fn raw(tx_buf_ptr: *const u8, rx_buf_ptr: *mut u8, transfer_length: u16) -> Self {
+        let tx_only = rx_buf_ptr.is_null();
+        Self {
+            tx_buf_ptr,
+            rx_buf_ptr: match tx_only {
+                true => scratch_rx_ptr(transfer_length),
+                false => rx_buf_ptr,
+            },
+            transfer_length,
+            payload_ptr: core::ptr::null(),
+            payload_length: 0,
+            busy_timeout_us: DEFAULT_BUSY_TIMEOUT_US,
+            on_complete: None,
+            id: None,
+            radio: 0,
+            tx_only,
+            status: TransferStatus::Pending,
+            deferred: None,
+            #[cfg(feature = "critical-section")]
+            signal: None,
+        }
+    }
+    /// Attaches a callback for the executor to run through
+    /// [`complete`](Self::complete) once the transfer has finished, e.g. to
+    /// decode the response or queue the next command from the DMA ISR.
+    #[inline(always)]
+    pub const fn with_on_complete(mut self, on_complete: fn(&mut SpiDescriptor)) -> Self {
+        self.on_complete = Some(on_complete);
+        self
+    }
+    /// Overrides the expected maximum BUSY duration, e.g. for a SetTx or SetRx
+    /// whose TCXO start-up delay is only known at runtime.
+    #[inline(always)]
+    pub const fn with_busy_timeout_us(mut self, busy_timeout_us: u32) -> Self {
+        self.busy_timeout_us = busy_timeout_us;
+        self
+    }
+    /// Longest time in microseconds BUSY is expected to stay high after this
+    /// transfer, see [`Command::BUSY_TIMEOUT_US`].
+    #[inline(always)]
+    pub const fn busy_timeout_us(&self) -> u32 {
+        self.busy_timeout_us
+    }
+    /// Tags the descriptor with a sequence or correlation ID, so a completed
+    /// transfer can be matched to the request that produced it.
+    #[inline(always)]
+    pub const fn with_id(mut self, id: u16) -> Self {
+        self.id = Some(id);
+        self
+    }
+    #[inline(always)]
+    pub const fn id(&self) -> Option<u16> {
+        self.id
+    }
+    /// Tags the descriptor with the radio it is meant for, so descriptors for
+    /// several SX126x on one SPI bus can share a queue and the executor can
+    /// pick the chip select (and BUSY pin) by index. Defaults to 0.
+    #[inline(always)]
+    pub const fn with_radio(mut self, radio: u8) -> Self {
+        self.radio = radio;
+        self
+    }
+    #[inline(always)]
+    pub const fn radio(&self) -> u8 {
+        self.radio
+    }
+    /// Whether the transfer length is only known once
+    /// [`resolve_length`](Self::resolve_length) has run, see
+    /// [`ReadBuffer::deferred_descriptor`].
+    #[inline(always)]
+    pub const fn is_deferred(&self) -> bool {
+        self.deferred.is_some()
+    }
+    /// Fills in the transfer length and buffer offset of a deferred
+    /// descriptor from the GetRxBufferStatus response it was built with.
+    /// Does nothing for other descriptors. Called by the executor when the
+    /// descriptor is dequeued, after the GetRxBufferStatus transfer has
+    /// completed.
+    ///
+    /// # Safety
+    /// The commands the descriptor was built from must still be in place
+    /// and not be accessed concurrently.
+    pub unsafe fn resolve_length(&mut self) {
+        if let Some(deferred) = self.deferred {
+            // SAFETY: upheld by the caller.
+            let data_length = unsafe {
+                let payload_length_rx = deferred.rx_buffer_status.add(2).read_volatile();
+                let rx_start_buffer_pointer = deferred.rx_buffer_status.add(3).read_volatile();
+                let data_length = (payload_length_rx as u16).min(deferred.max_data_length);
+                deferred.offset.write_volatile(rx_start_buffer_pointer);
+                deferred.data_length.write_volatile(data_length);
+                data_length
+            };
+            self.transfer_length = data_length + 3;
+        }
+    }
+    /// Attaches a [`TransferSignal`] that [`complete`](Self::complete) sets,
+    /// waking the task awaiting this transfer.
+    #[cfg(feature = "critical-section")]
+    #[inline(always)]
+    pub const fn with_signal(mut self, signal: &'static TransferSignal) -> Self {
+        self.signal = Some(signal);
+        self
+    }
+    #[inline(always)]
+    pub const fn status(&self) -> TransferStatus {
+        self.status
+    }
+    /// Records the progress of the transfer. Called by the executor, e.g.
+    /// with [`TransferStatus::InFlight`] when the DMA is started or
+    /// [`TransferStatus::SpiError`] when it fails.
+    #[inline(always)]
+    pub const fn set_status(&mut self, status: TransferStatus) {
+        self.status = status;
+    }
+    /// Marks the transfer as [`Done`](TransferStatus::Done) unless an error
+    /// has been recorded, runs the completion callback, if any, then sets
+    /// the attached signal. Called by the executor after the transfer has
+    /// finished.
+    #[inline(always)]
+    pub fn complete(&mut self) {
+        if !self.status.is_error() {
+            self.status = TransferStatus::Done;
+        }
+        if let Some(on_complete) = self.on_complete {
+            on_complete(self);
+        }
+        #[cfg(feature = "critical-section")]
+        if let Some(signal) = self.signal {
+            signal.signal();
+        }
+    }
+    #[inline(always)]
+    pub const fn tx_buf_ptr(&self) -> *const u8 {
+        self.tx_buf_ptr
+    }
+    /// Null for TX-only descriptors, whose received bytes are discarded;
+    /// with the `scratch-rx` feature, the shared scratch buffer instead.
+    #[inline(always)]
+    pub const fn rx_buf_ptr(&self) -> *mut u8 {
+        self.rx_buf_ptr
+    }
+    /// Drops the receive side, so drivers that support TX-only DMA can skip
+    /// it. Useful for write-only commands whose response is never read.
+    #[inline(always)]
+    pub const fn tx_only(mut self) -> Self {
+        self.rx_buf_ptr = scratch_rx_ptr(self.transfer_length);
+        self.tx_only = true;
+        self
+    }
+    /// Whether the received bytes are discarded, see
+    /// [`rx_buf_ptr`](Self::rx_buf_ptr).
+    #[inline(always)]
+    pub const fn is_tx_only(&self) -> bool {
+        self.tx_only
+    }
+    #[inline(always)]
+    pub const fn transfer_length(&self) -> u16 {
+        self.transfer_length
+    }
+    /// Second, TX-only region clocked out after the first one with NSS still
+    /// asserted, see [`WriteBufferHeader::scatter_descriptor`]. Null if the
+    /// descriptor has a single region.
+    #[inline(always)]
+    pub const fn payload_ptr(&self) -> *const u8 {
+        self.payload_ptr
+    }
+    #[inline(always)]
+    pub const fn payload_length(&self) -> u16 {
+        self.payload_length
+    }
+    /// The [`transfer_length`](Self::transfer_length) bytes clocked out
+    /// first.
+    ///
+    /// # Safety
+    /// The command the descriptor was built from must still be in place, and
+    /// its tx buffer must not be written while the slice is alive.
+    #[inline(always)]
+    pub const unsafe fn tx_slice(&self) -> &[u8] {
+        // SAFETY: upheld by the caller.
+        unsafe { core::slice::from_raw_parts(self.tx_buf_ptr, self.transfer_length as usize) }
+    }
+    /// The second tx region, empty if the descriptor has a single region.
+    ///
+    /// # Safety
+    /// Same as [`tx_slice`](Self::tx_slice), for the payload.
+    #[inline(always)]
+    pub const unsafe fn payload_slice(&self) -> &[u8] {
+        match self.payload_ptr.is_null() {
+            true => &[],
+            // SAFETY: upheld by the caller.
+            false => unsafe {
+                core::slice::from_raw_parts(self.payload_ptr, self.payload_length as usize)
+            },
+        }
+    }
+    /// The [`transfer_length`](Self::transfer_length) bytes received, empty
+    /// for TX-only descriptors.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::{GetStatus, SetStandby, StdbyConfig};
+    ///
+    /// let mut get_status = GetStatus::new();
+    /// let mut desc = get_status.descriptor();
+    /// unsafe {
+    ///     assert_eq!(desc.tx_slice(), [0xC0, 0]);
+    ///     desc.rx_slice_mut().copy_from_slice(&[0xA2, 0x22]); // DMA
+    /// }
+    /// assert_eq!(get_status.rx_buf, [0xA2, 0x22]);
+    ///
+    /// let mut set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+    /// let mut desc = set_standby.descriptor().tx_only();
+    /// assert!(unsafe { desc.rx_slice_mut() }.is_empty());
+    /// ```
+    ///
+    /// # Safety
+    /// The command the descriptor was built from must still be in place, and
+    /// its rx buffer must not be accessed elsewhere while the slice is alive.
+    #[inline(always)]
+    pub const unsafe fn rx_slice_mut(&mut self) -> &mut [u8] {
+        match self.tx_only {
+            true => &mut [],
+            // SAFETY: upheld by the caller.
+            false => unsafe {
+                core::slice::from_raw_parts_mut(self.rx_buf_ptr, self.transfer_length as usize)
+            },
+        }
+    }
+    /// Passes the bytes about to be clocked out to the hook installed with
+    /// [`trace::set_tx_hook`](crate::trace::set_tx_hook), once for each tx
+    /// region. Called by the executor right before the transfer.
+    ///
+    /// # Safety
+    /// The buffers the descriptor points to must still be in place and not
+    /// be written concurrently.
+    #[cfg(feature = "trace")]
+    pub unsafe fn trace_tx(&self) {
+        if let Some(hook) = crate::trace::tx_hook() {
+            // SAFETY: upheld by the caller.
+            unsafe {
+                hook(self, self.tx_slice());
+                if !self.payload_ptr.is_null() {
+                    hook(self, self.payload_slice());
+                }
+            }
+        }
+    }
+    /// Passes the received bytes to the hook installed with
+    /// [`trace::set_rx_hook`](crate::trace::set_rx_hook). Does nothing for
+    /// TX-only descriptors. Called by the executor right after the transfer.
+    ///
+    /// # Safety
+    /// Same as [`trace_tx`](Self::trace_tx).
+    #[cfg(feature = "trace")]
+    pub unsafe fn trace_rx(&self) {
+        if let Some(hook) = crate::trace::rx_hook()
+            && !self.tx_only
+        {
+            // SAFETY: upheld by the caller.
+            let rx_buf = unsafe {
+                core::slice::from_raw_parts(self.rx_buf_ptr, self.transfer_length as usize)
+            };
+            hook(self, rx_buf);
+        }
+    }
+}
+
+/// Size of the buffer shared by TX-only descriptors with the `scratch-rx`
+/// feature, enough for a 255-byte WriteBuffer. Longer TX-only transfers
+/// keep a null rx pointer.
+pub const SCRATCH_RX_LENGTH: usize = 258;
+
+/// Sink for the bytes received by TX-only transfers, never read.
+#[cfg(feature = "scratch-rx")]
+static mut SCRATCH_RX: [u8; SCRATCH_RX_LENGTH] = [0; SCRATCH_RX_LENGTH];
+
+/// rx pointer of a TX-only descriptor of `transfer_length` bytes.
+#[inline(always)]
+const fn scratch_rx_ptr(transfer_length: u16) -> *mut u8 {
+    #[cfg(feature = "scratch-rx")]
+    if transfer_length as usize <= SCRATCH_RX_LENGTH {
+        return (&raw mut SCRATCH_RX).cast();
+    }
+    let _ = transfer_length;
+    core::ptr::null_mut()
+}
+
+// SAFETY: a descriptor only carries addresses; dereferencing them is already
+// subject to the command outliving the transfer, whichever context runs it.
+unsafe impl Send for SpiDescriptor {}
+
+// The status is progress, not part of the transfer, and is left out.
+impl PartialEq for SpiDescriptor {
+    fn eq(&self, other: &Self) -> bool {
+        self.tx_buf_ptr == other.tx_buf_ptr
+            && self.rx_buf_ptr == other.rx_buf_ptr
+            && self.transfer_length == other.transfer_length
+            && self.payload_ptr == other.payload_ptr
+            && self.payload_length == other.payload_length
+            && self.busy_timeout_us == other.busy_timeout_us
+            && self.id == other.id
+            && self.radio == other.radio
+            && self.tx_only == other.tx_only
+            && self.deferred == other.deferred
+            && match (self.on_complete, other.on_complete) {
+                (Some(a), Some(b)) => core::ptr::fn_addr_eq(a, b),
+                (a, b) => a.is_none() && b.is_none(),
+            }
+            && self.signal_eq(other)
+    }
+}
+impl SpiDescriptor {
+    #[cfg(feature = "critical-section")]
+    fn signal_eq(&self, other: &Self) -> bool {
+        match (self.signal, other.signal) {
+            (Some(a), Some(b)) => core::ptr::eq(a, b),
+            (a, b) => a.is_none() && b.is_none(),
+        }
+    }
+    #[cfg(not(feature = "critical-section"))]
+    fn signal_eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+impl Eq for SpiDescriptor {}
+
+/// Progress of the transfer described by a [`SpiDescriptor`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferStatus {
+    /// Not started yet.
+    Pending,
+    /// Handed to the SPI peripheral.
+    InFlight,
+    /// Transferred successfully.
+    Done,
+    /// The SPI peripheral or DMA reported an error, or BUSY timed out.
+    SpiError,
+    /// The transfer completed but the chip rejected the command, e.g. its
+    /// status reported a command processing error or failure to execute.
+    ChipError,
+}
+impl TransferStatus {
+    #[inline(always)]
+    pub const fn is_error(&self) -> bool {
+        matches!(self, Self::SpiError | Self::ChipError)
+    }
+}
+
+/// Where a deferred ReadBuffer descriptor takes its length and offset from.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct DeferredLength {
+    rx_buffer_status: *const u8,
+    offset: *mut u8,
+    data_length: *mut u16,
+    max_data_length: u16,
+}
+
+/// BUSY timeout for commands that complete within a few microseconds.
+pub const DEFAULT_BUSY_TIMEOUT_US: u32 = 1_000;
+
+/// # Command
+/// An SX126x command laid out as a pair of equally sized SPI buffers.
+/// Implemented by every command in this module. Commands are `Clone` but
+/// not `Copy`, so the buffers a descriptor points to are never duplicated
+/// behind its back.
+pub trait Command {
+    /// Size of the tx and rx buffers in bytes.
+    const LENGTH: usize;
+    /// Longest time in microseconds BUSY is expected to stay high after the
+    /// command, so an executor can time out instead of hanging on a stuck
+    /// radio. 0 means BUSY is not expected to fall at all (SetSleep) and
+    /// the executor must not wait for it.
+    const BUSY_TIMEOUT_US: u32 = DEFAULT_BUSY_TIMEOUT_US;
+
+    fn tx_buf(&self) -> &[u8];
+    /// Empty for TX-only commands such as [`TxOnly`].
+    fn rx_buf_mut(&mut self) -> &mut [u8];
+    /// Number of bytes to transfer, at most [`Self::LENGTH`].
+    fn transfer_length(&self) -> u16;
+    /// [`BUSY_TIMEOUT_US`](Self::BUSY_TIMEOUT_US), unless it depends on the
+    /// instance, as for a [`TxOnly`].
+    #[inline(always)]
+    fn busy_timeout_us(&self) -> u32 {
+        Self::BUSY_TIMEOUT_US
+    }
+}
+
+/// # AnyCommand
+/// Object-safe view of a [`Command`], implemented by every command, so that
+/// commands of different types can be stored together and run by the same
+/// executor.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{AnyCommand, GetStatus, SetRfFrequency, SetStandby, StdbyConfig};
+///
+/// let mut set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+/// let mut set_rf_frequency = SetRfFrequency::from_hz(868_100_000);
+/// let mut get_status = GetStatus::new();
+/// let commands: [&mut dyn AnyCommand; 3] = [&mut set_standby, &mut set_rf_frequency, &mut get_status];
+///
+/// assert_eq!(commands.iter().map(|command| command.transfer_len()).sum::<u16>(), 9);
+/// assert_eq!(commands[1].tx_bytes(), [0x86, 0x36, 0x41, 0x99, 0x9A]);
+/// assert_eq!(commands[2].buffer_len(), 2);
+/// ```
+pub trait AnyCommand {
+    /// [`Command::tx_buf`].
+    fn tx_bytes(&self) -> &[u8];
+    /// [`Command::rx_buf_mut`].
+    fn rx_bytes_mut(&mut self) -> &mut [u8];
+    /// [`Command::transfer_length`].
+    fn transfer_len(&self) -> u16;
+    /// [`Command::LENGTH`].
+    fn buffer_len(&self) -> usize;
+    /// [`Command::busy_timeout_us`].
+    fn busy_timeout_us(&self) -> u32;
+}
+impl<C: Command> AnyCommand for C {
+    #[inline(always)]
+    fn tx_bytes(&self) -> &[u8] {
+        self.tx_buf()
+    }
+    #[inline(always)]
+    fn rx_bytes_mut(&mut self) -> &mut [u8] {
+        self.rx_buf_mut()
+    }
+    #[inline(always)]
+    fn transfer_len(&self) -> u16 {
+        self.transfer_length()
+    }
+    #[inline(always)]
+    fn buffer_len(&self) -> usize {
+        C::LENGTH
+    }
+    #[inline(always)]
+    fn busy_timeout_us(&self) -> u32 {
+        Command::busy_timeout_us(self)
+    }
+}
+
+/// # Response
+/// A command that reads data back from the radio, with its rx buffer
+/// parsed into [`Output`](Self::Output), so that executors can return typed
+/// results for any of them.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{GetRxBufferStatus, GetStatsLora, LoraStats, Response, RxBufferStatus};
+///
+/// let mut get_rx_buffer_status = GetRxBufferStatus::new();
+/// get_rx_buffer_status.rx_buf = [0xA2, 0xA2, 16, 8];
+/// assert_eq!(get_rx_buffer_status.parse(), RxBufferStatus { payload_length_rx: 16, rx_start_buffer_pointer: 8 });
+///
+/// let mut get_stats_lora = GetStatsLora::new();
+/// get_stats_lora.rx_buf[2..].copy_from_slice(&[0, 12, 0, 1, 0, 2]);
+/// let stats: LoraStats = get_stats_lora.parse();
+/// assert_eq!((stats.nb_pkt_received, stats.nb_pkt_crc_error, stats.nb_pkt_header_err), (12, 1, 2));
+/// ```
+pub trait Response: Command {
+    type Output;
+
+    /// Parses the rx buffer, once the command has been transferred.
+    fn parse(&self) -> Self::Output;
+}
+
+/// Error of the `TryFrom<u8>` conversions of the parameter enums: the value
+/// is a reserved encoding or does not fit the field.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{Bw, InvalidValue, Sf};
+///
+/// assert_eq!(Sf::try_from(0x07), Ok(Sf::Sf7));
+/// assert_eq!(Sf::try_from(0x0D), Err(InvalidValue(0x0D)));
+/// assert_eq!(Sf::from_bits_truncate(0x0D), Sf::Reserved6);
+/// assert_eq!(Bw::try_from(0x14), Err(InvalidValue(0x14)));
+/// assert_eq!(Bw::from_bits_truncate(0x14), Bw::Bw125);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct InvalidValue(pub u8);
+
+/// Errors of the validating constructors and builders of this module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// A parameter has a reserved encoding.
+    Reserved,
+    /// SF6 was combined with a variable length header.
+    ImplicitHeaderRequired,
+    /// The data does not fit the buffer of the command.
+    PayloadTooLong,
+    /// A parameter is outside the range accepted by the chip.
+    OutOfRange,
+}
+
+/// Implements `TryFrom<u8>` accepting the encodings of the listed variants.
+macro_rules! impl_try_from_u8 {
+    ($enum:ident: $($variant:ident),* $(,)?) => {
+        impl TryFrom<u8> for $enum {
+            type Error = InvalidValue;
+
+            fn try_from(value: u8) -> Result<Self, InvalidValue> {
+                $(
+                    if value == Self::$variant as u8 {
+                        return Ok(Self::$variant);
+                    }
+                )*
+                Err(InvalidValue(value))
+            }
+        }
+    };
+}
+
+/// # Opcode
+/// Every SX126x command opcode, e.g. to label captured SPI traffic.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{InvalidValue, Opcode, SetRfFrequency};
+///
+/// let set_rf_frequency = SetRfFrequency::from_hz(868_100_000);
+/// assert_eq!(Opcode::from_frame(&set_rf_frequency.tx_buf), Some(Opcode::SetRfFrequency));
+/// assert_eq!(Opcode::try_from(0x8B), Ok(Opcode::SetModulationParams));
+/// assert_eq!(Opcode::try_from(0xFF), Err(InvalidValue(0xFF)));
+/// assert_eq!(u8::from(Opcode::GetStatus), 0xC0);
+/// ```
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Opcode {
+    SetSleep = 0x84,
+    SetStandby = 0x80,
+    SetFs = 0xC1,
+    SetTx = 0x83,
+    SetRx = 0x82,
+    StopTimerOnPreamble = 0x9F,
+    SetRxDutyCycle = 0x94,
+    SetCad = 0xC5,
+    SetTxContinuousWave = 0xD1,
+    SetTxInfinitePreamble = 0xD2,
+    SetRegulatorMode = 0x96,
+    Calibrate = 0x89,
+    CalibrateImage = 0x98,
+    SetPaConfig = 0x95,
+    SetRxTxFallbackMode = 0x93,
+    WriteRegister = 0x0D,
+    ReadRegister = 0x1D,
+    WriteBuffer = 0x0E,
+    ReadBuffer = 0x1E,
+    SetDioIrqParams = 0x08,
+    GetIrqStatus = 0x12,
+    ClearIrqStatus = 0x02,
+    SetDio2AsRfSwitchCtrl = 0x9D,
+    SetDio3AsTcxoCtrl = 0x97,
+    SetRfFrequency = 0x86,
+    SetPacketType = 0x8A,
+    GetPacketType = 0x11,
+    SetTxParams = 0x8E,
+    SetModulationParams = 0x8B,
+    SetPacketParams = 0x8C,
+    SetCadParams = 0x88,
+    SetBufferBaseAddress = 0x8F,
+    SetLoraSymbNumTimeout = 0xA0,
+    GetStatus = 0xC0,
+    GetRssiInst = 0x15,
+    GetRxBufferStatus = 0x13,
+    GetPacketStatus = 0x14,
+    GetDeviceErrors = 0x17,
+    ClearDeviceErrors = 0x07,
+    GetStats = 0x10,
+    ResetStats = 0x00,
+}
+impl Opcode {
+    /// Opcode of the command in the first byte of `frame`.
+    #[inline(always)]
+    pub fn from_frame(frame: &[u8]) -> Option<Self> {
+        Self::try_from(*frame.first()?).ok()
+    }
+}
+impl_try_from_u8!(Opcode: SetSleep,
+    SetStandby,
+    SetFs,
+    SetTx,
+    SetRx,
+    StopTimerOnPreamble,
+    SetRxDutyCycle,
+    SetCad,
+    SetTxContinuousWave,
+    SetTxInfinitePreamble,
+    SetRegulatorMode,
+    Calibrate,
+    CalibrateImage,
+    SetPaConfig,
+    SetRxTxFallbackMode,
+    WriteRegister,
+    ReadRegister,
+    WriteBuffer,
+    ReadBuffer,
+    SetDioIrqParams,
+    GetIrqStatus,
+    ClearIrqStatus,
+    SetDio2AsRfSwitchCtrl,
+    SetDio3AsTcxoCtrl,
+    SetRfFrequency,
+    SetPacketType,
+    GetPacketType,
+    SetTxParams,
+    SetModulationParams,
+    SetPacketParams,
+    SetCadParams,
+    SetBufferBaseAddress,
+    SetLoraSymbNumTimeout,
+    GetStatus,
+    GetRssiInst,
+    GetRxBufferStatus,
+    GetPacketStatus,
+    GetDeviceErrors,
+    ClearDeviceErrors,
+    GetStats,
+    ResetStats);
+impl From<Opcode> for u8 {
+    #[inline(always)]
+    fn from(opcode: Opcode) -> u8 {
+        opcode as u8
+    }
+}
+
+/// Const `union`, `intersection` and `complement` for a flag register with
+/// an `ALL` mask, and the matching `|`, `&` and `!` operators. Reserved bits
+/// stay clear. Also converts to and from the MSB-first bytes of the SPI
+/// frames.
+macro_rules! impl_flag_ops {
+    ($flags:ident) => {
+        impl $flags {
+            /// From the two bytes of a response, MSB first.
+            #[inline(always)]
+            pub const fn from_be_bytes(bytes: [u8; 2]) -> Self {
+                Self::from_bits(u16::from_be_bytes(bytes))
+            }
+            /// The two bytes of a command parameter, MSB first.
+            #[inline(always)]
+            pub const fn to_be_bytes(self) -> [u8; 2] {
+                self.into_bits().to_be_bytes()
+            }
+            #[inline(always)]
+            pub const fn union(self, other: Self) -> Self {
+                Self::from_bits(self.into_bits() | other.into_bits())
+            }
+            #[inline(always)]
+            pub const fn intersection(self, other: Self) -> Self {
+                Self::from_bits(self.into_bits() & other.into_bits())
+            }
+            /// The flags of `ALL` not set in `self`.
+            #[inline(always)]
+            pub const fn complement(self) -> Self {
+                Self::from_bits(!self.into_bits() & Self::ALL.into_bits())
+            }
+        }
+        impl core::ops::BitOr for $flags {
+            type Output = Self;
+
+            #[inline(always)]
+            fn bitor(self, other: Self) -> Self {
+                self.union(other)
+            }
+        }
+        impl core::ops::BitOrAssign for $flags {
+            #[inline(always)]
+            fn bitor_assign(&mut self, other: Self) {
+                *self = self.union(other);
+            }
+        }
+        impl core::ops::BitAnd for $flags {
+            type Output = Self;
+
+            #[inline(always)]
+            fn bitand(self, other: Self) -> Self {
+                self.intersection(other)
+            }
+        }
+        impl core::ops::BitAndAssign for $flags {
+            #[inline(always)]
+            fn bitand_assign(&mut self, other: Self) {
+                *self = self.intersection(other);
+            }
+        }
+        impl core::ops::Not for $flags {
+            type Output = Self;
+
+            #[inline(always)]
+            fn not(self) -> Self {
+                self.complement()
+            }
+        }
+    };
+}
+
+/// Adds `descriptor` to a command and implements [`Command`] for it, with
+/// the buffer length written once. The whole buffers are transferred unless
+/// `transfer_length` computes a shorter length from the command, and BUSY
+/// is given `BUSY_TIMEOUT_US` unless `busy_timeout_us` computes it from the
+/// parameters.
+macro_rules! impl_command {
+    ($command:ident<const $n:ident>: $($rest:tt)*) => {
+        impl_command!(@impl [const $n: usize] [$command<$n>] $($rest)*);
+    };
+    ($command:ident<$param:ident>: $($rest:tt)*) => {
+        impl_command!(@impl [$param] [$command<$param>] $($rest)*);
+    };
+    ($command:ident: $($rest:tt)*) => {
+        impl_command!(@impl [] [$command] $($rest)*);
+    };
+    (
+        @impl [$($generics:tt)*] [$type:ty] $length:expr
+        $(, BUSY_TIMEOUT_US = $busy_timeout_us:expr)?
+        $(, busy_timeout_us = |$command:ident| $instance_busy_timeout_us:expr)?
+        $(, transfer_length = |$this:ident| $transfer_length:expr)?
+    ) => {
+        impl<$($generics)*> $type {
+            #[inline(always)]
+            pub const fn descriptor(&mut self) -> SpiDescriptor {
+                let transfer_length = impl_command!(@transfer_length self, $length $(, |$this| $transfer_length)?);
+                SpiDescriptor::raw(self.tx_buf.as_ptr(), self.rx_buf.as_mut_ptr(), transfer_length)
+                    .with_busy_timeout_us(self.busy_timeout_us())
+            }
+            /// See [`Command::BUSY_TIMEOUT_US`].
+            #[inline(always)]
+            pub const fn busy_timeout_us(&self) -> u32 {
+                impl_command!(@busy_timeout_us self $(, |$command| $instance_busy_timeout_us)?)
+            }
+        }
+        impl<$($generics)*> Command for $type {
+            const LENGTH: usize = $length;
+            $(const BUSY_TIMEOUT_US: u32 = $busy_timeout_us;)?
+
+            #[inline(always)]
+            fn tx_buf(&self) -> &[u8] {
+                let tx_buf: &[u8; $length] = &self.tx_buf;
+                tx_buf
+            }
+            #[inline(always)]
+            fn rx_buf_mut(&mut self) -> &mut [u8] {
+                let rx_buf: &mut [u8; $length] = &mut self.rx_buf;
+                rx_buf
+            }
+            #[inline(always)]
+            fn transfer_length(&self) -> u16 {
+                impl_command!(@transfer_length self, $length $(, |$this| $transfer_length)?)
+            }
+            #[inline(always)]
+            fn busy_timeout_us(&self) -> u32 {
+                <$type>::busy_timeout_us(self)
+            }
+        }
+    };
+    (@busy_timeout_us $self:ident) => {
+        <Self as Command>::BUSY_TIMEOUT_US
+    };
+    (@busy_timeout_us $self:ident, |$command:ident| $busy_timeout_us:expr) => {{
+        let $command = &*$self;
+        $busy_timeout_us
+    }};
+    (@transfer_length $self:ident, $length:expr) => {
+        $length as u16
+    };
+    (@transfer_length $self:ident, $length:expr, |$this:ident| $transfer_length:expr) => {{
+        let $this = &*$self;
+        $transfer_length
+    }};
+}
+
+/// # TxOnly
+/// The tx buffer and BUSY timeout of a write-only command without the
+/// matching rx buffer, halving its RAM usage. Its descriptors are TX-only.
+/// `to_tx_only` converts any [`WriteOnly`] command, in const contexts too.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{SetSleep, SetStandby, SleepConfig, StdbyConfig, TxOnly};
+///
+/// static mut SET_STANDBY: TxOnly<2> = SetStandby::new(StdbyConfig::StdbyXosc).to_tx_only();
+///
+/// let set_standby = unsafe { &mut *core::ptr::addr_of_mut!(SET_STANDBY) };
+/// assert_eq!(set_standby.tx_buf, [0x80, 0x01]);
+/// assert_eq!(set_standby.descriptor().transfer_length(), 2);
+/// assert_eq!(set_standby.descriptor().busy_timeout_us(), 5_000);
+/// assert!(set_standby.descriptor().is_tx_only());
+///
+/// let mut set_sleep = SetSleep::new(SleepConfig::new()).to_tx_only();
+/// assert_eq!(set_sleep.descriptor().busy_timeout_us(), 0);
+/// assert_eq!(TxOnly::new([0x80, 0x00]).busy_timeout_us(), 1_000);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxOnly<const N: usize> {
+    pub tx_buf: [u8; N],
+    busy_timeout_us: u32,
+}
+impl<const N: usize> TxOnly<N> {
+    /// Wraps a raw tx buffer with the default BUSY timeout; `to_tx_only`
+    /// keeps the timeout of the command.
+    #[inline(always)]
+    pub const fn new(tx_buf: [u8; N]) -> Self {
+        Self {
+            tx_buf,
+            busy_timeout_us: DEFAULT_BUSY_TIMEOUT_US,
+        }
+    }
+    #[inline(always)]
+    pub const fn with_busy_timeout_us(mut self, busy_timeout_us: u32) -> Self {
+        self.busy_timeout_us = busy_timeout_us;
+        self
+    }
+    #[inline(always)]
+    pub const fn busy_timeout_us(&self) -> u32 {
+        self.busy_timeout_us
+    }
+    #[inline(always)]
+    pub const fn descriptor(&mut self) -> SpiDescriptor {
+        SpiDescriptor::raw(self.tx_buf.as_ptr(), core::ptr::null_mut(), N as u16)
+            .with_busy_timeout_us(self.busy_timeout_us)
+    }
+}
+impl<const N: usize> WriteOnly for TxOnly<N> {}
+impl<const N: usize> Command for TxOnly<N> {
+    const LENGTH: usize = N;
+
+    #[inline(always)]
+    fn tx_buf(&self) -> &[u8] {
+        &self.tx_buf
+    }
+    #[inline(always)]
+    fn rx_buf_mut(&mut self) -> &mut [u8] {
+        &mut []
+    }
+    #[inline(always)]
+    fn transfer_length(&self) -> u16 {
+        N as u16
+    }
+    #[inline(always)]
+    fn busy_timeout_us(&self) -> u32 {
+        self.busy_timeout_us
+    }
 }
 
+/// # WriteOnly
+/// Marker for commands whose rx buffer never holds anything but status
+/// bytes, so they can be stored as a [`TxOnly`] instead. WriteBuffer is
+/// left out: its transfer length follows the payload, not the buffer.
+pub trait WriteOnly: Command {}
+
+/// Implements [`WriteOnly`] and adds `to_tx_only`.
+macro_rules! impl_write_only {
+    ($command:ident<const $n:ident>) => {
+        impl<const $n: usize> WriteOnly for $command<$n> {}
+        impl<const $n: usize> $command<$n> {
+            /// Copies the command into a [`TxOnly`], BUSY timeout included.
+            #[inline(always)]
+            pub const fn to_tx_only(&self) -> TxOnly<$n> {
+                TxOnly::new(self.tx_buf).with_busy_timeout_us(self.busy_timeout_us())
+            }
+        }
+    };
+    ($command:ident) => {
+        impl WriteOnly for $command {}
+        impl $command {
+            /// Copies the command into a [`TxOnly`], BUSY timeout included.
+            #[inline(always)]
+            pub const fn to_tx_only(&self) -> TxOnly<{ <$command as Command>::LENGTH }> {
+                TxOnly::new(self.tx_buf).with_busy_timeout_us(self.busy_timeout_us())
+            }
+        }
+    };
+}
 /// # SetSleep command
 /// Sets the device to sleep mode.
 ///
@@ -22,8 +1003,12 @@ pub struct SpiDescriptor {
 /// const SET_SLEEP: SetSleep = SetSleep::new(SleepConfig::new().with_warm_start(true));
 /// assert_eq!(SET_SLEEP.tx_buf, [0x84, 0x04]);
 /// assert_eq!(SET_SLEEP.rx_buf, [0, 0]);
-/// assert_eq!(SET_SLEEP.descriptor().transfer_length, 2);
+/// assert_eq!(SET_SLEEP.descriptor().transfer_length(), 2);
+/// assert_eq!(SET_SLEEP.descriptor().busy_timeout_us(), 0);
 /// ``````
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetSleep {
     pub tx_buf: [u8; 2],
     pub rx_buf: [u8; 2],
@@ -38,16 +1023,12 @@ impl SetSleep {
             rx_buf: [0; 2],
         }
     }
-    #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 2,
-        }
-    }
 }
+// BUSY stays high until the next wake-up.
+impl_command!(SetSleep: 2, BUSY_TIMEOUT_US = 0);
+impl_write_only!(SetSleep);
 #[bitfield(u8, order = Msb)]
+#[derive(PartialEq, Eq)]
 pub struct SleepConfig {
     #[bits(5)]
     __: u8,
@@ -68,8 +1049,12 @@ pub struct SleepConfig {
 /// const SET_STANDBY: SetStandby = SetStandby::new(StdbyConfig::StdbyXosc);
 /// assert_eq!(SET_STANDBY.tx_buf, [0x80, 1]);
 /// assert_eq!(SET_STANDBY.rx_buf, [0, 0]);
-/// assert_eq!(SET_STANDBY.descriptor().transfer_length, 2);
+/// assert_eq!(SET_STANDBY.descriptor().transfer_length(), 2);
+/// assert_eq!(SET_STANDBY.descriptor().busy_timeout_us(), 5_000);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetStandby {
     pub tx_buf: [u8; 2],
     pub rx_buf: [u8; 2],
@@ -84,20 +1069,55 @@ impl SetStandby {
             rx_buf: [0; 2],
         }
     }
-    #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 2,
-        }
-    }
 }
+// Waking from cold sleep reruns calibration, about 3.5 ms.
+impl_command!(SetStandby: 2, BUSY_TIMEOUT_US = 5_000);
+impl_write_only!(SetStandby);
 #[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum StdbyConfig {
     StdbyRc = 0,
     StdbyXosc = 1,
 }
+impl_try_from_u8!(StdbyConfig: StdbyRc, StdbyXosc);
+
+/// Longest timeout of SetTx and SetRx, about 262 s; SetRx reserves
+/// 0xFFFFFF for continuous mode.
+pub const MAX_TIMEOUT_TICKS: u32 = 0xFF_FFFE;
+
+/// Converts milliseconds to the 15.625 µs steps of SetTx, SetRx and
+/// SetRxDutyCycle. `None` above [`MAX_TIMEOUT_TICKS`].
+///
+/// ```
+/// use sx126x_spi_buffers::commands::{SetTx, ticks_from_ms, ticks_from_us};
+///
+/// assert_eq!(ticks_from_ms(1_000), Some(64_000));
+/// assert_eq!(ticks_from_ms(300_000), None);
+/// assert_eq!(ticks_from_us(20), Some(2));
+/// let set_tx = SetTx::new(ticks_from_ms(3_000).unwrap());
+/// assert_eq!(set_tx.tx_buf, [0x83, 0x02, 0xEE, 0x00]);
+/// ```
+#[inline(always)]
+pub const fn ticks_from_ms(ms: u32) -> Option<u32> {
+    ticks(ms as u64 * 64)
+}
+
+/// Converts microseconds to the 15.625 µs steps of SetTx, SetRx and
+/// SetRxDutyCycle, rounded up so that the timeout is never shorter than
+/// requested. `None` above [`MAX_TIMEOUT_TICKS`].
+#[inline(always)]
+pub const fn ticks_from_us(us: u32) -> Option<u32> {
+    ticks((us as u64 * 64).div_ceil(1000))
+}
+
+#[inline(always)]
+const fn ticks(ticks: u64) -> Option<u32> {
+    if ticks > MAX_TIMEOUT_TICKS as u64 {
+        None
+    } else {
+        Some(ticks as u32)
+    }
+}
 
 /// # SetTx command
 /// Sets the device to transmit mode with a specified timeout.
@@ -109,8 +1129,11 @@ pub enum StdbyConfig {
 /// const SET_TX: SetTx = SetTx::new(6862921);
 /// assert_eq!(SET_TX.tx_buf, [0x83, 104, 184, 73]);
 /// assert_eq!(SET_TX.rx_buf, [0; 4]);
-/// assert_eq!(SET_TX.descriptor().transfer_length, 4);
+/// assert_eq!(SET_TX.descriptor().transfer_length(), 4);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetTx {
     pub tx_buf: [u8; 4],
     pub rx_buf: [u8; 4],
@@ -130,181 +1153,953 @@ impl SetTx {
             rx_buf: [0; 4],
         }
     }
-    #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 4,
-        }
-    }
 }
+impl_command!(SetTx: 4);
+impl_write_only!(SetTx);
 
 /// # SetRx command
 /// Sets the device to receive mode with a specified timeout.
 ///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::commands::SetRx;
+/// use sx126x_spi_buffers::commands::SetRx;
+///
+/// const SET_RX: SetRx = SetRx::new(120);
+/// assert_eq!(SET_RX.tx_buf, [0x82, 0, 0, 120]);
+/// assert_eq!(SET_RX.rx_buf, [0; 4]);
+/// assert_eq!(SET_RX.descriptor().transfer_length(), 4);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetRx {
+    pub tx_buf: [u8; 4],
+    pub rx_buf: [u8; 4],
+}
+impl SetRx {
+    const OPCODE: u8 = 0x82;
+
+    #[inline(always)]
+    pub const fn new(timeout: u32) -> Self {
+        Self {
+            tx_buf: [
+                Self::OPCODE,
+                (timeout >> 16) as u8,
+                (timeout >> 8) as u8,
+                timeout as u8,
+            ],
+            rx_buf: [0; 4],
+        }
+    }
+}
+impl_command!(SetRx: 4);
+impl_write_only!(SetRx);
+
+/// # SetRxDutyCycle command
+/// Alternates between RX for `rx_period` and sleep with warm start for
+/// `sleep_period`, both in steps of 15.625 µs, until a packet is detected.
+/// The chip then stays in RX for `2 * rx_period + sleep_period` to receive
+/// it. See [`RxDutyCycle`] to derive both periods from the preamble length.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{SetRxDutyCycle, Sf, Bw};
+///
+/// const SET_RX_DUTY_CYCLE: SetRxDutyCycle = SetRxDutyCycle::new(0x000280, 0x012C00);
+/// assert_eq!(SET_RX_DUTY_CYCLE.tx_buf, [0x94, 0x00, 0x02, 0x80, 0x01, 0x2C, 0x00]);
+/// assert_eq!(SET_RX_DUTY_CYCLE.rx_buf, [0; 7]);
+/// assert_eq!(SET_RX_DUTY_CYCLE.descriptor().transfer_length(), 7);
+///
+/// // 10 ms RX every 1.2 s needs a 1.22 s preamble at SF9/125 kHz
+/// assert_eq!(SET_RX_DUTY_CYCLE.preamble_length(Sf::Sf9, Bw::Bw125), Some(294));
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetRxDutyCycle {
+    pub tx_buf: [u8; 7],
+    pub rx_buf: [u8; 7],
+}
+impl SetRxDutyCycle {
+    const OPCODE: u8 = 0x94;
+
+    #[inline(always)]
+    pub const fn new(rx_period: u32, sleep_period: u32) -> Self {
+        Self {
+            tx_buf: [
+                Self::OPCODE,
+                (rx_period >> 16) as u8,
+                (rx_period >> 8) as u8,
+                rx_period as u8,
+                (sleep_period >> 16) as u8,
+                (sleep_period >> 8) as u8,
+                sleep_period as u8,
+            ],
+            rx_buf: [0; 7],
+        }
+    }
+    #[inline(always)]
+    pub const fn rx_period(&self) -> u32 {
+        (self.tx_buf[1] as u32) << 16 | (self.tx_buf[2] as u32) << 8 | self.tx_buf[3] as u32
+    }
+    #[inline(always)]
+    pub const fn sleep_period(&self) -> u32 {
+        (self.tx_buf[4] as u32) << 16 | (self.tx_buf[5] as u32) << 8 | self.tx_buf[6] as u32
+    }
+    /// Shortest LoRa preamble, in symbols, a transmitter must send for this
+    /// receiver to catch it: preamble and sync word must span one sleep
+    /// period and two RX periods. `None` for reserved bandwidths or if
+    /// longer than 65535 symbols.
+    #[inline(always)]
+    pub const fn preamble_length(&self, sf: Sf, bw: Bw) -> Option<u16> {
+        let symbol_us = symbol_time_us(sf, bw) as u64;
+        if symbol_us == 0 {
+            return None;
+        }
+        // 15.625 µs per step, in quarter symbols less the 4.25 symbols of
+        // sync word
+        let ticks = 2 * self.rx_period() as u64 + self.sleep_period() as u64;
+        let quarter_symbols = (ticks * 4 * 1000).div_ceil(64 * symbol_us);
+        let length = quarter_symbols.saturating_sub(17).div_ceil(4);
+        if length > u16::MAX as u64 {
+            None
+        } else {
+            Some(length as u16)
+        }
+    }
+}
+impl_command!(SetRxDutyCycle: 7);
+impl_write_only!(SetRxDutyCycle);
+
+/// # StopTimerOnPreamble command
+/// Selects whether the RX timeout timer stops on preamble detection
+/// (`true`) or on sync word/header detection (`false`, the reset value).
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::StopTimerOnPreamble;
+///
+/// const STOP_TIMER_ON_PREAMBLE: StopTimerOnPreamble = StopTimerOnPreamble::new(true);
+/// assert_eq!(STOP_TIMER_ON_PREAMBLE.tx_buf, [0x9F, 0x01]);
+/// assert_eq!(STOP_TIMER_ON_PREAMBLE.rx_buf, [0; 2]);
+/// assert_eq!(STOP_TIMER_ON_PREAMBLE.descriptor().transfer_length(), 2);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StopTimerOnPreamble {
+    pub tx_buf: [u8; 2],
+    pub rx_buf: [u8; 2],
+}
+impl StopTimerOnPreamble {
+    const OPCODE: u8 = 0x9F;
+
+    #[inline(always)]
+    pub const fn new(enable: bool) -> Self {
+        Self {
+            tx_buf: [Self::OPCODE, enable as u8],
+            rx_buf: [0; 2],
+        }
+    }
+}
+impl_command!(StopTimerOnPreamble: 2);
+impl_write_only!(StopTimerOnPreamble);
+
+/// # RX duty cycle sequence
+/// Sniff mode for a LoRa receiver woken every `wake_interval_us`: the RX
+/// window is made as long as a preamble of `preamble_length` symbols allows,
+/// i.e. such that the preamble always spans one sleep period and two RX
+/// windows, and the chip sleeps for the rest of the interval.
+/// [`new`](Self::new) returns `None` unless the preamble lasts longer than
+/// one and shorter than two wake intervals. Send the descriptors in order;
+/// the timer stops on header detection.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{RxDutyCycle, Sf, Bw};
+///
+/// // SF7/125 kHz: 1.024 ms symbols, 40 symbols + 4.25 sync = 45.312 ms.
+/// let mut rx_duty_cycle = RxDutyCycle::new(Sf::Sf7, Bw::Bw125, 40, 40_000).unwrap();
+/// assert_eq!(rx_duty_cycle.stop_timer_on_preamble.tx_buf, [0x9F, 0x00]);
+/// // 5.312 ms RX, 34.688 ms sleep
+/// assert_eq!(rx_duty_cycle.set_rx_duty_cycle.tx_buf, [0x94, 0x00, 0x01, 0x53, 0x00, 0x08, 0xAC]);
+/// assert_eq!(rx_duty_cycle.descriptors().map(|d| d.transfer_length()), [2, 7]);
+/// assert!(RxDutyCycle::new(Sf::Sf7, Bw::Bw125, 8, 40_000).is_none());
+/// assert_eq!(rx_duty_cycle.set_rx_duty_cycle.preamble_length(Sf::Sf7, Bw::Bw125), Some(40));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RxDutyCycle {
+    pub stop_timer_on_preamble: StopTimerOnPreamble,
+    pub set_rx_duty_cycle: SetRxDutyCycle,
+}
+impl RxDutyCycle {
+    #[inline(always)]
+    pub const fn new(sf: Sf, bw: Bw, preamble_length: u16, wake_interval_us: u32) -> Option<Self> {
+        // Preamble plus the 4.25 symbols of sync word
+        let preamble_us = symbol_time_us(sf, bw) as u64 * (4 * preamble_length as u64 + 17) / 4;
+        let wake_interval_us = wake_interval_us as u64;
+        if preamble_us <= wake_interval_us || preamble_us >= 2 * wake_interval_us {
+            return None;
+        }
+        let rx_period_us = preamble_us - wake_interval_us;
+        let sleep_period_us = wake_interval_us - rx_period_us;
+        // 15.625 µs per step
+        let rx_period = rx_period_us * 64 / 1000;
+        let sleep_period = sleep_period_us * 64 / 1000;
+        if rx_period == 0 || sleep_period > 0xFFFFFF {
+            return None;
+        }
+        Some(Self {
+            stop_timer_on_preamble: StopTimerOnPreamble::new(false),
+            set_rx_duty_cycle: SetRxDutyCycle::new(rx_period as u32, sleep_period as u32),
+        })
+    }
+    #[inline(always)]
+    pub const fn descriptors(&mut self) -> [SpiDescriptor; 2] {
+        [
+            self.stop_timer_on_preamble.descriptor(),
+            self.set_rx_duty_cycle.descriptor(),
+        ]
+    }
+}
+
+/// # SetFs command
+/// Sets the device to frequency synthesis mode, with the PLL locked to the
+/// RF frequency. Mostly used for test purposes.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::SetFs;
+///
+/// const SET_FS: SetFs = SetFs::new();
+/// assert_eq!(SET_FS.tx_buf, [0xC1]);
+/// assert_eq!(SET_FS.rx_buf, [0]);
+/// assert_eq!(SET_FS.descriptor().transfer_length(), 1);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetFs {
+    pub tx_buf: [u8; 1],
+    pub rx_buf: [u8; 1],
+}
+impl SetFs {
+    const OPCODE: u8 = 0xC1;
+
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            tx_buf: [Self::OPCODE],
+            rx_buf: [0; 1],
+        }
+    }
+}
+impl_command!(SetFs: 1);
+impl_write_only!(SetFs);
+
+/// # SetCadParams command
+/// Configures Channel Activity Detection: the number of symbols searched,
+/// the detection thresholds, what the chip does after CAD and, for
+/// [`CadExitMode::CadRx`], the RX timeout in steps of 15.625 µs.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{SetCadParams, CadSymbolNum, CadExitMode, Sf, Bw};
+///
+/// const SET_CAD_PARAMS: SetCadParams = SetCadParams::new(CadSymbolNum::Symbols2, 22, 10, CadExitMode::CadOnly, 0);
+/// assert_eq!(SET_CAD_PARAMS.tx_buf, [0x88, 0x01, 22, 10, 0x00, 0, 0, 0]);
+/// assert_eq!(SET_CAD_PARAMS.rx_buf, [0; 8]);
+/// assert_eq!(SET_CAD_PARAMS.descriptor().transfer_length(), 8);
+///
+/// let set_cad_params = SetCadParams::recommended(Sf::Sf10, Bw::Bw125, CadExitMode::CadOnly, 0).unwrap();
+/// assert_eq!(set_cad_params.tx_buf, [0x88, 0x02, 24, 10, 0x00, 0, 0, 0]);
+/// assert!(SetCadParams::recommended(Sf::Sf10, Bw::Bw250, CadExitMode::CadOnly, 0).is_none());
+/// assert!(SetCadParams::recommended(Sf::Sf5, Bw::Bw125, CadExitMode::CadOnly, 0).is_none());
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetCadParams {
+    pub tx_buf: [u8; 8],
+    pub rx_buf: [u8; 8],
+}
+impl SetCadParams {
+    const OPCODE: u8 = 0x88;
+
+    #[inline(always)]
+    pub const fn new(
+        cad_symbol_num: CadSymbolNum,
+        cad_det_peak: u8,
+        cad_det_min: u8,
+        cad_exit_mode: CadExitMode,
+        cad_timeout: u32,
+    ) -> Self {
+        Self {
+            tx_buf: [
+                Self::OPCODE,
+                cad_symbol_num as u8,
+                cad_det_peak,
+                cad_det_min,
+                cad_exit_mode as u8,
+                (cad_timeout >> 16) as u8,
+                (cad_timeout >> 8) as u8,
+                cad_timeout as u8,
+            ],
+            rx_buf: [0; 8],
+        }
+    }
+    /// Search length and thresholds recommended by Semtech (AN1200.48),
+    /// which tabulates SF7 to SF12 at 125 kHz only; `None` otherwise.
+    #[inline(always)]
+    pub const fn recommended(
+        sf: Sf,
+        bw: Bw,
+        cad_exit_mode: CadExitMode,
+        cad_timeout: u32,
+    ) -> Option<Self> {
+        if !matches!(bw, Bw::Bw125) {
+            return None;
+        }
+        let (cad_symbol_num, cad_det_peak) = match sf {
+            Sf::Sf7 | Sf::Sf8 => (CadSymbolNum::Symbols2, 22),
+            Sf::Sf9 => (CadSymbolNum::Symbols4, 23),
+            Sf::Sf10 => (CadSymbolNum::Symbols4, 24),
+            Sf::Sf11 => (CadSymbolNum::Symbols4, 25),
+            Sf::Sf12 => (CadSymbolNum::Symbols4, 28),
+            _ => return None,
+        };
+        Some(Self::new(
+            cad_symbol_num,
+            cad_det_peak,
+            10,
+            cad_exit_mode,
+            cad_timeout,
+        ))
+    }
+}
+impl_command!(SetCadParams: 8);
+impl_write_only!(SetCadParams);
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CadSymbolNum {
+    Symbols1 = 0x00,
+    Symbols2 = 0x01,
+    Symbols4 = 0x02,
+    Symbols8 = 0x03,
+    Symbols16 = 0x04,
+}
+impl_try_from_u8!(CadSymbolNum: Symbols1, Symbols2, Symbols4, Symbols8, Symbols16);
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CadExitMode {
+    /// Return to STDBY_RC once CAD is done.
+    CadOnly = 0x00,
+    /// Stay in RX if activity was detected, until a packet is received or
+    /// the timeout expires.
+    CadRx = 0x01,
+}
+impl_try_from_u8!(CadExitMode: CadOnly, CadRx);
+
+/// # SetCad command
+/// Starts Channel Activity Detection with the parameters set by
+/// SetCadParams. CadDone is raised when the search completes, together
+/// with CadDetected if a LoRa signal was found.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::SetCad;
+///
+/// const SET_CAD: SetCad = SetCad::new();
+/// assert_eq!(SET_CAD.tx_buf, [0xC5]);
+/// assert_eq!(SET_CAD.rx_buf, [0]);
+/// assert_eq!(SET_CAD.descriptor().transfer_length(), 1);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetCad {
+    pub tx_buf: [u8; 1],
+    pub rx_buf: [u8; 1],
+}
+impl SetCad {
+    const OPCODE: u8 = 0xC5;
+
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            tx_buf: [Self::OPCODE],
+            rx_buf: [0; 1],
+        }
+    }
+}
+impl_command!(SetCad: 1);
+impl_write_only!(SetCad);
+
+/// # SetPaConfig command
+/// Configures the power amplifier settings.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::SetPaConfig;
+///
+/// const SET_PA_CONFIG: SetPaConfig = SetPaConfig::new(0x04, 0x07);
+/// assert_eq!(SET_PA_CONFIG.tx_buf, [0x95, 0x04, 0x07, 0x00, 0x01]);
+/// assert_eq!(SET_PA_CONFIG.rx_buf, [0; 5]);
+/// assert_eq!(SET_PA_CONFIG.descriptor().transfer_length(), 5);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SetPaConfig {
+    pub tx_buf: [u8; 5],
+    pub rx_buf: [u8; 5],
+}
+impl SetPaConfig {
+    const OPCODE: u8 = 0x95;
+
+    #[inline(always)]
+    pub const fn new(pa_duty_cycle: u8, hp_max: u8) -> Self {
+        Self {
+            tx_buf: [Self::OPCODE, pa_duty_cycle, hp_max, 0x00, 0x01], // High-power PA, see with_device_sel
+            rx_buf: [0; 5],
+        }
+    }
+    /// Selects the PA, see [`Pa::device_sel`].
+    #[inline(always)]
+    pub const fn with_device_sel(mut self, device_sel: u8) -> Self {
+        self.tx_buf[3] = device_sel;
+        self
+    }
+}
+impl_command!(SetPaConfig: 5);
+impl_write_only!(SetPaConfig);
+/// Power amplifier wired to the antenna, known from the board design.
+/// [`ReadVersion`] cannot tell it: SX1262 parts report `"SX1261 ..."`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Pa {
+    /// SX1261 low-power PA, up to +15 dBm.
+    LowPower,
+    /// SX1262 or LLCC68 high-power PA, up to +22 dBm.
+    HighPower,
+    /// SX1268 high-power PA, up to +22 dBm.
+    HighPowerSx1268,
+}
+impl Pa {
+    /// `deviceSel` value of SetPaConfig: 1 selects the SX1261 low-power PA,
+    /// 0 the high-power PA.
+    #[inline(always)]
+    pub const fn device_sel(&self) -> u8 {
+        match self {
+            Self::LowPower => 0x01,
+            Self::HighPower | Self::HighPowerSx1268 => 0x00,
+        }
+    }
+}
+
+/// # WriteRegister command
+/// Writes a block of bytes starting at a specific address.
+///
+/// ## Example
+#[cfg_attr(feature = "nightly", doc = "```")]
+#[cfg_attr(not(feature = "nightly"), doc = "```ignore")]
+/// use sx126x_spi_buffers::{registers, commands::{WriteRegister}};
+///
+/// const WRITE_REGISTER: WriteRegister = WriteRegister::new(registers::LoraSyncWordMsb(0x48));
+/// assert_eq!(WRITE_REGISTER.tx_buf, [0x0D, 0x07, 0x40, 0x48]);
+/// assert_eq!(WRITE_REGISTER.rx_buf, [0; 4]);
+/// assert_eq!(WRITE_REGISTER.descriptor().transfer_length(), 4);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WriteRegister {
+    pub tx_buf: [u8; 4],
+    pub rx_buf: [u8; 4],
+}
+register_fns! {
+    impl WriteRegister {
+        #[inline(always)]
+        pub fn new<R: Register>(register: R) -> Self {
+            Self {
+                tx_buf: [
+                    Self::OPCODE,
+                    (R::ADDRESS >> 8) as u8,
+                    R::ADDRESS as u8,
+                    register.bits(),
+                ],
+                rx_buf: [0; 4],
+            }
+        }
+    }
+}
+impl WriteRegister {
+    const OPCODE: u8 = 0x0D;
+
+    #[inline(always)]
+    pub const fn new_raw(register: RawRegister) -> Self {
+        Self {
+            tx_buf: [
+                Self::OPCODE,
+                (register.address >> 8) as u8,
+                register.address as u8,
+                register.value,
+            ],
+            rx_buf: [0; 4],
+        }
+    }
+}
+impl_command!(WriteRegister: 4);
+impl_write_only!(WriteRegister);
+
+/// # ReadRegister command
+/// Reads a block of bytes starting at a specific address.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers, commands::{ReadRegister}};
+///
+/// let mut read_register: ReadRegister<registers::LoraSyncWordLsb> = ReadRegister::new();
+/// assert_eq!(read_register.tx_buf, [0x1D, 0x07, 0x41, 0, 0]);
+/// assert_eq!(read_register.rx_buf, [0; 5]);
+/// assert_eq!(read_register.descriptor().transfer_length(), 5);
+/// read_register.rx_buf[4] = 0x86;
+/// assert_eq!(read_register.register(), registers::LoraSyncWordLsb(0x86));
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadRegister<R> {
+    pub tx_buf: [u8; 5],
+    pub rx_buf: [u8; 5],
+    register: PhantomData<R>,
+}
+impl<R> ReadRegister<R> {
+    const OPCODE: u8 = 0x1D;
+}
+impl<R: Register> ReadRegister<R> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            tx_buf: [
+                Self::OPCODE,
+                (R::ADDRESS >> 8) as u8,
+                R::ADDRESS as u8,
+                0,
+                0,
+            ],
+            rx_buf: [0; 5],
+            register: PhantomData,
+        }
+    }
+}
+register_fns! {
+    impl<R: Register> ReadRegister<R> {
+        #[inline(always)]
+        pub fn register(&self) -> R {
+            R::from_bits(self.rx_buf[4])
+        }
+    }
+}
+impl ReadRegister<RawRegister> {
+    #[inline(always)]
+    pub const fn new_raw(address: u16) -> Self {
+        Self {
+            tx_buf: [Self::OPCODE, (address >> 8) as u8, address as u8, 0, 0],
+            rx_buf: [0; 5],
+            register: PhantomData,
+        }
+    }
+    #[inline(always)]
+    pub const fn raw_register(&self) -> RawRegister {
+        RawRegister {
+            address: (self.tx_buf[1] as u16) << 8 | self.tx_buf[2] as u16,
+            value: self.rx_buf[4],
+        }
+    }
+}
+impl<R: Register> Response for ReadRegister<R> {
+    type Output = R;
+
+    #[inline(always)]
+    fn parse(&self) -> R {
+        R::from_bits(self.rx_buf[4])
+    }
+}
+impl_command!(ReadRegister<R>: 5);
+
+/// # WriteRegister command (16-bit)
+/// Writes a 16-bit register pair, MSB first, in a single transfer.
+///
+/// ## Example
+#[cfg_attr(feature = "nightly", doc = "```")]
+#[cfg_attr(not(feature = "nightly"), doc = "```ignore")]
+/// use sx126x_spi_buffers::{registers, commands::WriteRegister16};
+///
+/// const WRITE_REGISTER_16: WriteRegister16 = WriteRegister16::new(registers::CrcPolynomial(0x8005));
+/// assert_eq!(WRITE_REGISTER_16.tx_buf, [0x0D, 0x06, 0xBE, 0x80, 0x05]);
+/// assert_eq!(WRITE_REGISTER_16.rx_buf, [0; 5]);
+/// assert_eq!(WRITE_REGISTER_16.descriptor().transfer_length(), 5);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WriteRegister16 {
+    pub tx_buf: [u8; 5],
+    pub rx_buf: [u8; 5],
+}
+register_fns! {
+    impl WriteRegister16 {
+        #[inline(always)]
+        pub fn new<R: Register16>(register: R) -> Self {
+            Self {
+                tx_buf: [
+                    Self::OPCODE,
+                    (R::ADDRESS >> 8) as u8,
+                    R::ADDRESS as u8,
+                    (register.bits() >> 8) as u8,
+                    register.bits() as u8,
+                ],
+                rx_buf: [0; 5],
+            }
+        }
+    }
+}
+impl WriteRegister16 {
+    const OPCODE: u8 = 0x0D;
+}
+impl_command!(WriteRegister16: 5);
+impl_write_only!(WriteRegister16);
+
+/// # ReadRegister command (16-bit)
+/// Reads a 16-bit register pair, MSB first, in a single transfer.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers, commands::ReadRegister16};
+///
+/// let mut read_register_16: ReadRegister16<registers::CrcInitial> = ReadRegister16::new();
+/// assert_eq!(read_register_16.tx_buf, [0x1D, 0x06, 0xBC, 0, 0, 0]);
+/// assert_eq!(read_register_16.rx_buf, [0; 6]);
+/// assert_eq!(read_register_16.descriptor().transfer_length(), 6);
+/// read_register_16.rx_buf[4] = 0xFF;
+/// read_register_16.rx_buf[5] = 0xFF;
+/// assert_eq!(read_register_16.register(), registers::CrcInitial(0xFFFF));
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadRegister16<R> {
+    pub tx_buf: [u8; 6],
+    pub rx_buf: [u8; 6],
+    register: PhantomData<R>,
+}
+impl<R: Register16> ReadRegister16<R> {
+    const OPCODE: u8 = 0x1D;
+
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            tx_buf: [
+                Self::OPCODE,
+                (R::ADDRESS >> 8) as u8,
+                R::ADDRESS as u8,
+                0,
+                0,
+                0,
+            ],
+            rx_buf: [0; 6],
+            register: PhantomData,
+        }
+    }
+}
+register_fns! {
+    impl<R: Register16> ReadRegister16<R> {
+        #[inline(always)]
+        pub fn register(&self) -> R {
+            R::from_bits((self.rx_buf[4] as u16) << 8 | self.rx_buf[5] as u16)
+        }
+    }
+}
+impl<R: Register16> Response for ReadRegister16<R> {
+    type Output = R;
+
+    #[inline(always)]
+    fn parse(&self) -> R {
+        R::from_bits((self.rx_buf[4] as u16) << 8 | self.rx_buf[5] as u16)
+    }
+}
+impl_command!(ReadRegister16<R>: 6);
+
+/// # WriteRegister command (block)
+/// Writes a block of bytes starting at a specific address in a single
+/// transfer. The address is auto-incremented after each byte.
+///
+/// #### Type Parameter `N`
+/// `N` = data length + 3
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::WriteRegisterBlock;
 ///
-/// const SET_RX: SetRx = SetRx::new(120);
-/// assert_eq!(SET_RX.tx_buf, [0x82, 0, 0, 120]);
-/// assert_eq!(SET_RX.rx_buf, [0; 4]);
-/// assert_eq!(SET_RX.descriptor().transfer_length, 4);
+/// const WRITE_SYNC_WORD: WriteRegisterBlock<11> =
+///     WriteRegisterBlock::gfsk_sync_word([0xC1, 0x94, 0xC1, 0, 0, 0, 0, 0]);
+/// assert_eq!(WRITE_SYNC_WORD.tx_buf, [0x0D, 0x06, 0xC0, 0xC1, 0x94, 0xC1, 0, 0, 0, 0, 0]);
+/// assert_eq!(WRITE_SYNC_WORD.rx_buf, [0; 11]);
+/// assert_eq!(WRITE_SYNC_WORD.descriptor().transfer_length(), 11);
 /// ```
-pub struct SetRx {
-    pub tx_buf: [u8; 4],
-    pub rx_buf: [u8; 4],
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WriteRegisterBlock<const N: usize> {
+    pub tx_buf: [u8; N],
+    pub rx_buf: [u8; N],
 }
-impl SetRx {
-    const OPCODE: u8 = 0x82;
+impl<const N: usize> WriteRegisterBlock<N> {
+    const OPCODE: u8 = 0x0D;
 
+    #[cfg(feature = "nightly")]
     #[inline(always)]
-    pub const fn new(timeout: u32) -> Self {
+    pub const fn new(address: u16, data: [u8; N - 3]) -> Self {
+        Self::from_slice(address, &data)
+    }
+    /// Like `new`, which requires the `nightly` feature, from a slice.
+    ///
+    /// # Panics
+    /// If `data` is not `N - 3` bytes long.
+    #[inline(always)]
+    pub const fn from_slice(address: u16, data: &[u8]) -> Self {
+        assert!(data.len() + 3 == N, "data must be N - 3 bytes long");
+        let mut tx_buf = [0; N];
+        tx_buf[0] = Self::OPCODE;
+        tx_buf[1] = (address >> 8) as u8;
+        tx_buf[2] = address as u8;
+        let mut i: usize = 0;
+        while i < N - 3 {
+            tx_buf[i + 3] = data[i];
+            i += 1;
+        }
         Self {
-            tx_buf: [
-                Self::OPCODE,
-                (timeout >> 16) as u8,
-                (timeout >> 8) as u8,
-                timeout as u8,
-            ],
-            rx_buf: [0; 4],
+            tx_buf,
+            rx_buf: [0; N],
         }
     }
+}
+impl WriteRegisterBlock<11> {
+    /// Writes the 8-byte GFSK sync word to SyncWord0..SyncWord7.
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 4,
-        }
+    pub const fn gfsk_sync_word(sync_word: [u8; 8]) -> Self {
+        Self::from_slice(SyncWord0::ADDRESS, &sync_word)
     }
 }
+impl_command!(WriteRegisterBlock<const N>: N);
+impl_write_only!(WriteRegisterBlock<const N>);
 
-/// # SetPaConfig command
-/// Configures the power amplifier settings.
+/// # ReadRegister command (block)
+/// Reads a block of bytes starting at a specific address in a single
+/// transfer. The address is auto-incremented after each byte.
+///
+/// #### Type Parameter `N`
+/// `N` = data length + 4, at least 4:
+/// ```compile_fail
+/// use sx126x_spi_buffers::commands::ReadRegisterBlock;
+///
+/// let read_register_block: ReadRegisterBlock<3> = ReadRegisterBlock::new(0x06CD);
+/// ```
 ///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::commands::SetPaConfig;
+/// use sx126x_spi_buffers::commands::ReadRegisterBlock;
 ///
-/// const SET_PA_CONFIG: SetPaConfig = SetPaConfig::new(0x04, 0x07);
-/// assert_eq!(SET_PA_CONFIG.tx_buf, [0x95, 0x04, 0x07, 0x00, 0x01]);
-/// assert_eq!(SET_PA_CONFIG.rx_buf, [0; 5]);
-/// assert_eq!(SET_PA_CONFIG.descriptor().transfer_length, 5);
+/// let mut read_register_block: ReadRegisterBlock<6> = ReadRegisterBlock::new(0x06CD);
+/// assert_eq!(read_register_block.tx_buf, [0x1D, 0x06, 0xCD, 0, 0, 0]);
+/// assert_eq!(read_register_block.rx_buf, [0; 6]);
+/// assert_eq!(read_register_block.descriptor().transfer_length(), 6);
+/// read_register_block.rx_buf[4..].copy_from_slice(&[0x2A, 0xFF]);
+/// assert_eq!(read_register_block.data(), &[0x2A, 0xFF]);
 /// ```
-pub struct SetPaConfig {
-    pub tx_buf: [u8; 5],
-    pub rx_buf: [u8; 5],
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadRegisterBlock<const N: usize> {
+    pub tx_buf: [u8; N],
+    pub rx_buf: [u8; N],
 }
-impl SetPaConfig {
-    const OPCODE: u8 = 0x95;
+impl<const N: usize> ReadRegisterBlock<N> {
+    const OPCODE: u8 = 0x1D;
+    /// Fails to compile when there is no room for the opcode, the address
+    /// and the status byte.
+    const SIZE_OK: () = assert!(N >= 4, "N must be at least 4");
 
     #[inline(always)]
-    pub const fn new(pa_duty_cycle: u8, hp_max: u8) -> Self {
+    pub const fn new(address: u16) -> Self {
+        let () = Self::SIZE_OK;
+        let mut tx_buf = [0; N];
+        tx_buf[0] = Self::OPCODE;
+        tx_buf[1] = (address >> 8) as u8;
+        tx_buf[2] = address as u8;
         Self {
-            tx_buf: [Self::OPCODE, pa_duty_cycle, hp_max, 0x00, 0x01], // Doesn't support SX1261
-            rx_buf: [0; 5],
+            tx_buf,
+            rx_buf: [0; N],
         }
     }
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 5,
-        }
+    pub fn data(&self) -> &[u8] {
+        let () = Self::SIZE_OK;
+        &self.rx_buf[4..]
     }
 }
+impl_command!(ReadRegisterBlock<const N>: N);
 
-/// # WriteRegister command
-/// Writes a block of bytes starting at a specific address.
+/// # Random number sequence
+/// Reads the 32-bit random number generator (RandomNumberGen0..3). The
+/// generator is only fed by noise while the receiver is running, so the
+/// sequence enters continuous RX, reads the registers and returns to
+/// STDBY_RC. Send the three descriptors in order, then read
+/// [`random_number`](Self::random_number).
 ///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::{registers, commands::{WriteRegister}};
+/// use sx126x_spi_buffers::commands::GetRandomNumber;
 ///
-/// const WRITE_REGISTER: WriteRegister = WriteRegister::new(registers::LoraSyncWordMsb(0x48));
-/// assert_eq!(WRITE_REGISTER.tx_buf, [0x0D, 0x07, 0x40, 0x48]);
-/// assert_eq!(WRITE_REGISTER.rx_buf, [0; 4]);
-/// assert_eq!(WRITE_REGISTER.descriptor().transfer_length, 4);
+/// let mut get_random_number: GetRandomNumber = GetRandomNumber::new();
+/// assert_eq!(get_random_number.set_rx.tx_buf, [0x82, 0xFF, 0xFF, 0xFF]);
+/// assert_eq!(get_random_number.read_random.tx_buf, [0x1D, 0x08, 0x19, 0, 0, 0, 0, 0]);
+/// assert_eq!(get_random_number.set_standby.tx_buf, [0x80, 0]);
+/// assert_eq!(get_random_number.descriptors().map(|d| d.transfer_length()), [4, 8, 2]);
+/// get_random_number.read_random.rx_buf[4..].copy_from_slice(&[0xDE, 0xAD, 0xBE, 0xEF]);
+/// assert_eq!(get_random_number.random_number(), 0xDEADBEEF);
 /// ```
-pub struct WriteRegister {
-    pub tx_buf: [u8; 4],
-    pub rx_buf: [u8; 4],
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GetRandomNumber {
+    pub set_rx: SetRx,
+    pub read_random: ReadRegisterBlock<8>,
+    pub set_standby: SetStandby,
 }
-impl WriteRegister {
-    const OPCODE: u8 = 0x0D;
-
+impl GetRandomNumber {
     #[inline(always)]
-    pub const fn new<R: const Register>(register: R) -> Self {
+    pub const fn new() -> Self {
         Self {
-            tx_buf: [
-                Self::OPCODE,
-                (R::ADDRESS >> 8) as u8,
-                R::ADDRESS as u8,
-                register.bits(),
-            ],
-            rx_buf: [0; 4],
+            set_rx: SetRx::new(0xFFFFFF),
+            read_random: ReadRegisterBlock::new(RandomNumberGen0::ADDRESS),
+            set_standby: SetStandby::new(StdbyConfig::StdbyRc),
         }
     }
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 4,
-        }
+    pub const fn descriptors(&mut self) -> [SpiDescriptor; 3] {
+        [
+            self.set_rx.descriptor(),
+            self.read_random.descriptor(),
+            self.set_standby.descriptor(),
+        ]
+    }
+    #[inline(always)]
+    pub const fn random_number(&self) -> u32 {
+        (self.read_random.rx_buf[4] as u32) << 24
+            | (self.read_random.rx_buf[5] as u32) << 16
+            | (self.read_random.rx_buf[6] as u32) << 8
+            | self.read_random.rx_buf[7] as u32
     }
 }
 
-/// # ReadRegister command
-/// Reads a block of bytes starting at a specific address.
+/// # ReadVersion command
+/// Reads the 16-byte silicon version string stored at register 0x0320
+/// (e.g. `"SX1261 V2D 2D02"`) using a ReadRegister transfer.
+///
+/// The string identifies the die family rather than the exact part: SX1262
+/// devices are commonly reported as `"SX1261 ..."`, which is parsed as
+/// [`ChipVariant::Sx1261Or1262`]. Only board knowledge tells these apart,
+/// see [`Pa`].
 ///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::{registers, commands::{ReadRegister}};
+/// use sx126x_spi_buffers::commands::{ReadVersion, ChipVariant};
 ///
-/// let mut read_register: ReadRegister<registers::LoraSyncWordLsb> = ReadRegister::new();
-/// assert_eq!(read_register.tx_buf, [0x1D, 0x07, 0x41, 0, 0]);
-/// assert_eq!(read_register.rx_buf, [0; 5]);
-/// assert_eq!(read_register.descriptor().transfer_length, 5);
-/// read_register.rx_buf[4] = 0x86;
-/// assert_eq!(read_register.register(), registers::LoraSyncWordLsb(0x86));
+/// let mut read_version: ReadVersion = ReadVersion::new();
+/// assert_eq!(read_version.tx_buf[..4], [0x1D, 0x03, 0x20, 0]);
+/// assert_eq!(read_version.rx_buf, [0; 20]);
+/// assert_eq!(read_version.descriptor().transfer_length(), 20);
+/// read_version.rx_buf[4..].copy_from_slice(b"LLCC68 V2D 2D02\0");
+/// assert_eq!(read_version.version(), b"LLCC68 V2D 2D02\0");
+/// assert_eq!(read_version.chip_variant(), ChipVariant::Llcc68);
+/// assert_eq!(read_version.chip_variant().device_sel(), Some(0x00));
+///
+/// read_version.rx_buf[4..].copy_from_slice(b"SX1261 V2D 2D02\0");
+/// assert_eq!(read_version.chip_variant(), ChipVariant::Sx1261Or1262);
+/// assert_eq!(read_version.chip_variant().device_sel(), None);
 /// ```
-pub struct ReadRegister<R> {
-    pub tx_buf: [u8; 5],
-    pub rx_buf: [u8; 5],
-    register: PhantomData<R>,
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ReadVersion {
+    pub tx_buf: [u8; 20],
+    pub rx_buf: [u8; 20],
 }
-impl<R: const Register> ReadRegister<R> {
+impl ReadVersion {
     const OPCODE: u8 = 0x1D;
 
     #[inline(always)]
     pub const fn new() -> Self {
+        let mut tx_buf = [0; 20];
+        tx_buf[0] = Self::OPCODE;
+        tx_buf[1] = (addresses::VERSION_STRING >> 8) as u8;
+        tx_buf[2] = addresses::VERSION_STRING as u8;
         Self {
-            tx_buf: [
-                Self::OPCODE,
-                (R::ADDRESS >> 8) as u8,
-                R::ADDRESS as u8,
-                0,
-                0,
-            ],
-            rx_buf: [0; 5],
-            register: PhantomData,
+            tx_buf,
+            rx_buf: [0; 20],
         }
     }
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 5,
+    pub fn version(&self) -> &[u8] {
+        &self.rx_buf[4..]
+    }
+    #[inline(always)]
+    pub const fn chip_variant(&self) -> ChipVariant {
+        let mut prefix = [0; 6];
+        let mut i = 0;
+        while i < 6 {
+            prefix[i] = self.rx_buf[4 + i];
+            i += 1;
+        }
+        ChipVariant::from_prefix(prefix)
+    }
+}
+impl Response for ReadVersion {
+    type Output = ChipVariant;
+
+    #[inline(always)]
+    fn parse(&self) -> ChipVariant {
+        self.chip_variant()
+    }
+}
+impl_command!(ReadVersion: 20);
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChipVariant {
+    /// `"SX1261"`, reported by both SX1261 and SX1262 parts.
+    Sx1261Or1262,
+    Sx1262,
+    Sx1268,
+    Llcc68,
+    Unknown,
+}
+impl ChipVariant {
+    #[inline(always)]
+    const fn from_prefix(prefix: [u8; 6]) -> Self {
+        match prefix {
+            [b'S', b'X', b'1', b'2', b'6', b'1'] => Self::Sx1261Or1262,
+            [b'S', b'X', b'1', b'2', b'6', b'2'] => Self::Sx1262,
+            [b'S', b'X', b'1', b'2', b'6', b'8'] => Self::Sx1268,
+            [b'L', b'L', b'C', b'C', b'6', b'8'] => Self::Llcc68,
+            _ => Self::Unknown,
         }
     }
+    /// `deviceSel` value of SetPaConfig for this variant, `None` when the
+    /// variant does not tell which PA the part has.
     #[inline(always)]
-    pub const fn register(&self) -> R {
-        R::from_bits(self.rx_buf[4])
+    pub const fn device_sel(&self) -> Option<u8> {
+        match self {
+            Self::Sx1262 | Self::Sx1268 | Self::Llcc68 => Some(0x00),
+            Self::Sx1261Or1262 | Self::Unknown => None,
+        }
     }
 }
 
@@ -313,19 +2108,30 @@ impl<R: const Register> ReadRegister<R> {
 /// when it exceeds 255 it is wrapped back to 0.
 ///
 /// #### Type Parameter `N`
-/// `N` = data length + 2
+/// `N` = data length + 2, with at most 255 bytes of data:
+/// ```compile_fail
+/// use sx126x_spi_buffers::commands::WriteBuffer;
 ///
-/// ## Example
+/// let write_buffer: WriteBuffer<258> = WriteBuffer::from_slice(0, &[0; 256]);
 /// ```
-/// use sx126x_spi_buffers::commands::WriteBuffer;
+///
+/// ## Example
+#[cfg_attr(feature = "nightly", doc = "```")]
+#[cfg_attr(not(feature = "nightly"), doc = "```ignore")]
+/// use sx126x_spi_buffers::commands::{Error, WriteBuffer};
 ///
 /// let mut write_buffer: WriteBuffer<7> = WriteBuffer::new(0x10, [b'h', b'e', b'l', b'l', b'o'].into());
 /// assert_eq!(write_buffer.tx_buf, [0x0E, 0x10, b'h', b'e', b'l', b'l', b'o']);
 /// assert_eq!(write_buffer.rx_buf, [0; 7]);
-/// assert_eq!(write_buffer.descriptor().transfer_length, 7);
-/// write_buffer.set_data_length(3);
-/// assert_eq!(write_buffer.descriptor().transfer_length, 5);
+/// assert_eq!(write_buffer.descriptor().transfer_length(), 7);
+/// write_buffer.set_data_length(3).unwrap();
+/// assert_eq!(write_buffer.descriptor().transfer_length(), 5);
+/// assert_eq!(write_buffer.set_data_length(6), Err(Error::PayloadTooLong));
+/// assert_eq!(write_buffer.descriptor().transfer_length(), 5);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct WriteBuffer<const N: usize> {
     pub tx_buf: [u8; N],
     pub rx_buf: [u8; N],
@@ -333,9 +2139,23 @@ pub struct WriteBuffer<const N: usize> {
 }
 impl<const N: usize> WriteBuffer<N> {
     const OPCODE: u8 = 0x0E;
+    /// Fails to compile when the data would not fit the 256-byte FIFO in
+    /// one packet.
+    const SIZE_OK: () = assert!(N >= 2 && N - 2 <= 255, "N - 2 must be at most 255");
 
+    #[cfg(feature = "nightly")]
     #[inline(always)]
     pub const fn new(offset: u8, data: [u8; N - 2]) -> Self {
+        Self::from_slice(offset, &data)
+    }
+    /// Like `new`, which requires the `nightly` feature, from a slice.
+    ///
+    /// # Panics
+    /// If `data` is not `N - 2` bytes long.
+    #[inline(always)]
+    pub const fn from_slice(offset: u8, data: &[u8]) -> Self {
+        let () = Self::SIZE_OK;
+        assert!(data.len() + 2 == N, "data must be N - 2 bytes long");
         let mut tx_buf = [0; N];
         tx_buf[0] = Self::OPCODE;
         tx_buf[1] = offset;
@@ -350,25 +2170,167 @@ impl<const N: usize> WriteBuffer<N> {
             data_length: N as u16 - 2,
         }
     }
+    /// Writes `data`, at most `N - 2` bytes, at `offset`, with the data
+    /// length set to its length.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::{Error, WriteBuffer};
+    ///
+    /// let mut write_buffer: WriteBuffer<8> = WriteBuffer::try_new(0, b"ping").unwrap();
+    /// assert_eq!(write_buffer.tx_buf, [0x0E, 0, b'p', b'i', b'n', b'g', 0, 0]);
+    /// assert_eq!(write_buffer.descriptor().transfer_length(), 6);
+    /// assert_eq!(WriteBuffer::<8>::try_new(0, b"pingpong").err(), Some(Error::PayloadTooLong));
+    /// ```
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: self.data_length + 2,
+    pub const fn try_new(offset: u8, data: &[u8]) -> Result<Self, Error> {
+        let () = Self::SIZE_OK;
+        if data.len() + 2 > N {
+            return Err(Error::PayloadTooLong);
+        }
+        let mut tx_buf = [0; N];
+        tx_buf[0] = Self::OPCODE;
+        tx_buf[1] = offset;
+        let mut i: usize = 0;
+        while i < data.len() {
+            tx_buf[i + 2] = data[i];
+            i += 1;
         }
+        Ok(Self {
+            tx_buf,
+            rx_buf: [0; N],
+            data_length: data.len() as u16,
+        })
     }
+    /// Sends the first `data_length` bytes of the data area, at most
+    /// `N - 2`; the length is left untouched on error.
     #[inline(always)]
-    pub const fn set_data_length(&mut self, data_length: u16) {
+    pub const fn set_data_length(&mut self, data_length: u16) -> Result<(), Error> {
+        if data_length as usize > N - 2 {
+            return Err(Error::PayloadTooLong);
+        }
         self.data_length = data_length;
+        Ok(())
+    }
+    /// Replaces the data with `payload`, at most `N - 2` bytes, and sets the
+    /// data length to its length, e.g. to reuse one static command for
+    /// every packet. The buffer is left untouched on error.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::{Error, WriteBuffer};
+    ///
+    /// static mut WRITE_BUFFER: WriteBuffer<8> = WriteBuffer::from_slice(0, &[0; 6]);
+    ///
+    /// let write_buffer = unsafe { &mut *core::ptr::addr_of_mut!(WRITE_BUFFER) };
+    /// write_buffer.set_payload(b"ping").unwrap();
+    /// assert_eq!(write_buffer.descriptor().transfer_length(), 6);
+    /// assert_eq!(write_buffer.set_payload(b"too long"), Err(Error::PayloadTooLong));
+    ///
+    /// write_buffer.payload_mut()[..3].copy_from_slice(b"ack");
+    /// write_buffer.set_data_length(3).unwrap();
+    /// assert_eq!(write_buffer.tx_buf[..5], [0x0E, 0, b'a', b'c', b'k']);
+    /// ```
+    #[inline(always)]
+    pub const fn set_payload(&mut self, payload: &[u8]) -> Result<(), Error> {
+        if payload.len() + 2 > N {
+            return Err(Error::PayloadTooLong);
+        }
+        let mut i: usize = 0;
+        while i < payload.len() {
+            self.tx_buf[i + 2] = payload[i];
+            i += 1;
+        }
+        self.data_length = payload.len() as u16;
+        Ok(())
+    }
+    /// The whole data area, `N - 2` bytes, to fill in place before setting
+    /// the data length with [`set_data_length`](Self::set_data_length).
+    #[inline(always)]
+    pub const fn payload_mut(&mut self) -> &mut [u8] {
+        self.tx_buf.split_at_mut(2).1
+    }
+    /// The data sent by the next transfer, the first
+    /// [`set_data_length`](Self::set_data_length) bytes of the data area.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::WriteBuffer;
+    ///
+    /// let mut write_buffer: WriteBuffer<8> = WriteBuffer::try_new(0, b"hello").unwrap();
+    /// assert_eq!(write_buffer.data(), b"hello");
+    /// write_buffer.set_data_length(4).unwrap();
+    /// assert_eq!(write_buffer.data(), b"hell");
+    /// ```
+    #[inline(always)]
+    pub fn data(&self) -> &[u8] {
+        self.tx_buf
+            .get(2..2 + self.data_length as usize)
+            .unwrap_or_default()
+    }
+}
+impl_command!(WriteBuffer<const N>: N, transfer_length = |command| command.data_length + 2);
+
+/// # WriteBuffer header
+/// Opcode and offset of a WriteBuffer command on their own, for sending a
+/// payload straight from application memory instead of copying it into
+/// [`WriteBuffer::tx_buf`].
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::WriteBufferHeader;
+///
+/// static PAYLOAD: [u8; 5] = *b"hello";
+///
+/// let mut header = WriteBufferHeader::new(0x10);
+/// assert_eq!(header.tx_buf, [0x0E, 0x10]);
+/// assert_eq!(header.rx_buf, [0; 2]);
+/// assert_eq!(header.descriptor().transfer_length(), 2);
+/// let desc = header.scatter_descriptor(&PAYLOAD);
+/// assert_eq!(desc.transfer_length(), 2);
+/// assert_eq!(desc.payload_ptr(), PAYLOAD.as_ptr());
+/// assert_eq!(desc.payload_length(), 5);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WriteBufferHeader {
+    pub tx_buf: [u8; 2],
+    pub rx_buf: [u8; 2],
+}
+impl WriteBufferHeader {
+    const OPCODE: u8 = 0x0E;
+
+    #[inline(always)]
+    pub const fn new(offset: u8) -> Self {
+        Self {
+            tx_buf: [Self::OPCODE, offset],
+            rx_buf: [0; 2],
+        }
+    }
+    /// Descriptor that clocks out the header followed by `payload` within a
+    /// single NSS frame. Like the header, `payload` must stay in place until
+    /// the transfer completes.
+    #[inline(always)]
+    pub const fn scatter_descriptor(&mut self, payload: &[u8]) -> SpiDescriptor {
+        let mut desc = self.descriptor();
+        desc.payload_ptr = payload.as_ptr();
+        desc.payload_length = payload.len() as u16;
+        desc
     }
 }
+impl_command!(WriteBufferHeader: 2);
 
 /// # ReadBuffer command
 /// Reads bytes of payload received starting at offset.
 ///
 /// #### Type Parameter `N`
-/// `N` = data length + 3
+/// `N` = data length + 3, with at most 255 bytes of data:
+/// ```compile_fail
+/// use sx126x_spi_buffers::commands::ReadBuffer;
+///
+/// let read_buffer: ReadBuffer<259> = ReadBuffer::new(0);
+/// ```
 ///
 /// ## Example
 /// ```
@@ -377,13 +2339,37 @@ impl<const N: usize> WriteBuffer<N> {
 /// let mut read_buffer: ReadBuffer<8> = ReadBuffer::new(0x17);
 /// assert_eq!(read_buffer.tx_buf, [0x1E, 0x17, 0, 0, 0, 0, 0, 0]);
 /// assert_eq!(read_buffer.rx_buf, [0; 8]);
-/// assert_eq!(read_buffer.descriptor().transfer_length, 8);
+/// assert_eq!(read_buffer.descriptor().transfer_length(), 8);
 /// read_buffer.rx_buf[3..8].copy_from_slice(&[b'h', b'e', b'l', b'l', b'o']);
 /// assert_eq!(read_buffer.data(), &[b'h', b'e', b'l', b'l', b'o']);
-/// read_buffer.set_data_length(3);
-/// assert_eq!(read_buffer.descriptor().transfer_length, 6);
+/// read_buffer.set_data_length(3).unwrap();
+/// assert_eq!(read_buffer.descriptor().transfer_length(), 6);
 /// assert_eq!(read_buffer.data(), &[b'h', b'e', b'l']);
 /// ```
+///
+/// ## Deferred length
+/// ```
+/// use sx126x_spi_buffers::commands::{GetRxBufferStatus, ReadBuffer};
+///
+/// let mut get_rx_buffer_status = GetRxBufferStatus::new();
+/// let mut read_buffer: ReadBuffer<258> = ReadBuffer::new(0);
+/// let status_desc = get_rx_buffer_status.descriptor();
+/// let mut read_desc = read_buffer.deferred_descriptor(&get_rx_buffer_status);
+/// assert!(read_desc.is_deferred());
+///
+/// // status_desc transferred: 5 bytes received at offset 0x80
+/// unsafe {
+///     *status_desc.rx_buf_ptr().add(2) = 5;
+///     *status_desc.rx_buf_ptr().add(3) = 0x80;
+///     read_desc.resolve_length();
+/// }
+/// assert_eq!(read_desc.transfer_length(), 8);
+/// assert_eq!(read_buffer.tx_buf[1], 0x80);
+/// assert_eq!(read_buffer.data().len(), 5);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ReadBuffer<const N: usize> {
     pub tx_buf: [u8; N],
     pub rx_buf: [u8; N],
@@ -391,9 +2377,13 @@ pub struct ReadBuffer<const N: usize> {
 }
 impl<const N: usize> ReadBuffer<N> {
     const OPCODE: u8 = 0x1E;
+    /// Fails to compile when the data would not fit the 256-byte FIFO in
+    /// one packet.
+    const SIZE_OK: () = assert!(N >= 3 && N - 3 <= 255, "N - 3 must be at most 255");
 
     #[inline(always)]
     pub const fn new(offset: u8) -> Self {
+        let () = Self::SIZE_OK;
         let mut tx_buf = [0; N];
         tx_buf[0] = Self::OPCODE;
         tx_buf[1] = offset;
@@ -403,21 +2393,185 @@ impl<const N: usize> ReadBuffer<N> {
             data_length: N as u16 - 3,
         }
     }
+    /// Descriptor whose offset and length are taken from the response of
+    /// `rx_buffer_status` by [`SpiDescriptor::resolve_length`], for the
+    /// "get length, then read exactly that many bytes" RX flow. Both
+    /// commands must stay in place until the transfer completes.
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: self.data_length + 3,
-        }
+    pub const fn deferred_descriptor(
+        &mut self,
+        rx_buffer_status: &GetRxBufferStatus,
+    ) -> SpiDescriptor {
+        let mut desc = self.descriptor();
+        desc.deferred = Some(DeferredLength {
+            rx_buffer_status: rx_buffer_status.rx_buf.as_ptr(),
+            offset: &mut self.tx_buf[1],
+            data_length: &mut self.data_length,
+            max_data_length: N as u16 - 3,
+        });
+        desc
     }
+    /// Reads `data_length` bytes, at most `N - 3`; the length is left
+    /// untouched on error.
     #[inline(always)]
-    pub const fn set_data_length(&mut self, data_length: u16) {
+    pub const fn set_data_length(&mut self, data_length: u16) -> Result<(), Error> {
+        if data_length as usize > N - 3 {
+            return Err(Error::PayloadTooLong);
+        }
         self.data_length = data_length;
+        Ok(())
     }
     #[inline(always)]
     pub fn data(&self) -> &[u8] {
-        &self.rx_buf[3..3 + self.data_length as usize]
+        self.rx_buf
+            .get(3..3 + self.data_length as usize)
+            .unwrap_or_default()
+    }
+    /// Parses [`data`](Self::data) as a `T`, after checking its length
+    /// against the bounds of `T`.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::{DecodeError, FromRadioPayload, ReadBuffer};
+    ///
+    /// #[derive(Debug, PartialEq)]
+    /// struct Reading {
+    ///     sensor: u8,
+    ///     value: i16,
+    /// }
+    /// impl FromRadioPayload for Reading {
+    ///     const MIN_LENGTH: usize = 3;
+    ///     const MAX_LENGTH: usize = 3;
+    ///     type Error = ();
+    ///
+    ///     fn from_payload(payload: &[u8]) -> Result<Self, ()> {
+    ///         match payload[0] {
+    ///             0 => Err(()),
+    ///             sensor => Ok(Self { sensor, value: i16::from_be_bytes([payload[1], payload[2]]) }),
+    ///         }
+    ///     }
+    /// }
+    ///
+    /// let mut read_buffer: ReadBuffer<8> = ReadBuffer::new(0);
+    /// read_buffer.rx_buf[3..6].copy_from_slice(&[7, 0xFF, 0x38]);
+    /// read_buffer.set_data_length(3).unwrap();
+    /// assert_eq!(read_buffer.decode::<Reading>(), Ok(Reading { sensor: 7, value: -200 }));
+    /// assert_eq!(read_buffer.decode::<[u8; 3]>(), Ok([7, 0xFF, 0x38]));
+    ///
+    /// read_buffer.set_data_length(2).unwrap();
+    /// assert_eq!(read_buffer.decode::<Reading>(), Err(DecodeError::Length(2)));
+    /// read_buffer.rx_buf[3] = 0;
+    /// read_buffer.set_data_length(3).unwrap();
+    /// assert_eq!(read_buffer.decode::<Reading>(), Err(DecodeError::Payload(())));
+    /// ```
+    pub fn decode<T: FromRadioPayload>(&self) -> Result<T, DecodeError<T::Error>> {
+        let data = self.data();
+        if data.len() < T::MIN_LENGTH || data.len() > T::MAX_LENGTH {
+            return Err(DecodeError::Length(data.len()));
+        }
+        T::from_payload(data).map_err(DecodeError::Payload)
+    }
+}
+impl_command!(ReadBuffer<const N>: N, transfer_length = |command| command.data_length + 3);
+
+/// # Radio payload
+/// An application frame type that can be parsed from a received payload,
+/// see [`ReadBuffer::decode`]. `from_payload` is only called with payloads
+/// of `MIN_LENGTH` to `MAX_LENGTH` bytes.
+pub trait FromRadioPayload: Sized {
+    const MIN_LENGTH: usize = 0;
+    const MAX_LENGTH: usize = 255;
+    type Error;
+
+    fn from_payload(payload: &[u8]) -> Result<Self, Self::Error>;
+}
+/// Payloads of exactly `K` bytes.
+impl<const K: usize> FromRadioPayload for [u8; K] {
+    const MIN_LENGTH: usize = K;
+    const MAX_LENGTH: usize = K;
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn from_payload(payload: &[u8]) -> Result<Self, Self::Error> {
+        let mut bytes = [0; K];
+        bytes.copy_from_slice(payload);
+        Ok(bytes)
+    }
+}
+
+/// Errors of [`ReadBuffer::decode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DecodeError<E> {
+    /// The payload length, out of the bounds of the frame type.
+    Length(usize),
+    /// The frame type rejected the payload.
+    Payload(E),
+}
+
+/// # Double-buffered RX
+/// Two GetRxBufferStatus + deferred ReadBuffer descriptor pairs for
+/// continuous RX. Each call to [`descriptors`](Self::descriptors) hands
+/// out the pair for the other payload buffer, so the next packet can be
+/// drained by DMA while the previous payload is still being processed.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::RxDoubleBuffer;
+///
+/// let mut rx: RxDoubleBuffer<258> = RxDoubleBuffer::new();
+/// assert_eq!(rx.next_slot(), 0);
+/// let [status, mut read] = rx.descriptors();
+/// assert_eq!(status.transfer_length(), 4);
+/// assert!(read.is_deferred());
+/// assert_eq!(rx.next_slot(), 1);
+///
+/// // status transferred: 2 bytes at offset 0
+/// unsafe {
+///     *status.rx_buf_ptr().add(2) = 2;
+///     read.resolve_length();
+///     read.rx_buf_ptr().add(3).copy_from([0xAB, 0xCD].as_ptr(), 2);
+/// }
+/// let [_, _] = rx.descriptors(); // next packet goes to slot 1
+/// assert_eq!(rx.payload(0), Some(&[0xAB, 0xCD][..]));
+/// assert_eq!(rx.payload(2), None);
+/// assert_eq!(rx.next_slot(), 0);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RxDoubleBuffer<const N: usize> {
+    pub get_rx_buffer_status: [GetRxBufferStatus; 2],
+    pub read_buffer: [ReadBuffer<N>; 2],
+    next_slot: usize,
+}
+impl<const N: usize> RxDoubleBuffer<N> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            get_rx_buffer_status: [GetRxBufferStatus::new(), GetRxBufferStatus::new()],
+            read_buffer: [ReadBuffer::new(0), ReadBuffer::new(0)],
+            next_slot: 0,
+        }
+    }
+    /// Slot the next call to [`descriptors`](Self::descriptors) fills.
+    #[inline(always)]
+    pub const fn next_slot(&self) -> usize {
+        self.next_slot
+    }
+    /// GetRxBufferStatus and deferred ReadBuffer descriptors for the next
+    /// slot; switches to the other slot afterwards. The payload of a slot
+    /// must be consumed before its descriptors are handed out again.
+    #[inline(always)]
+    pub const fn descriptors(&mut self) -> [SpiDescriptor; 2] {
+        let slot = self.next_slot;
+        self.next_slot ^= 1;
+        [
+            self.get_rx_buffer_status[slot].descriptor(),
+            self.read_buffer[slot].deferred_descriptor(&self.get_rx_buffer_status[slot]),
+        ]
+    }
+    /// Payload received into `slot`, `None` unless it is 0 or 1.
+    #[inline(always)]
+    pub fn payload(&self, slot: usize) -> Option<&[u8]> {
+        self.read_buffer.get(slot).map(ReadBuffer::data)
     }
 }
 
@@ -435,8 +2589,11 @@ impl<const N: usize> ReadBuffer<N> {
 /// );
 /// assert_eq!(SET_DIO_IRQ_PARAMS.tx_buf, [0x08, 0, 1, 0, 2, 2, 0, 0, 0]);
 /// assert_eq!(SET_DIO_IRQ_PARAMS.rx_buf, [0; 9]);
-/// assert_eq!(SET_DIO_IRQ_PARAMS.descriptor().transfer_length, 9);
+/// assert_eq!(SET_DIO_IRQ_PARAMS.descriptor().transfer_length(), 9);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetDioIrqParams {
     pub tx_buf: [u8; 9],
     pub rx_buf: [u8; 9],
@@ -446,30 +2603,48 @@ impl SetDioIrqParams {
 
     #[inline(always)]
     pub const fn new(irq_mask: Irq, dio1_mask: Irq, dio2_mask: Irq, dio3_mask: Irq) -> Self {
+        let [irq_msb, irq_lsb] = irq_mask.to_be_bytes();
+        let [dio1_msb, dio1_lsb] = dio1_mask.to_be_bytes();
+        let [dio2_msb, dio2_lsb] = dio2_mask.to_be_bytes();
+        let [dio3_msb, dio3_lsb] = dio3_mask.to_be_bytes();
         Self {
             tx_buf: [
                 Self::OPCODE,
-                (irq_mask.into_bits() >> 8) as u8,
-                irq_mask.into_bits() as u8,
-                (dio1_mask.into_bits() >> 8) as u8,
-                dio1_mask.into_bits() as u8,
-                (dio2_mask.into_bits() >> 8) as u8,
-                dio2_mask.into_bits() as u8,
-                (dio3_mask.into_bits() >> 8) as u8,
-                dio3_mask.into_bits() as u8,
+                irq_msb,
+                irq_lsb,
+                dio1_msb,
+                dio1_lsb,
+                dio2_msb,
+                dio2_lsb,
+                dio3_msb,
+                dio3_lsb,
             ],
             rx_buf: [0; 9],
         }
     }
-    #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 9,
-        }
-    }
 }
+impl_command!(SetDioIrqParams: 9);
+impl_write_only!(SetDioIrqParams);
+/// IRQ flags, as read by GetIrqStatus and masked by SetDioIrqParams.
+/// Masks can be combined with `|`, `&` and `!`, or in const items with
+/// `union`, `intersection` and `complement`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::Irq;
+///
+/// const RX_IRQS: Irq = Irq::new().with_rx_done(true).with_timeout(true)
+///     .union(Irq::new().with_crc_err(true).with_header_err(true));
+/// assert_eq!(RX_IRQS.into_bits(), 0x0262);
+/// assert_eq!(RX_IRQS & Irq::new().with_timeout(true), Irq::new().with_timeout(true));
+/// assert_eq!((!RX_IRQS).into_bits(), 0x419D);
+/// assert_eq!(!Irq::ALL, Irq::new());
+/// let mut irq = Irq::new().with_tx_done(true);
+/// irq |= Irq::new().with_timeout(true);
+/// assert_eq!(irq, Irq::new().with_tx_done(true) | Irq::new().with_timeout(true));
+/// assert_eq!(irq.to_be_bytes(), [0x02, 0x01]);
+/// assert_eq!(Irq::from_be_bytes([0x02, 0x01]), irq);
+/// ```
 #[bitfield(u16)]
 #[derive(PartialEq, Eq)]
 pub struct Irq {
@@ -500,6 +2675,225 @@ pub struct Irq {
     #[bits(1)]
     __: bool,
 }
+impl Irq {
+    /// Every defined interrupt bit set.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::Irq;
+    ///
+    /// assert_eq!(Irq::ALL.into_bits(), 0x43FF);
+    /// assert!(Irq::ALL.tx_done() && Irq::ALL.timeout() && Irq::ALL.lr_fhss_hop());
+    /// ```
+    pub const ALL: Self = Self::new()
+        .with_tx_done(true)
+        .with_rx_done(true)
+        .with_preamble_detected(true)
+        .with_sync_word_valid(true)
+        .with_header_valid(true)
+        .with_header_err(true)
+        .with_crc_err(true)
+        .with_cad_done(true)
+        .with_cad_detected(true)
+        .with_timeout(true)
+        .with_lr_fhss_hop(true);
+
+    /// Calls the [`IrqHandler`] method of every flag set in `self`, in bit
+    /// order, and returns the flags whose handler reported them as handled,
+    /// ready to be passed to [`ClearIrqStatus::new`].
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::{Irq, IrqHandler};
+    ///
+    /// #[derive(Default)]
+    /// struct Handler {
+    ///     received: bool,
+    /// }
+    /// impl IrqHandler for Handler {
+    ///     fn rx_done(&mut self) -> bool {
+    ///         self.received = true;
+    ///         true
+    ///     }
+    /// }
+    ///
+    /// let mut handler = Handler::default();
+    /// let irq = Irq::new().with_rx_done(true).with_crc_err(true);
+    /// assert_eq!(irq.dispatch(&mut handler), Irq::new().with_rx_done(true));
+    /// assert!(handler.received);
+    /// ```
+    pub fn dispatch<H: IrqHandler + ?Sized>(self, handler: &mut H) -> Irq {
+        let mut handled = Irq::new();
+        if self.tx_done() && handler.tx_done() {
+            handled.set_tx_done(true);
+        }
+        if self.rx_done() && handler.rx_done() {
+            handled.set_rx_done(true);
+        }
+        if self.preamble_detected() && handler.preamble_detected() {
+            handled.set_preamble_detected(true);
+        }
+        if self.sync_word_valid() && handler.sync_word_valid() {
+            handled.set_sync_word_valid(true);
+        }
+        if self.header_valid() && handler.header_valid() {
+            handled.set_header_valid(true);
+        }
+        if self.header_err() && handler.header_err() {
+            handled.set_header_err(true);
+        }
+        if self.crc_err() && handler.crc_err() {
+            handled.set_crc_err(true);
+        }
+        if self.cad_done() && handler.cad_done(self.cad_detected()) {
+            handled.set_cad_done(true);
+            handled.set_cad_detected(self.cad_detected());
+        }
+        if self.timeout() && handler.timeout() {
+            handled.set_timeout(true);
+        }
+        if self.lr_fhss_hop() && handler.lr_fhss_hop() {
+            handled.set_lr_fhss_hop(true);
+        }
+        handled
+    }
+    /// The flags set in `self`, in bit order.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::{Irq, IrqFlag};
+    ///
+    /// let irq = Irq::new().with_timeout(true).with_preamble_detected(true);
+    /// assert!(irq.iter().eq([IrqFlag::PreambleDetected, IrqFlag::Timeout]));
+    /// assert!(irq.any() && !Irq::new().any());
+    /// assert_eq!(irq.count(), 2);
+    /// assert!(irq.contains(Irq::new().with_timeout(true)));
+    /// assert!(!irq.contains(Irq::new().with_timeout(true).with_rx_done(true)));
+    /// assert!(irq.contains(IrqFlag::PreambleDetected.into()));
+    /// ```
+    #[inline(always)]
+    pub const fn iter(&self) -> IrqIter {
+        IrqIter {
+            bits: self.into_bits() & Self::ALL.into_bits(),
+        }
+    }
+    /// Whether any flag is set.
+    #[inline(always)]
+    pub const fn any(&self) -> bool {
+        self.into_bits() & Self::ALL.into_bits() != 0
+    }
+    /// Number of flags set.
+    #[inline(always)]
+    pub const fn count(&self) -> u32 {
+        (self.into_bits() & Self::ALL.into_bits()).count_ones()
+    }
+    /// Whether every flag of `other` is set in `self`.
+    #[inline(always)]
+    pub const fn contains(&self, other: Irq) -> bool {
+        self.into_bits() & other.into_bits() == other.into_bits()
+    }
+}
+impl_flag_ops!(Irq);
+
+/// A single IRQ flag, valued as its bit number.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IrqFlag {
+    TxDone = 0,
+    RxDone = 1,
+    PreambleDetected = 2,
+    SyncWordValid = 3,
+    HeaderValid = 4,
+    HeaderErr = 5,
+    CrcErr = 6,
+    CadDone = 7,
+    CadDetected = 8,
+    Timeout = 9,
+    LrFhssHop = 14,
+}
+impl IrqFlag {
+    #[inline(always)]
+    pub const fn irq(self) -> Irq {
+        Irq::from_bits(1 << self as u8)
+    }
+}
+impl From<IrqFlag> for Irq {
+    #[inline(always)]
+    fn from(flag: IrqFlag) -> Self {
+        flag.irq()
+    }
+}
+
+/// Iterator over the flags of an [`Irq`], see [`Irq::iter`].
+#[derive(Clone, Debug)]
+pub struct IrqIter {
+    bits: u16,
+}
+impl Iterator for IrqIter {
+    type Item = IrqFlag;
+
+    fn next(&mut self) -> Option<IrqFlag> {
+        let flag = match self.bits.trailing_zeros() {
+            0 => IrqFlag::TxDone,
+            1 => IrqFlag::RxDone,
+            2 => IrqFlag::PreambleDetected,
+            3 => IrqFlag::SyncWordValid,
+            4 => IrqFlag::HeaderValid,
+            5 => IrqFlag::HeaderErr,
+            6 => IrqFlag::CrcErr,
+            7 => IrqFlag::CadDone,
+            8 => IrqFlag::CadDetected,
+            9 => IrqFlag::Timeout,
+            14 => IrqFlag::LrFhssHop,
+            _ => return None,
+        };
+        self.bits &= self.bits - 1;
+        Some(flag)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.bits.count_ones() as usize;
+        (count, Some(count))
+    }
+}
+impl ExactSizeIterator for IrqIter {}
+
+/// Per-event callbacks for [`Irq::dispatch`], e.g. from the DIO1 ISR. Each
+/// method returns whether the event was handled; unimplemented events are
+/// left unhandled.
+pub trait IrqHandler {
+    fn tx_done(&mut self) -> bool {
+        false
+    }
+    fn rx_done(&mut self) -> bool {
+        false
+    }
+    fn preamble_detected(&mut self) -> bool {
+        false
+    }
+    fn sync_word_valid(&mut self) -> bool {
+        false
+    }
+    fn header_valid(&mut self) -> bool {
+        false
+    }
+    fn header_err(&mut self) -> bool {
+        false
+    }
+    fn crc_err(&mut self) -> bool {
+        false
+    }
+    /// CadDone, with CadDetected passed as `detected`.
+    fn cad_done(&mut self, detected: bool) -> bool {
+        let _ = detected;
+        false
+    }
+    fn timeout(&mut self) -> bool {
+        false
+    }
+    fn lr_fhss_hop(&mut self) -> bool {
+        false
+    }
+}
 
 /// # GetIrqStatus command
 /// Retrieves the value of the IRQ register.
@@ -510,10 +2904,13 @@ pub struct Irq {
 /// let mut get_irq_status: GetIrqStatus = GetIrqStatus::new();
 /// assert_eq!(get_irq_status.tx_buf, [0x12, 0, 0, 0]);
 /// assert_eq!(get_irq_status.rx_buf, [0; 4]);
-/// assert_eq!(get_irq_status.descriptor().transfer_length, 4);
+/// assert_eq!(get_irq_status.descriptor().transfer_length(), 4);
 /// get_irq_status.rx_buf[3] = 0x03;
 /// assert_eq!(get_irq_status.irq_status(), Irq::new().with_tx_done(true).with_rx_done(true).with_timeout(false));
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GetIrqStatus {
     pub tx_buf: [u8; 4],
     pub rx_buf: [u8; 4],
@@ -529,16 +2926,22 @@ impl GetIrqStatus {
         }
     }
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 4,
-        }
+    pub const fn irq_status(&self) -> Irq {
+        Irq::from_be_bytes([self.rx_buf[2], self.rx_buf[3]])
     }
+}
+impl Response for GetIrqStatus {
+    type Output = Irq;
+
     #[inline(always)]
-    pub const fn irq_status(&self) -> Irq {
-        Irq::from_bits((self.rx_buf[2] as u16) << 8 | (self.rx_buf[3] as u16))
+    fn parse(&self) -> Irq {
+        self.irq_status()
+    }
+}
+impl_command!(GetIrqStatus: 4);
+impl Default for GetIrqStatus {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -553,8 +2956,14 @@ impl GetIrqStatus {
 ///     .with_timeout(true));
 /// assert_eq!(CLEAR_IRQ_STATUS.tx_buf, [0x02, 2, 16]);
 /// assert_eq!(CLEAR_IRQ_STATUS.rx_buf, [0; 3]);
-/// assert_eq!(CLEAR_IRQ_STATUS.descriptor().transfer_length, 3);
+/// assert_eq!(CLEAR_IRQ_STATUS.descriptor().transfer_length(), 3);
+///
+/// const CLEAR_ALL: ClearIrqStatus = ClearIrqStatus::all();
+/// assert_eq!(CLEAR_ALL.tx_buf, [0x02, 0x43, 0xFF]);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ClearIrqStatus {
     pub tx_buf: [u8; 3],
     pub rx_buf: [u8; 3],
@@ -564,24 +2973,20 @@ impl ClearIrqStatus {
 
     #[inline(always)]
     pub const fn new(clear_irq_param: Irq) -> Self {
+        let [msb, lsb] = clear_irq_param.to_be_bytes();
         Self {
-            tx_buf: [
-                Self::OPCODE,
-                (clear_irq_param.into_bits() >> 8) as u8,
-                clear_irq_param.into_bits() as u8,
-            ],
+            tx_buf: [Self::OPCODE, msb, lsb],
             rx_buf: [0; 3],
         }
     }
+    /// Clears every IRQ flag, see [`Irq::ALL`].
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 3,
-        }
+    pub const fn all() -> Self {
+        Self::new(Irq::ALL)
     }
 }
+impl_command!(ClearIrqStatus: 3);
+impl_write_only!(ClearIrqStatus);
 
 /// # SetDio2AsRfSwitchCtrl command
 /// Used to configure DIO2 so that it can be used to control an external RF switch.
@@ -593,8 +2998,11 @@ impl ClearIrqStatus {
 /// const SET_DIO2_AS_RF_SWITCH_CTRL: SetDio2AsRfSwitchCtrl = SetDio2AsRfSwitchCtrl::new(true);
 /// assert_eq!(SET_DIO2_AS_RF_SWITCH_CTRL.tx_buf, [0x9D, 1]);
 /// assert_eq!(SET_DIO2_AS_RF_SWITCH_CTRL.rx_buf, [0; 2]);
-/// assert_eq!(SET_DIO2_AS_RF_SWITCH_CTRL.descriptor().transfer_length, 2);
+/// assert_eq!(SET_DIO2_AS_RF_SWITCH_CTRL.descriptor().transfer_length(), 2);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetDio2AsRfSwitchCtrl {
     pub tx_buf: [u8; 2],
     pub rx_buf: [u8; 2],
@@ -609,18 +3017,19 @@ impl SetDio2AsRfSwitchCtrl {
             rx_buf: [0; 2],
         }
     }
-    #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 2,
-        }
-    }
 }
+impl_command!(SetDio2AsRfSwitchCtrl: 2);
+impl_write_only!(SetDio2AsRfSwitchCtrl);
 
 /// # SetDio3AsTcxoCtrl command
 /// Configures the chip for an external TCXO reference voltage controlled by DIO3.
+/// `delay` is the time the TCXO is given to start, in steps of 15.625 µs;
+/// XOSC_START_ERR is raised if it has not started by then. BUSY may stay
+/// high for that long, so the BUSY timeout is the delay plus
+/// [`DEFAULT_BUSY_TIMEOUT_US`].
+///
+/// Most TCXOs start within 1 to 3 ms; the Semtech reference designs allow
+/// 5 ms, a safe value unless the TCXO datasheet says otherwise.
 ///
 /// ## Example
 /// ```
@@ -628,9 +3037,15 @@ impl SetDio2AsRfSwitchCtrl {
 ///
 /// const SET_DIO3_AS_TCXO_CTRL: SetDio3AsTcxoCtrl = SetDio3AsTcxoCtrl::new(TcxoVoltage::V3_3, 3500);
 /// assert_eq!(SET_DIO3_AS_TCXO_CTRL.tx_buf, [0x97, 7, 0, 13, 172]);
+/// assert_eq!(SetDio3AsTcxoCtrl::from_ms(TcxoVoltage::V1_8, 5).tx_buf, [0x97, 2, 0, 1, 64]);
 /// assert_eq!(SET_DIO3_AS_TCXO_CTRL.rx_buf, [0; 5]);
-/// assert_eq!(SET_DIO3_AS_TCXO_CTRL.descriptor().transfer_length, 5);
+/// assert_eq!(SET_DIO3_AS_TCXO_CTRL.descriptor().transfer_length(), 5);
+/// assert_eq!(SET_DIO3_AS_TCXO_CTRL.delay_us(), 54_687);
+/// assert_eq!(SetDio3AsTcxoCtrl::from_ms(TcxoVoltage::V1_8, 5).busy_timeout_us(), 6_000);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetDio3AsTcxoCtrl {
     pub tx_buf: [u8; 5],
     pub rx_buf: [u8; 5],
@@ -651,16 +3066,57 @@ impl SetDio3AsTcxoCtrl {
             rx_buf: [0; 5],
         }
     }
+    /// Same as [`new`](Self::new), rejecting delays that do not fit the
+    /// 24 bits of the command instead of dropping the high byte.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::{Error, SetDio3AsTcxoCtrl, TcxoVoltage};
+    ///
+    /// let set_dio3_as_tcxo_ctrl = SetDio3AsTcxoCtrl::try_new(TcxoVoltage::V1_8, 0xFF_FFFF);
+    /// assert_eq!(set_dio3_as_tcxo_ctrl.unwrap().tx_buf, [0x97, 2, 0xFF, 0xFF, 0xFF]);
+    /// assert_eq!(
+    ///     SetDio3AsTcxoCtrl::try_new(TcxoVoltage::V1_8, 0x100_0000),
+    ///     Err(Error::OutOfRange)
+    /// );
+    /// ```
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 5,
+    pub const fn try_new(tcxo_voltage: TcxoVoltage, delay: u32) -> Result<Self, Error> {
+        if delay > 0xFF_FFFF {
+            return Err(Error::OutOfRange);
         }
+        Ok(Self::new(tcxo_voltage, delay))
+    }
+    /// Same as [`new`](Self::new) with a delay in milliseconds, saturating
+    /// at 0xFFFFFF steps (about 262 s).
+    #[inline(always)]
+    pub const fn from_ms(tcxo_voltage: TcxoVoltage, delay_ms: u32) -> Self {
+        let delay = delay_ms as u64 * 64;
+        Self::new(
+            tcxo_voltage,
+            if delay > 0xFF_FFFF {
+                0xFF_FFFF
+            } else {
+                delay as u32
+            },
+        )
+    }
+    /// The start-up delay in microseconds, rounded down.
+    #[inline(always)]
+    pub const fn delay_us(&self) -> u32 {
+        let delay =
+            (self.tx_buf[2] as u32) << 16 | (self.tx_buf[3] as u32) << 8 | self.tx_buf[4] as u32;
+        // 15.625 µs steps; 0xFFFFFF * 125 still fits a u32.
+        delay * 125 / 8
     }
 }
+impl_command!(
+    SetDio3AsTcxoCtrl: 5,
+    busy_timeout_us = |command| command.delay_us() + DEFAULT_BUSY_TIMEOUT_US
+);
+impl_write_only!(SetDio3AsTcxoCtrl);
 #[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum TcxoVoltage {
     V1_6 = 0x00,
     V1_7 = 0x01,
@@ -671,6 +3127,173 @@ pub enum TcxoVoltage {
     V3_0 = 0x06,
     V3_3 = 0x07,
 }
+impl_try_from_u8!(TcxoVoltage: V1_6, V1_7, V1_8, V2_2, V2_4, V2_7, V3_0, V3_3);
+
+/// # Calibrate command
+/// Calibrates the blocks selected in `calib_param`, as done at power-up,
+/// e.g. after [`SetDio3AsTcxoCtrl`]. Calibrating every block takes about
+/// 3.5 ms; with a TCXO, the chip first waits for its start-up delay, which
+/// has to be added to the BUSY timeout.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{Calibrate, CalibParam, SetDio3AsTcxoCtrl, TcxoVoltage};
+///
+/// const CALIBRATE: Calibrate = Calibrate::new(CalibParam::ALL);
+/// assert_eq!(CALIBRATE.tx_buf, [0x89, 0x7F]);
+/// assert_eq!(CALIBRATE.rx_buf, [0; 2]);
+/// assert_eq!(CALIBRATE.descriptor().transfer_length(), 2);
+/// assert_eq!(CALIBRATE.descriptor().busy_timeout_us(), 5_000);
+///
+/// let tcxo = SetDio3AsTcxoCtrl::from_ms(TcxoVoltage::V1_8, 5);
+/// let mut calibrate = Calibrate::new(CalibParam::new().with_pll(true));
+/// let desc = calibrate.descriptor().with_busy_timeout_us(5_000 + tcxo.delay_us());
+/// assert_eq!(desc.busy_timeout_us(), 10_000);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Calibrate {
+    pub tx_buf: [u8; 2],
+    pub rx_buf: [u8; 2],
+}
+impl Calibrate {
+    const OPCODE: u8 = 0x89;
+
+    #[inline(always)]
+    pub const fn new(calib_param: CalibParam) -> Self {
+        Self {
+            tx_buf: [Self::OPCODE, calib_param.into_bits()],
+            rx_buf: [0; 2],
+        }
+    }
+}
+// About 3.5 ms for every block.
+impl_command!(Calibrate: 2, BUSY_TIMEOUT_US = 5_000);
+impl_write_only!(Calibrate);
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct CalibParam {
+    #[bits(1)]
+    pub rc64k: bool,
+    #[bits(1)]
+    pub rc13m: bool,
+    #[bits(1)]
+    pub pll: bool,
+    #[bits(1)]
+    pub adc_pulse: bool,
+    #[bits(1)]
+    pub adc_bulk_n: bool,
+    #[bits(1)]
+    pub adc_bulk_p: bool,
+    #[bits(1)]
+    pub image: bool,
+    #[bits(1)]
+    __: bool,
+}
+impl CalibParam {
+    /// Every block, as calibrated at power-up.
+    pub const ALL: Self = Self::from_bits(0x7F);
+}
+
+/// # CalibrateImage command
+/// Calibrates the image rejection for the band `freq1 * 4` to `freq2 * 4`
+/// MHz, e.g. when the device moves to a band other than the one calibrated
+/// at power-up (902 to 928 MHz).
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::CalibrateImage;
+///
+/// const CALIBRATE_IMAGE: CalibrateImage = CalibrateImage::from_mhz(863, 870);
+/// assert_eq!(CALIBRATE_IMAGE.tx_buf, [0x98, 0xD7, 0xDA]);
+/// assert_eq!(CALIBRATE_IMAGE.rx_buf, [0; 3]);
+/// assert_eq!(CALIBRATE_IMAGE.descriptor().transfer_length(), 3);
+/// assert_eq!(CALIBRATE_IMAGE.descriptor().busy_timeout_us(), 5_000);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CalibrateImage {
+    pub tx_buf: [u8; 3],
+    pub rx_buf: [u8; 3],
+}
+impl CalibrateImage {
+    const OPCODE: u8 = 0x98;
+
+    #[inline(always)]
+    pub const fn new(freq1: u8, freq2: u8) -> Self {
+        Self {
+            tx_buf: [Self::OPCODE, freq1, freq2],
+            rx_buf: [0; 3],
+        }
+    }
+    /// Covers `min_mhz..=max_mhz`, rounding the band outwards to 4 MHz.
+    #[inline(always)]
+    pub const fn from_mhz(min_mhz: u16, max_mhz: u16) -> Self {
+        let freq2 = max_mhz.div_ceil(4);
+        Self::new(
+            (min_mhz / 4) as u8,
+            if freq2 > u8::MAX as u16 {
+                u8::MAX
+            } else {
+                freq2 as u8
+            },
+        )
+    }
+}
+// Part of the 3.5 ms of a full calibration.
+impl_command!(CalibrateImage: 3, BUSY_TIMEOUT_US = 5_000);
+impl_write_only!(CalibrateImage);
+
+/// Frequency of the crystal oscillator.
+pub const XTAL_FREQ_HZ: u32 = 32_000_000;
+
+/// Converts a frequency in Hz to the PLL steps of SetRfFrequency,
+/// `hz * 2^25 / 32 MHz`, rounded to the nearest step. Frequencies above
+/// 4096 MHz saturate.
+///
+/// ```
+/// use sx126x_spi_buffers::commands::rf_freq_from_hz;
+///
+/// assert_eq!(rf_freq_from_hz(868_000_000), 0x3640_0000);
+/// assert_eq!(rf_freq_from_hz(915_000_000), 0x3930_0000);
+/// assert_eq!(rf_freq_from_hz(u32::MAX), u32::MAX);
+/// ```
+#[inline(always)]
+pub const fn rf_freq_from_hz(hz: u32) -> u32 {
+    let steps = (((hz as u64) << 25) + XTAL_FREQ_HZ as u64 / 2) / XTAL_FREQ_HZ as u64;
+    if steps > u32::MAX as u64 {
+        u32::MAX
+    } else {
+        steps as u32
+    }
+}
+
+/// Same as [`rf_freq_from_hz`] for a crystal off by `xtal_error_ppm`, e.g.
+/// from factory calibration: a crystal running fast by 10 ppm also makes
+/// the RF frequency 10 ppm high, so fewer PLL steps are programmed.
+///
+/// ```
+/// use sx126x_spi_buffers::commands::{rf_freq_from_hz, rf_freq_from_hz_ppm};
+///
+/// assert_eq!(rf_freq_from_hz_ppm(868_000_000, 0), rf_freq_from_hz(868_000_000));
+/// // 8680 Hz lower on a perfect crystal
+/// assert_eq!(rf_freq_from_hz_ppm(868_000_000, 10), 0x3640_0000 - 9_102);
+/// assert_eq!(rf_freq_from_hz_ppm(868_000_000, -10), 0x3640_0000 + 9_102);
+/// ```
+#[inline(always)]
+pub const fn rf_freq_from_hz_ppm(hz: u32, xtal_error_ppm: i16) -> u32 {
+    // hz * 2^25 / (32 MHz * (1 + ppm / 10^6))
+    let numerator = ((hz as u128) << 25) * 1_000_000;
+    let denominator = XTAL_FREQ_HZ as u128 * (1_000_000 + xtal_error_ppm as i32) as u128;
+    let steps = (numerator + denominator / 2) / denominator;
+    if steps > u32::MAX as u128 {
+        u32::MAX
+    } else {
+        steps as u32
+    }
+}
 
 /// # SetRfFrequency command
 /// Sets the RF frequency for the device.
@@ -681,9 +3304,13 @@ pub enum TcxoVoltage {
 ///
 /// const SET_RF_FREQUENCY: SetRfFrequency = SetRfFrequency::new(455_081_984);
 /// assert_eq!(SET_RF_FREQUENCY.tx_buf, [0x86, 0x1B, 0x20, 0, 0]);
+/// assert_eq!(SetRfFrequency::from_hz(868_000_000).tx_buf, [0x86, 0x36, 0x40, 0, 0]);
 /// assert_eq!(SET_RF_FREQUENCY.rx_buf, [0; 5]);
-/// assert_eq!(SET_RF_FREQUENCY.descriptor().transfer_length, 5);
+/// assert_eq!(SET_RF_FREQUENCY.descriptor().transfer_length(), 5);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetRfFrequency {
     pub tx_buf: [u8; 5],
     pub rx_buf: [u8; 5],
@@ -704,15 +3331,21 @@ impl SetRfFrequency {
             rx_buf: [0; 5],
         }
     }
+    /// Same as [`new`](Self::new) with a frequency in Hz, see
+    /// [`rf_freq_from_hz`].
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 5,
-        }
+    pub const fn from_hz(hz: u32) -> Self {
+        Self::new(rf_freq_from_hz(hz))
+    }
+    /// Same as [`from_hz`](Self::from_hz) corrected for a crystal error, see
+    /// [`rf_freq_from_hz_ppm`].
+    #[inline(always)]
+    pub const fn from_hz_ppm(hz: u32, xtal_error_ppm: i16) -> Self {
+        Self::new(rf_freq_from_hz_ppm(hz, xtal_error_ppm))
     }
 }
+impl_command!(SetRfFrequency: 5);
+impl_write_only!(SetRfFrequency);
 
 /// # SetPacketType command
 /// Sets the packet type for the device.
@@ -723,8 +3356,11 @@ impl SetRfFrequency {
 /// const SET_PACKET_TYPE: SetPacketType = SetPacketType::new(PacketType::Lora);
 /// assert_eq!(SET_PACKET_TYPE.tx_buf, [0x8A, 0x01]);
 /// assert_eq!(SET_PACKET_TYPE.rx_buf, [0; 2]);
-/// assert_eq!(SET_PACKET_TYPE.descriptor().transfer_length, 2);
+/// assert_eq!(SET_PACKET_TYPE.descriptor().transfer_length(), 2);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetPacketType {
     pub tx_buf: [u8; 2],
     pub rx_buf: [u8; 2],
@@ -739,17 +3375,11 @@ impl SetPacketType {
             rx_buf: [0; 2],
         }
     }
-    #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 2,
-        }
-    }
 }
+impl_command!(SetPacketType: 2);
+impl_write_only!(SetPacketType);
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PacketType {
     Gfsk = 0x00,
     Lora = 0x01,
@@ -757,11 +3387,14 @@ pub enum PacketType {
     LrFhss = 0x03,
 }
 impl PacketType {
+    /// Keeps the low two bits of `value`, reserved encodings
+    /// included; `try_from` rejects them.
     #[inline(always)]
-    pub const fn from(value: u8) -> Self {
+    pub const fn from_bits_truncate(value: u8) -> Self {
         unsafe { core::mem::transmute(value & 0x03) }
     }
 }
+impl_try_from_u8!(PacketType: Gfsk, Lora, LrFhss);
 
 /// # GetPacketType command
 /// Retrieves the current packet type of the device.
@@ -772,9 +3405,12 @@ impl PacketType {
 /// const GET_PACKET_TYPE: GetPacketType = GetPacketType::new();
 /// assert_eq!(GET_PACKET_TYPE.tx_buf, [0x11, 0, 0]);
 /// assert_eq!(GET_PACKET_TYPE.rx_buf, [0; 3]);
-/// assert_eq!(GET_PACKET_TYPE.descriptor().transfer_length, 3);
+/// assert_eq!(GET_PACKET_TYPE.descriptor().transfer_length(), 3);
 /// assert_eq!(GET_PACKET_TYPE.packet_type(), PacketType::Gfsk);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GetPacketType {
     pub tx_buf: [u8; 3],
     pub rx_buf: [u8; 3],
@@ -790,16 +3426,22 @@ impl GetPacketType {
         }
     }
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 3,
-        }
+    pub const fn packet_type(&self) -> PacketType {
+        PacketType::from_bits_truncate(self.rx_buf[2])
     }
+}
+impl Response for GetPacketType {
+    type Output = PacketType;
+
     #[inline(always)]
-    pub const fn packet_type(&self) -> PacketType {
-        PacketType::from(self.rx_buf[2])
+    fn parse(&self) -> PacketType {
+        self.packet_type()
+    }
+}
+impl_command!(GetPacketType: 3);
+impl Default for GetPacketType {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -812,8 +3454,11 @@ impl GetPacketType {
 /// const SET_TX_PARAMS: SetTxParams = SetTxParams::new(22, RampTime::Ramp200U);
 /// assert_eq!(SET_TX_PARAMS.tx_buf, [0x8E, 22, 4]);
 /// assert_eq!(SET_TX_PARAMS.rx_buf, [0; 3]);
-/// assert_eq!(SET_TX_PARAMS.descriptor().transfer_length, 3);
+/// assert_eq!(SET_TX_PARAMS.descriptor().transfer_length(), 3);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetTxParams {
     pub tx_buf: [u8; 3],
     pub rx_buf: [u8; 3],
@@ -828,17 +3473,30 @@ impl SetTxParams {
             rx_buf: [0; 3],
         }
     }
+    /// Same as [`new`](Self::new) with the power in dBm, rejecting values
+    /// outside the -9..=22 dBm of the SX1262 high power PA.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::{Error, RampTime, SetTxParams};
+    ///
+    /// let set_tx_params = SetTxParams::try_new(-9, RampTime::Ramp40U).unwrap();
+    /// assert_eq!(set_tx_params.tx_buf, [0x8E, 0xF7, 2]);
+    /// assert_eq!(SetTxParams::try_new(23, RampTime::Ramp40U), Err(Error::OutOfRange));
+    /// assert_eq!(SetTxParams::try_new(-10, RampTime::Ramp40U), Err(Error::OutOfRange));
+    /// ```
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 3,
+    pub const fn try_new(power_dbm: i8, ramp_time: RampTime) -> Result<Self, Error> {
+        if power_dbm < -9 || power_dbm > 22 {
+            return Err(Error::OutOfRange);
         }
+        Ok(Self::new(power_dbm as u8, ramp_time))
     }
 }
+impl_command!(SetTxParams: 3);
+impl_write_only!(SetTxParams);
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum RampTime {
     Ramp10U = 0x00,
     Ramp20U = 0x01,
@@ -850,11 +3508,106 @@ pub enum RampTime {
     Ramp3400U = 0x07,
 }
 impl RampTime {
+    /// Keeps the low three bits of `value`, reserved encodings
+    /// included; `try_from` rejects them.
     #[inline(always)]
-    pub const fn from(value: u8) -> Self {
+    pub const fn from_bits_truncate(value: u8) -> Self {
         unsafe { core::mem::transmute(value & 0x07) }
     }
 }
+impl_try_from_u8!(RampTime: Ramp10U, Ramp20U, Ramp40U, Ramp80U, Ramp200U, Ramp800U, Ramp1700U, Ramp3400U);
+
+/// # TX power sequence
+/// SetPaConfig and SetTxParams for an output power in dBm, after the
+/// optimal settings table of the datasheet: the PA is configured for the
+/// nearest tabulated power at or above the target, and the power parameter
+/// lowered by the difference. [`new`](Self::new) returns `None` outside
+/// -9 to +22 dBm for the high-power PA and -17 to +15 dBm for the
+/// low-power PA. Send the descriptors in order.
+///
+/// The [`Pa`] comes from the board design; do not derive it from
+/// [`ReadVersion`], which reports SX1262 parts as SX1261.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{Pa, RampTime, TxPower};
+///
+/// let mut tx_power = TxPower::new(Pa::HighPower, 14, RampTime::Ramp200U).unwrap();
+/// assert_eq!(tx_power.set_pa_config.tx_buf, [0x95, 0x02, 0x02, 0x00, 0x01]);
+/// assert_eq!(tx_power.set_tx_params.tx_buf, [0x8E, 22, 0x04]);
+/// assert_eq!(tx_power.descriptors().map(|d| d.transfer_length()), [5, 3]);
+///
+/// let tx_power = TxPower::new(Pa::HighPower, 12, RampTime::Ramp200U).unwrap();
+/// assert_eq!(tx_power.set_tx_params.tx_buf, [0x8E, 20, 0x04]);
+/// let tx_power = TxPower::new(Pa::HighPowerSx1268, 22, RampTime::Ramp200U).unwrap();
+/// assert_eq!(tx_power.set_pa_config.tx_buf, [0x95, 0x04, 0x06, 0x00, 0x01]);
+/// let tx_power = TxPower::new(Pa::LowPower, 10, RampTime::Ramp40U).unwrap();
+/// assert_eq!(tx_power.set_pa_config.tx_buf, [0x95, 0x01, 0x00, 0x01, 0x01]);
+/// assert_eq!(tx_power.set_tx_params.tx_buf, [0x8E, 13, 0x02]);
+/// assert!(TxPower::new(Pa::LowPower, 22, RampTime::Ramp40U).is_none());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxPower {
+    pub set_pa_config: SetPaConfig,
+    pub set_tx_params: SetTxParams,
+}
+impl TxPower {
+    #[inline(always)]
+    pub const fn new(pa: Pa, power_dbm: i8, ramp_time: RampTime) -> Option<Self> {
+        // (dBm, paDutyCycle, hpMax, power) in ascending power
+        let (min_dbm, table): (i8, &[(i8, u8, u8, i8)]) = match pa {
+            Pa::HighPower => (
+                -9,
+                &[
+                    (14, 0x02, 0x02, 22),
+                    (17, 0x02, 0x03, 22),
+                    (20, 0x03, 0x05, 22),
+                    (22, 0x04, 0x07, 22),
+                ],
+            ),
+            Pa::HighPowerSx1268 => (
+                -9,
+                &[
+                    (14, 0x02, 0x02, 22),
+                    (17, 0x02, 0x03, 22),
+                    (20, 0x03, 0x05, 22),
+                    (22, 0x04, 0x06, 22),
+                ],
+            ),
+            Pa::LowPower => (
+                -17,
+                &[
+                    (10, 0x01, 0x00, 13),
+                    (14, 0x04, 0x00, 14),
+                    (15, 0x06, 0x00, 14),
+                ],
+            ),
+        };
+        if power_dbm < min_dbm {
+            return None;
+        }
+        let mut i = 0;
+        while i < table.len() {
+            let (dbm, pa_duty_cycle, hp_max, power) = table[i];
+            if power_dbm <= dbm {
+                return Some(Self {
+                    set_pa_config: SetPaConfig::new(pa_duty_cycle, hp_max)
+                        .with_device_sel(pa.device_sel()),
+                    set_tx_params: SetTxParams::new((power - (dbm - power_dbm)) as u8, ramp_time),
+                });
+            }
+            i += 1;
+        }
+        None
+    }
+    #[inline(always)]
+    pub const fn descriptors(&mut self) -> [SpiDescriptor; 2] {
+        [
+            self.set_pa_config.descriptor(),
+            self.set_tx_params.descriptor(),
+        ]
+    }
+}
 
 /// # SetModulationParamsLora command
 /// Configures the LoRa modulation parameters of the radio.
@@ -870,8 +3623,14 @@ impl RampTime {
 /// );
 /// assert_eq!(SET_MODULATION_PARAMS_LORA.tx_buf, [0x8B, 0x0A, 0x04, 0x01, 0]);
 /// assert_eq!(SET_MODULATION_PARAMS_LORA.rx_buf, [0; 5]);
-/// assert_eq!(SET_MODULATION_PARAMS_LORA.descriptor().transfer_length, 5);
+/// assert_eq!(SET_MODULATION_PARAMS_LORA.descriptor().transfer_length(), 5);
+///
+/// let sf12 = SetModulationParamsLora::new_auto_ldro(Sf::Sf12, Bw::Bw125, Cr::Cr4_5);
+/// assert_eq!(sf12.tx_buf, [0x8B, 0x0C, 0x04, 0x01, 1]);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetModulationParamsLora {
     pub tx_buf: [u8; 5],
     pub rx_buf: [u8; 5],
@@ -892,16 +3651,22 @@ impl SetModulationParamsLora {
             rx_buf: [0; 5],
         }
     }
+    /// Like [`new`](Self::new), with LDRO set by [`low_data_rate_optimize`].
+    /// Named alternative to [`new`](Self::new), see
+    /// [`ModulationParamsLoraBuilder`].
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 5,
-        }
+    pub const fn builder() -> ModulationParamsLoraBuilder {
+        ModulationParamsLoraBuilder::new()
+    }
+    #[inline(always)]
+    pub const fn new_auto_ldro(sf: Sf, bw: Bw, cr: Cr) -> Self {
+        Self::new(sf, bw, cr, low_data_rate_optimize(sf, bw))
     }
 }
+impl_command!(SetModulationParamsLora: 5);
+impl_write_only!(SetModulationParamsLora);
 #[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Sf {
     Reserved1 = 0x00,
     Reserved2 = 0x01,
@@ -921,50 +3686,561 @@ pub enum Sf {
     Reserved8 = 0x0F,
 }
 impl Sf {
+    /// Keeps the low four bits of `value`, reserved encodings
+    /// included; `try_from` rejects them.
+    #[inline(always)]
+    pub const fn from_bits_truncate(value: u8) -> Self {
+        unsafe { core::mem::transmute(value & 0x0F) }
+    }
+    /// 5 to 12; 0 for the reserved encodings.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::Sf;
+    ///
+    /// assert_eq!(Sf::Sf9.spreading_factor(), 9);
+    /// assert_eq!(Sf::Reserved6.spreading_factor(), 0);
+    /// assert_eq!(format!("{}", Sf::Sf12), "SF12");
+    /// ```
+    #[inline(always)]
+    pub const fn spreading_factor(&self) -> u8 {
+        match *self as u8 {
+            sf @ 5..=12 => sf,
+            _ => 0,
+        }
+    }
+}
+impl_try_from_u8!(Sf: Sf5, Sf6, Sf7, Sf8, Sf9, Sf10, Sf11, Sf12);
+impl core::fmt::Display for Sf {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.spreading_factor() {
+            0 => write!(f, "reserved SF ({:#04x})", *self as u8),
+            sf => write!(f, "SF{sf}"),
+        }
+    }
+}
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bw {
+    Bw7_8 = 0x00,
+    Bw10_42 = 0x08,
+    Bw15_63 = 0x01,
+    Bw20_83 = 0x09,
+    Bw31_25 = 0x02,
+    Bw41_67 = 0x0A,
+    Bw62_50 = 0x03,
+    Bw125 = 0x04,
+    Bw250 = 0x05,
+    Bw500 = 0x06,
+    Reserved1 = 0x0B,
+    Reserved2 = 0x0C,
+    Reserved3 = 0x0D,
+    Reserved4 = 0x0E,
+    Reserved5 = 0x0F,
+    Reserved6 = 0x07,
+}
+impl Bw {
+    /// Keeps the low four bits of `value`, reserved encodings
+    /// included; `try_from` rejects them.
+    #[inline(always)]
+    pub const fn from_bits_truncate(value: u8) -> Self {
+        unsafe { core::mem::transmute(value & 0x0F) }
+    }
+    /// Bandwidth in Hz, rounded down; 0 for the reserved encodings.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::Bw;
+    ///
+    /// assert_eq!(Bw::Bw125.hz(), 125_000);
+    /// assert_eq!(Bw::Bw10_42.hz(), 10_416);
+    /// assert_eq!(Bw::Reserved1.hz(), 0);
+    /// assert_eq!(format!("{}", Bw::Bw62_50), "62.5 kHz");
+    /// ```
+    #[inline(always)]
+    pub const fn hz(&self) -> u32 {
+        match chip_time_us(*self) {
+            0 => 0,
+            chip_time_us => 1_000_000 / chip_time_us,
+        }
+    }
+}
+impl_try_from_u8!(Bw: Bw7_8, Bw10_42, Bw15_63, Bw20_83, Bw31_25, Bw41_67, Bw62_50, Bw125, Bw250, Bw500);
+impl core::fmt::Display for Bw {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let khz = match self {
+            Self::Bw7_8 => "7.81",
+            Self::Bw10_42 => "10.42",
+            Self::Bw15_63 => "15.63",
+            Self::Bw20_83 => "20.83",
+            Self::Bw31_25 => "31.25",
+            Self::Bw41_67 => "41.67",
+            Self::Bw62_50 => "62.5",
+            Self::Bw125 => "125",
+            Self::Bw250 => "250",
+            Self::Bw500 => "500",
+            _ => return write!(f, "reserved BW ({:#04x})", *self as u8),
+        };
+        write!(f, "{khz} kHz")
+    }
+}
+/// LoRa symbol duration 2^SF / BW in µs, exact for every bandwidth; 0 for
+/// the reserved bandwidths. Multiply by the symbol count of
+/// [`SetLoraSymbNumTimeout`] to get the length of an RX window.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{symbol_time_us, Sf, Bw};
+///
+/// assert_eq!(symbol_time_us(Sf::Sf7, Bw::Bw125), 1_024);
+/// assert_eq!(symbol_time_us(Sf::Sf12, Bw::Bw125), 32_768);
+/// assert_eq!(symbol_time_us(Sf::Sf9, Bw::Bw41_67), 12_288);
+/// // 5-symbol RX window at SF10/250 kHz
+/// assert_eq!(5 * symbol_time_us(Sf::Sf10, Bw::Bw250), 20_480);
+/// ```
+#[inline(always)]
+pub const fn symbol_time_us(sf: Sf, bw: Bw) -> u32 {
+    (1 << sf as u32) * chip_time_us(bw)
+}
+/// LoRa chip duration 1 / BW in µs; 0 for the reserved bandwidths.
+#[inline(always)]
+pub(crate) const fn chip_time_us(bw: Bw) -> u32 {
+    match bw {
+        Bw::Bw7_8 => 128,
+        Bw::Bw10_42 => 96,
+        Bw::Bw15_63 => 64,
+        Bw::Bw20_83 => 48,
+        Bw::Bw31_25 => 32,
+        Bw::Bw41_67 => 24,
+        Bw::Bw62_50 => 16,
+        Bw::Bw125 => 8,
+        Bw::Bw250 => 4,
+        Bw::Bw500 => 2,
+        _ => 0,
+    }
+}
+/// Whether LoRa low data rate optimization is required, i.e. whether a
+/// symbol lasts 16.38 ms or more: SF11 and SF12 at 125 kHz, SF10 and up at
+/// 62.5 kHz, and so on.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{low_data_rate_optimize, Sf, Bw};
+///
+/// assert!(!low_data_rate_optimize(Sf::Sf10, Bw::Bw125));
+/// assert!(low_data_rate_optimize(Sf::Sf11, Bw::Bw125));
+/// assert!(!low_data_rate_optimize(Sf::Sf12, Bw::Bw500));
+/// ```
+#[inline(always)]
+pub const fn low_data_rate_optimize(sf: Sf, bw: Bw) -> bool {
+    symbol_time_us(sf, bw) >= 16_384
+}
+/// LoRa time on air in µs of a packet of `payload_length` bytes, after the
+/// formulas of the datasheet, with low data rate optimization set as
+/// [`low_data_rate_optimize`] requires. Long interleaving coding rates are
+/// counted as their regular counterparts. Saturates at `u32::MAX`, about 71
+/// minutes.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{time_on_air_us, Bw, Cr, HeaderType, Sf};
+///
+/// // 8 + 4.25 + 8 + 4 * 5 symbols of 1.024 ms
+/// let toa = time_on_air_us(Sf::Sf7, Bw::Bw125, Cr::Cr4_5, 8, 10, HeaderType::VariableLength, true);
+/// assert_eq!(toa, 41_216);
+/// let toa = time_on_air_us(Sf::Sf12, Bw::Bw125, Cr::Cr4_5, 8, 10, HeaderType::VariableLength, true);
+/// assert_eq!(toa, 991_232);
+/// ```
+#[inline(always)]
+pub const fn time_on_air_us(
+    sf: Sf,
+    bw: Bw,
+    cr: Cr,
+    preamble_length: u16,
+    payload_length: u8,
+    header_type: HeaderType,
+    crc: bool,
+) -> u32 {
+    let sf = sf as u8;
+    let symbol_us = symbol_time_us(Sf::from_bits_truncate(sf), bw) as u64;
+    // Same as low_data_rate_optimize
+    let ldro = symbol_us >= 16_384;
+    let sf = sf as i64;
+    let cr = match cr.denominator() {
+        0 => 0,
+        denominator => denominator as i64 - 4,
+    };
+    let header_bits = match header_type {
+        HeaderType::VariableLength => 20,
+        HeaderType::FixedLength => 0,
+    };
+    // Preamble and sync word in quarter symbols, then the payload bits not
+    // carried by the first 8 symbols.
+    let (preamble, bits) = match sf {
+        5 | 6 => (4 * preamble_length as i64 + 25, 0),
+        _ => (4 * preamble_length as i64 + 17, 8),
+    };
+    let bits = 8 * payload_length as i64 + 16 * crc as i64 - 4 * sf + bits + header_bits;
+    let bits_per_block = 4 * (sf - 2 * ldro as i64);
+    let blocks = if bits > 0 {
+        (bits + bits_per_block - 1) / bits_per_block
+    } else {
+        0
+    };
+    let quarter_symbols = (preamble + 4 * 8 + 4 * blocks * (cr + 4)) as u64;
+    let toa = quarter_symbols * symbol_us / 4;
+    if toa > u32::MAX as u64 {
+        u32::MAX
+    } else {
+        toa as u32
+    }
+}
+/// Longest LoRa payload whose [`time_on_air_us`] does not exceed
+/// `max_time_on_air_us`, e.g. a 400 ms dwell time limit, capped at 255
+/// bytes. `None` if not even an empty packet fits.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{max_payload_length, Bw, Cr, HeaderType, Sf};
+///
+/// let length = max_payload_length(Sf::Sf10, Bw::Bw125, Cr::Cr4_5, 8, HeaderType::VariableLength, true, 400_000);
+/// assert_eq!(length, Some(24));
+/// let length = max_payload_length(Sf::Sf7, Bw::Bw125, Cr::Cr4_5, 8, HeaderType::VariableLength, true, 400_000);
+/// assert_eq!(length, Some(255));
+/// let length = max_payload_length(Sf::Sf12, Bw::Bw125, Cr::Cr4_5, 8, HeaderType::VariableLength, true, 400_000);
+/// assert_eq!(length, None);
+/// ```
+#[inline(always)]
+pub const fn max_payload_length(
+    sf: Sf,
+    bw: Bw,
+    cr: Cr,
+    preamble_length: u16,
+    header_type: HeaderType,
+    crc: bool,
+    max_time_on_air_us: u32,
+) -> Option<u8> {
+    let (sf, bw, cr, header_type) = (sf as u8, bw as u8, cr as u8, header_type as u8);
+    // Time on air grows with the payload length: bisect for the last fit.
+    let (mut low, mut high) = (-1i16, 256i16);
+    while high - low > 1 {
+        let middle = (low + high) / 2;
+        let time_on_air_us = time_on_air_us(
+            Sf::from_bits_truncate(sf),
+            Bw::from_bits_truncate(bw),
+            Cr::from_bits_truncate(cr),
+            preamble_length,
+            middle as u8,
+            HeaderType::from_bits_truncate(header_type),
+            crc,
+        );
+        if time_on_air_us <= max_time_on_air_us {
+            low = middle;
+        } else {
+            high = middle;
+        }
+    }
+    if low < 0 { None } else { Some(low as u8) }
+}
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cr {
+    Reserved = 0x00,
+    Cr4_5 = 0x01,
+    Cr4_6 = 0x02,
+    Cr4_7 = 0x03,
+    Cr4_8 = 0x04,
+    Cr4_5Li = 0x05,
+    Cr4_6Li = 0x06,
+    Cr4_8Li = 0x07,
+}
+impl Cr {
+    /// Keeps the low three bits of `value`, reserved encodings
+    /// included; `try_from` rejects them.
+    #[inline(always)]
+    pub const fn from_bits_truncate(value: u8) -> Self {
+        unsafe { core::mem::transmute(value & 0x07) }
+    }
+    /// Data bits per codeword: 4; 0 for the reserved encoding.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::Cr;
+    ///
+    /// assert_eq!((Cr::Cr4_7.numerator(), Cr::Cr4_7.denominator()), (4, 7));
+    /// assert_eq!((Cr::Cr4_6Li.numerator(), Cr::Cr4_6Li.denominator()), (4, 6));
+    /// assert_eq!(format!("{} {}", Cr::Cr4_5, Cr::Cr4_8Li), "4/5 4/8 LI");
+    /// ```
+    #[inline(always)]
+    pub const fn numerator(&self) -> u8 {
+        match self {
+            Self::Reserved => 0,
+            _ => 4,
+        }
+    }
+    /// Bits per codeword, 5 to 8; 0 for the reserved encoding.
+    #[inline(always)]
+    pub const fn denominator(&self) -> u8 {
+        match self {
+            Self::Reserved => 0,
+            Self::Cr4_5 | Self::Cr4_5Li => 5,
+            Self::Cr4_6 | Self::Cr4_6Li => 6,
+            Self::Cr4_7 => 7,
+            Self::Cr4_8 | Self::Cr4_8Li => 8,
+        }
+    }
+    /// Whether the coding rate uses long interleaving.
+    #[inline(always)]
+    pub const fn is_long_interleaving(&self) -> bool {
+        matches!(self, Self::Cr4_5Li | Self::Cr4_6Li | Self::Cr4_8Li)
+    }
+}
+impl_try_from_u8!(Cr: Cr4_5, Cr4_6, Cr4_7, Cr4_8, Cr4_5Li, Cr4_6Li, Cr4_8Li);
+impl core::fmt::Display for Cr {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Reserved => f.write_str("reserved CR")?,
+            _ => write!(f, "{}/{}", self.numerator(), self.denominator())?,
+        }
+        if self.is_long_interleaving() {
+            f.write_str(" LI")?;
+        }
+        Ok(())
+    }
+}
+
+/// # SetModulationParamsLora builder
+/// Builds a [`SetModulationParamsLora`] from named settings, starting from
+/// SF7, 125 kHz and CR 4/5. LDRO follows [`low_data_rate_optimize`] unless
+/// set explicitly. [`build`](Self::build) rejects reserved encodings and,
+/// when the header type is given, SF6 with a variable length header: SX127x
+/// radios only demodulate SF6 in implicit header mode.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{Bw, Error, HeaderType, SetModulationParamsLora, Sf};
+///
+/// let sf11 = SetModulationParamsLora::builder().with_sf(Sf::Sf11).build().unwrap();
+/// assert_eq!(sf11.tx_buf, [0x8B, 0x0B, 0x04, 0x01, 1]);
+///
+/// let sf11_no_ldro = SetModulationParamsLora::builder()
+///     .with_sf(Sf::Sf11)
+///     .with_bw(Bw::Bw250)
+///     .with_low_data_rate_optimize(false)
+///     .build()
+///     .unwrap();
+/// assert_eq!(sf11_no_ldro.tx_buf, [0x8B, 0x0B, 0x05, 0x01, 0]);
+///
+/// let sf6 = SetModulationParamsLora::builder().with_sf(Sf::Sf6);
+/// assert_eq!(
+///     sf6.with_header_type(HeaderType::VariableLength).build(),
+///     Err(Error::ImplicitHeaderRequired)
+/// );
+/// assert!(sf6.with_header_type(HeaderType::FixedLength).build().is_ok());
+/// assert_eq!(sf6.with_bw(Bw::Reserved1).build(), Err(Error::Reserved));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ModulationParamsLoraBuilder {
+    sf: Sf,
+    bw: Bw,
+    cr: Cr,
+    low_data_rate_optimize: Option<bool>,
+    header_type: Option<HeaderType>,
+}
+impl ModulationParamsLoraBuilder {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            sf: Sf::Sf7,
+            bw: Bw::Bw125,
+            cr: Cr::Cr4_5,
+            low_data_rate_optimize: None,
+            header_type: None,
+        }
+    }
+    #[inline(always)]
+    pub const fn with_sf(mut self, sf: Sf) -> Self {
+        self.sf = sf;
+        self
+    }
+    #[inline(always)]
+    pub const fn with_bw(mut self, bw: Bw) -> Self {
+        self.bw = bw;
+        self
+    }
     #[inline(always)]
-    pub const fn from(value: u8) -> Self {
-        unsafe { core::mem::transmute(value & 0x0F) }
+    pub const fn with_cr(mut self, cr: Cr) -> Self {
+        self.cr = cr;
+        self
+    }
+    /// Overrides the LDRO setting derived from SF and bandwidth.
+    #[inline(always)]
+    pub const fn with_low_data_rate_optimize(mut self, low_data_rate_optimize: bool) -> Self {
+        self.low_data_rate_optimize = Some(low_data_rate_optimize);
+        self
+    }
+    /// Header type of the packet params used with these settings, only
+    /// used for validation.
+    #[inline(always)]
+    pub const fn with_header_type(mut self, header_type: HeaderType) -> Self {
+        self.header_type = Some(header_type);
+        self
+    }
+    #[inline(always)]
+    pub const fn build(self) -> Result<SetModulationParamsLora, Error> {
+        let (sf, bw) = (self.sf as u8, self.bw as u8);
+        if sf < Sf::Sf5 as u8 || sf > Sf::Sf12 as u8 {
+            return Err(Error::Reserved);
+        }
+        if bw == Bw::Reserved6 as u8
+            || bw > Bw::Bw41_67 as u8
+            || self.cr as u8 == Cr::Reserved as u8
+        {
+            return Err(Error::Reserved);
+        }
+        if let (Sf::Sf6, Some(HeaderType::VariableLength)) = (self.sf, self.header_type) {
+            return Err(Error::ImplicitHeaderRequired);
+        }
+        let low_data_rate_optimize = match self.low_data_rate_optimize {
+            Some(low_data_rate_optimize) => low_data_rate_optimize,
+            None => low_data_rate_optimize(self.sf, self.bw),
+        };
+        Ok(SetModulationParamsLora::new(
+            self.sf,
+            self.bw,
+            self.cr,
+            low_data_rate_optimize,
+        ))
     }
 }
-#[repr(u8)]
-pub enum Bw {
-    Bw7_8 = 0x00,
-    Bw10_42 = 0x08,
-    Bw15_63 = 0x01,
-    Bw20_83 = 0x09,
-    Bw31_25 = 0x02,
-    Bw41_67 = 0x0A,
-    Bw62_50 = 0x03,
-    Bw125 = 0x04,
-    Bw250 = 0x05,
-    Bw500 = 0x06,
-    Reserved1 = 0x0B,
-    Reserved2 = 0x0C,
-    Reserved3 = 0x0D,
-    Reserved4 = 0x0E,
-    Reserved5 = 0x0F,
+impl Default for ModulationParamsLoraBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
-impl Bw {
-    #[inline(always)]
-    pub const fn from(value: u8) -> Self {
-        unsafe { core::mem::transmute(value & 0x0F) }
+
+/// Raw GFSK bitrate parameter `32 * 32 MHz / bitrate`, rounded to the
+/// nearest value. `None` outside 600 bps to 300 kbps.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{gfsk_bitrate, gfsk_fdev, GfskBw};
+///
+/// assert_eq!(gfsk_bitrate(50_000), Some(0x005000));
+/// assert_eq!(gfsk_bitrate(100), None);
+/// assert_eq!(gfsk_fdev(25_000), Some(0x006666));
+/// assert_eq!(GfskBw::from_hz(100_000), GfskBw::Bw117_3);
+/// assert_eq!(GfskBw::from_hz(100_000) as u8, 0x0B);
+/// assert_eq!(GfskBw::from_hz(93_800), GfskBw::Bw93_8);
+/// assert_eq!(GfskBw::from_hz(500_000), GfskBw::Bw467_0);
+/// assert_eq!(GfskBw::Bw117_3.hz(), 117_300);
+/// ```
+#[inline(always)]
+pub const fn gfsk_bitrate(bitrate_bps: u32) -> Option<u32> {
+    if bitrate_bps < 600 || bitrate_bps > 300_000 {
+        return None;
+    }
+    Some((32 * XTAL_FREQ_HZ + bitrate_bps / 2) / bitrate_bps)
+}
+/// Raw GFSK frequency deviation parameter `fdev * 2^25 / 32 MHz`, rounded to
+/// the nearest step. `None` above 200 kHz.
+#[inline(always)]
+pub const fn gfsk_fdev(fdev_hz: u32) -> Option<u32> {
+    if fdev_hz > 200_000 {
+        return None;
     }
+    Some(rf_freq_from_hz(fdev_hz))
 }
+/// GFSK RX bandwidth, double sideband.
 #[repr(u8)]
-pub enum Cr {
-    Reserved = 0x00,
-    Cr4_5 = 0x01,
-    Cr4_6 = 0x02,
-    Cr4_7 = 0x03,
-    Cr4_8 = 0x04,
-    Cr4_5Li = 0x05,
-    Cr4_6Li = 0x06,
-    Cr4_8Li = 0x07,
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GfskBw {
+    Bw4_8 = 0x1F,
+    Bw5_8 = 0x17,
+    Bw7_3 = 0x0F,
+    Bw9_7 = 0x1E,
+    Bw11_7 = 0x16,
+    Bw14_6 = 0x0E,
+    Bw19_5 = 0x1D,
+    Bw23_4 = 0x15,
+    Bw29_3 = 0x0D,
+    Bw39_0 = 0x1C,
+    Bw46_9 = 0x14,
+    Bw58_6 = 0x0C,
+    Bw78_2 = 0x1B,
+    Bw93_8 = 0x13,
+    Bw117_3 = 0x0B,
+    Bw156_2 = 0x1A,
+    Bw187_2 = 0x12,
+    Bw234_3 = 0x0A,
+    Bw312_0 = 0x19,
+    Bw373_6 = 0x11,
+    Bw467_0 = 0x09,
 }
-impl Cr {
+impl GfskBw {
+    /// Every bandwidth, narrowest first.
+    pub const ALL: [Self; 21] = [
+        Self::Bw4_8,
+        Self::Bw5_8,
+        Self::Bw7_3,
+        Self::Bw9_7,
+        Self::Bw11_7,
+        Self::Bw14_6,
+        Self::Bw19_5,
+        Self::Bw23_4,
+        Self::Bw29_3,
+        Self::Bw39_0,
+        Self::Bw46_9,
+        Self::Bw58_6,
+        Self::Bw78_2,
+        Self::Bw93_8,
+        Self::Bw117_3,
+        Self::Bw156_2,
+        Self::Bw187_2,
+        Self::Bw234_3,
+        Self::Bw312_0,
+        Self::Bw373_6,
+        Self::Bw467_0,
+    ];
+
     #[inline(always)]
-    pub const fn from(value: u8) -> Self {
-        unsafe { core::mem::transmute(value & 0x07) }
+    pub const fn hz(self) -> u32 {
+        match self {
+            Self::Bw4_8 => 4_800,
+            Self::Bw5_8 => 5_800,
+            Self::Bw7_3 => 7_300,
+            Self::Bw9_7 => 9_700,
+            Self::Bw11_7 => 11_700,
+            Self::Bw14_6 => 14_600,
+            Self::Bw19_5 => 19_500,
+            Self::Bw23_4 => 23_400,
+            Self::Bw29_3 => 29_300,
+            Self::Bw39_0 => 39_000,
+            Self::Bw46_9 => 46_900,
+            Self::Bw58_6 => 58_600,
+            Self::Bw78_2 => 78_200,
+            Self::Bw93_8 => 93_800,
+            Self::Bw117_3 => 117_300,
+            Self::Bw156_2 => 156_200,
+            Self::Bw187_2 => 187_200,
+            Self::Bw234_3 => 234_300,
+            Self::Bw312_0 => 312_000,
+            Self::Bw373_6 => 373_600,
+            Self::Bw467_0 => 467_000,
+        }
+    }
+    /// The narrowest supported bandwidth of at least `hz`, so the filter
+    /// does not cut the signal; [`Bw467_0`](Self::Bw467_0) above it.
+    #[inline(always)]
+    pub const fn from_hz(hz: u32) -> Self {
+        let mut i = 0;
+        while i < Self::ALL.len() {
+            if Self::ALL[i].hz() >= hz {
+                return Self::ALL[i];
+            }
+            i += 1;
+        }
+        Self::Bw467_0
     }
 }
 
@@ -973,18 +4249,25 @@ impl Cr {
 ///
 /// ## Example
 /// ```
-/// use sx126x_spi_buffers::commands::{SetPacketParams, HeaderType, InvertIq};
+/// use sx126x_spi_buffers::commands::{CrcType, SetPacketParams, HeaderType, InvertIq};
 /// const SET_PACKET_PARAMS: SetPacketParams = SetPacketParams::new(
 ///    8,
 ///    HeaderType::VariableLength,
 ///    14,
-///    false,
+///    CrcType::Off,
 ///    InvertIq::Standard,
 /// );
 /// assert_eq!(SET_PACKET_PARAMS.tx_buf, [0x8C, 0, 8, 0, 14, 0, 0]);
 /// assert_eq!(SET_PACKET_PARAMS.rx_buf, [0; 7]);
-/// assert_eq!(SET_PACKET_PARAMS.descriptor().transfer_length, 7);
+/// assert_eq!(SET_PACKET_PARAMS.descriptor().transfer_length(), 7);
+///
+/// let mut set_packet_params = SET_PACKET_PARAMS;
+/// set_packet_params.set_payload_length(32);
+/// assert_eq!(set_packet_params.payload_length(), 32);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetPacketParams {
     pub tx_buf: [u8; 7],
     pub rx_buf: [u8; 7],
@@ -997,7 +4280,7 @@ impl SetPacketParams {
         preamble_length: u16,
         header_type: HeaderType,
         payload_length: u8,
-        crc_type: bool,
+        crc_type: CrcType,
         invert_iq: InvertIq,
     ) -> Self {
         Self {
@@ -1013,39 +4296,236 @@ impl SetPacketParams {
             rx_buf: [0; 7],
         }
     }
+    /// Same as [`new`](Self::new), rejecting an empty preamble and a payload
+    /// length, e.g. that of a slice, above 255.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::{CrcType, Error, HeaderType, InvertIq, SetPacketParams};
+    ///
+    /// let payload = [0; 32];
+    /// let set_packet_params = SetPacketParams::try_new(
+    ///     8,
+    ///     HeaderType::VariableLength,
+    ///     payload.len(),
+    ///     CrcType::On,
+    ///     InvertIq::Standard,
+    /// );
+    /// assert_eq!(set_packet_params.unwrap().tx_buf, [0x8C, 0, 8, 0, 32, 1, 0]);
+    /// assert_eq!(
+    ///     SetPacketParams::try_new(0, HeaderType::VariableLength, 32, CrcType::On, InvertIq::Standard),
+    ///     Err(Error::OutOfRange)
+    /// );
+    /// assert_eq!(
+    ///     SetPacketParams::try_new(8, HeaderType::VariableLength, 256, CrcType::On, InvertIq::Standard),
+    ///     Err(Error::PayloadTooLong)
+    /// );
+    /// ```
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 7,
+    pub const fn try_new(
+        preamble_length: u16,
+        header_type: HeaderType,
+        payload_length: usize,
+        crc_type: CrcType,
+        invert_iq: InvertIq,
+    ) -> Result<Self, Error> {
+        if preamble_length == 0 {
+            return Err(Error::OutOfRange);
+        }
+        if payload_length > u8::MAX as usize {
+            return Err(Error::PayloadTooLong);
         }
+        Ok(Self::new(
+            preamble_length,
+            header_type,
+            payload_length as u8,
+            crc_type,
+            invert_iq,
+        ))
+    }
+    #[inline(always)]
+    pub const fn payload_length(&self) -> u8 {
+        self.tx_buf[4]
+    }
+    /// Updates the payload length, e.g. before each transmission.
+    #[inline(always)]
+    pub const fn set_payload_length(&mut self, payload_length: u8) {
+        self.tx_buf[4] = payload_length;
+    }
+    /// [`new`](Self::new) with the CRC as a `bool`, easily swapped with the
+    /// neighbouring parameters.
+    #[deprecated(note = "use `new` with a `CrcType`")]
+    #[inline(always)]
+    pub const fn new_with_crc_flag(
+        preamble_length: u16,
+        header_type: HeaderType,
+        payload_length: u8,
+        crc: bool,
+        invert_iq: InvertIq,
+    ) -> Self {
+        Self::new(
+            preamble_length,
+            header_type,
+            payload_length,
+            CrcType::from_bool(crc),
+            invert_iq,
+        )
+    }
+    /// Named alternative to [`new`](Self::new), see [`PacketParamsBuilder`].
+    #[inline(always)]
+    pub const fn builder() -> PacketParamsBuilder {
+        PacketParamsBuilder::new()
     }
 }
+impl_command!(SetPacketParams: 7);
+impl_write_only!(SetPacketParams);
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum HeaderType {
     VariableLength = 0x00,
     FixedLength = 0x01,
 }
 impl HeaderType {
+    /// Keeps the low bit of `value`; `try_from` rejects other values.
     #[inline(always)]
-    pub const fn from(value: u8) -> Self {
+    pub const fn from_bits_truncate(value: u8) -> Self {
         unsafe { core::mem::transmute(value & 0x01) }
     }
 }
+impl_try_from_u8!(HeaderType: VariableLength, FixedLength);
+/// Whether the LoRa payload CRC is sent, and checked in RX.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::CrcType;
+///
+/// assert_eq!(CrcType::from(true), CrcType::On);
+/// assert_eq!(CrcType::try_from(0x01), Ok(CrcType::On));
+/// assert!(CrcType::try_from(0x02).is_err());
+/// ```
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrcType {
+    Off = 0x00,
+    On = 0x01,
+}
+impl CrcType {
+    #[inline(always)]
+    pub const fn from_bool(crc: bool) -> Self {
+        match crc {
+            true => Self::On,
+            false => Self::Off,
+        }
+    }
+}
+impl_try_from_u8!(CrcType: Off, On);
+impl From<bool> for CrcType {
+    #[inline(always)]
+    fn from(crc: bool) -> Self {
+        Self::from_bool(crc)
+    }
+}
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum InvertIq {
     Standard = 0x00,
     Inverted = 0x01,
 }
 impl InvertIq {
+    /// Keeps the low bit of `value`; `try_from` rejects other values.
     #[inline(always)]
-    pub const fn from(value: u8) -> Self {
+    pub const fn from_bits_truncate(value: u8) -> Self {
         unsafe { core::mem::transmute(value & 0x01) }
     }
 }
+impl_try_from_u8!(InvertIq: Standard, Inverted);
+
+/// # SetPacketParams builder
+/// Builds a [`SetPacketParams`] from named settings. Starts from 8 preamble
+/// symbols, a variable length header, a payload length of 255, CRC on and
+/// standard IQ.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{CrcType, HeaderType, InvertIq, SetPacketParams};
+///
+/// const SET_PACKET_PARAMS: SetPacketParams = SetPacketParams::builder()
+///     .with_preamble_length(12)
+///     .with_payload_length(14)
+///     .build();
+/// assert_eq!(SET_PACKET_PARAMS.tx_buf, [0x8C, 0, 12, 0, 14, 1, 0]);
+///
+/// let downlink = SetPacketParams::builder()
+///     .with_header_type(HeaderType::FixedLength)
+///     .with_crc(CrcType::Off)
+///     .with_invert_iq(InvertIq::Inverted)
+///     .build();
+/// assert_eq!(downlink.tx_buf, [0x8C, 0, 8, 1, 255, 0, 1]);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PacketParamsBuilder {
+    preamble_length: u16,
+    header_type: HeaderType,
+    payload_length: u8,
+    crc_type: CrcType,
+    invert_iq: InvertIq,
+}
+impl PacketParamsBuilder {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            preamble_length: 8,
+            header_type: HeaderType::VariableLength,
+            payload_length: 255,
+            crc_type: CrcType::On,
+            invert_iq: InvertIq::Standard,
+        }
+    }
+    /// Preamble length in symbols.
+    #[inline(always)]
+    pub const fn with_preamble_length(mut self, preamble_length: u16) -> Self {
+        self.preamble_length = preamble_length;
+        self
+    }
+    #[inline(always)]
+    pub const fn with_header_type(mut self, header_type: HeaderType) -> Self {
+        self.header_type = header_type;
+        self
+    }
+    /// Length of the transmitted payload, or the longest payload accepted
+    /// in RX.
+    #[inline(always)]
+    pub const fn with_payload_length(mut self, payload_length: u8) -> Self {
+        self.payload_length = payload_length;
+        self
+    }
+    /// Whether the payload CRC is sent, and checked in RX.
+    #[inline(always)]
+    pub const fn with_crc(mut self, crc_type: CrcType) -> Self {
+        self.crc_type = crc_type;
+        self
+    }
+    #[inline(always)]
+    pub const fn with_invert_iq(mut self, invert_iq: InvertIq) -> Self {
+        self.invert_iq = invert_iq;
+        self
+    }
+    #[inline(always)]
+    pub const fn build(self) -> SetPacketParams {
+        SetPacketParams::new(
+            self.preamble_length,
+            self.header_type,
+            self.payload_length,
+            self.crc_type,
+            self.invert_iq,
+        )
+    }
+}
+impl Default for PacketParamsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 /// # SetBufferBaseAddress command
 /// Sets the base addresses for the TX and RX buffers.
@@ -1056,8 +4536,11 @@ impl InvertIq {
 /// const SET_BUFFER_BASE_ADDRESS: SetBufferBaseAddress = SetBufferBaseAddress::new(0x00, 0x80);
 /// assert_eq!(SET_BUFFER_BASE_ADDRESS.tx_buf, [0x8F, 0, 128]);
 /// assert_eq!(SET_BUFFER_BASE_ADDRESS.rx_buf, [0; 3]);
-/// assert_eq!(SET_BUFFER_BASE_ADDRESS.descriptor().transfer_length, 3);
+/// assert_eq!(SET_BUFFER_BASE_ADDRESS.descriptor().transfer_length(), 3);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetBufferBaseAddress {
     pub tx_buf: [u8; 3],
     pub rx_buf: [u8; 3],
@@ -1072,15 +4555,9 @@ impl SetBufferBaseAddress {
             rx_buf: [0; 3],
         }
     }
-    #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 3,
-        }
-    }
 }
+impl_command!(SetBufferBaseAddress: 3);
+impl_write_only!(SetBufferBaseAddress);
 
 /// # SetLoraSymbNumTimeout command
 /// Sets the number of symbols used by the modem to validate
@@ -1092,8 +4569,11 @@ impl SetBufferBaseAddress {
 /// const SET_LORA_SYMB_NUM_TIMEOUT: SetLoraSymbNumTimeout = SetLoraSymbNumTimeout::new(5);
 /// assert_eq!(SET_LORA_SYMB_NUM_TIMEOUT.tx_buf, [0xA0, 5]);
 /// assert_eq!(SET_LORA_SYMB_NUM_TIMEOUT.rx_buf, [0; 2]);
-/// assert_eq!(SET_LORA_SYMB_NUM_TIMEOUT.descriptor().transfer_length, 2);
+/// assert_eq!(SET_LORA_SYMB_NUM_TIMEOUT.descriptor().transfer_length(), 2);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct SetLoraSymbNumTimeout {
     pub tx_buf: [u8; 2],
     pub rx_buf: [u8; 2],
@@ -1108,15 +4588,26 @@ impl SetLoraSymbNumTimeout {
             rx_buf: [0; 2],
         }
     }
+    /// Same as [`new`](Self::new), rejecting symbol counts above 255
+    /// instead of truncating them.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::{Error, SetLoraSymbNumTimeout};
+    ///
+    /// assert_eq!(SetLoraSymbNumTimeout::try_new(255).unwrap().tx_buf, [0xA0, 255]);
+    /// assert_eq!(SetLoraSymbNumTimeout::try_new(256), Err(Error::OutOfRange));
+    /// ```
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 2,
+    pub const fn try_new(symb_num: u16) -> Result<Self, Error> {
+        if symb_num > u8::MAX as u16 {
+            return Err(Error::OutOfRange);
         }
+        Ok(Self::new(symb_num as u8))
     }
 }
+impl_command!(SetLoraSymbNumTimeout: 2);
+impl_write_only!(SetLoraSymbNumTimeout);
 
 /// # GetStatus command
 /// Retrieves the current status of the device.
@@ -1127,11 +4618,16 @@ impl SetLoraSymbNumTimeout {
 /// let mut get_status: GetStatus = GetStatus::new();
 /// assert_eq!(get_status.tx_buf, [0xC0, 0]);
 /// assert_eq!(get_status.rx_buf, [0; 2]);
-/// assert_eq!(get_status.descriptor().transfer_length, 2);
+/// assert_eq!(get_status.descriptor().transfer_length(), 2);
 /// get_status.rx_buf[1] = 0x64;
 /// assert_eq!(get_status.chip_mode(), StatusChipMode::Tx);
 /// assert_eq!(get_status.command_status(), StatusCommandStatus::DataIsAvailableToHost);
+/// get_status.rx_buf[1] = 0x28;
+/// assert_eq!(get_status.command_status(), StatusCommandStatus::CommandProcessingError);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GetStatus {
     pub tx_buf: [u8; 2],
     pub rx_buf: [u8; 2],
@@ -1147,14 +4643,6 @@ impl GetStatus {
         }
     }
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 2,
-        }
-    }
-    #[inline(always)]
     pub const fn chip_mode(&self) -> StatusChipMode {
         StatusChipMode::extract(self.rx_buf[1])
     }
@@ -1163,8 +4651,31 @@ impl GetStatus {
         StatusCommandStatus::extract(self.rx_buf[1])
     }
 }
+impl Response for GetStatus {
+    type Output = Status;
+
+    #[inline(always)]
+    fn parse(&self) -> Status {
+        Status {
+            chip_mode: self.chip_mode(),
+            command_status: self.command_status(),
+        }
+    }
+}
+impl_command!(GetStatus: 2);
+impl Default for GetStatus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+/// Response of [`GetStatus`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Status {
+    pub chip_mode: StatusChipMode,
+    pub command_status: StatusCommandStatus,
+}
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum StatusChipMode {
     Unused = 0x0,
     Reserved1 = 0x1,
@@ -1181,8 +4692,9 @@ impl StatusChipMode {
         unsafe { core::mem::transmute((value >> 4) & 0x07) }
     }
 }
+impl_try_from_u8!(StatusChipMode: StbyRc, StbyXosc, Fs, Rx, Tx);
 #[repr(u8)]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum StatusCommandStatus {
     Reserved1 = 0x0,
     Reserved2 = 0x1,
@@ -1196,9 +4708,10 @@ pub enum StatusCommandStatus {
 impl StatusCommandStatus {
     #[inline(always)]
     pub const fn extract(value: u8) -> Self {
-        unsafe { core::mem::transmute((value >> 1) & 0x03) }
+        unsafe { core::mem::transmute((value >> 1) & 0x07) }
     }
 }
+impl_try_from_u8!(StatusCommandStatus: DataIsAvailableToHost, CommandTimeout, CommandProcessingError, FailureToExecuteCommand, CommandTxDone);
 
 /// # GetRxBufferStatus command
 /// Returns the length of the last received packet (PayloadLengthRx) and
@@ -1210,12 +4723,15 @@ impl StatusCommandStatus {
 /// let mut get_rx_buffer_status: GetRxBufferStatus = GetRxBufferStatus::new();
 /// assert_eq!(get_rx_buffer_status.tx_buf, [0x13, 0, 0, 0]);
 /// assert_eq!(get_rx_buffer_status.rx_buf, [0; 4]);
-/// assert_eq!(get_rx_buffer_status.descriptor().transfer_length, 4);
+/// assert_eq!(get_rx_buffer_status.descriptor().transfer_length(), 4);
 /// get_rx_buffer_status.rx_buf[2] = 16;
 /// get_rx_buffer_status.rx_buf[3] = 8;
 /// assert_eq!(get_rx_buffer_status.payload_length_rx(), 16);
 /// assert_eq!(get_rx_buffer_status.rx_start_buffer_pointer(), 8);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GetRxBufferStatus {
     pub tx_buf: [u8; 4],
     pub rx_buf: [u8; 4],
@@ -1231,14 +4747,6 @@ impl GetRxBufferStatus {
         }
     }
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 4,
-        }
-    }
-    #[inline(always)]
     pub const fn payload_length_rx(&self) -> u8 {
         self.rx_buf[2]
     }
@@ -1247,6 +4755,29 @@ impl GetRxBufferStatus {
         self.rx_buf[3]
     }
 }
+impl Response for GetRxBufferStatus {
+    type Output = RxBufferStatus;
+
+    #[inline(always)]
+    fn parse(&self) -> RxBufferStatus {
+        RxBufferStatus {
+            payload_length_rx: self.payload_length_rx(),
+            rx_start_buffer_pointer: self.rx_start_buffer_pointer(),
+        }
+    }
+}
+impl_command!(GetRxBufferStatus: 4);
+impl Default for GetRxBufferStatus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+/// Response of [`GetRxBufferStatus`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RxBufferStatus {
+    pub payload_length_rx: u8,
+    pub rx_start_buffer_pointer: u8,
+}
 
 /// # GetPacketStatusLora command
 /// Gets the signal quality of the last received LoRa packets.
@@ -1258,14 +4789,20 @@ impl GetRxBufferStatus {
 /// let mut get_packet_status_lora: GetPacketStatusLora = GetPacketStatusLora::new();
 /// assert_eq!(get_packet_status_lora.tx_buf, [0x14, 0, 0, 0, 0]);
 /// assert_eq!(get_packet_status_lora.rx_buf, [0; 5]);
-/// assert_eq!(get_packet_status_lora.descriptor().transfer_length, 5);
+/// assert_eq!(get_packet_status_lora.descriptor().transfer_length(), 5);
 /// get_packet_status_lora.rx_buf[2] = 184;
 /// get_packet_status_lora.rx_buf[3] = 0b1111_1100;
 /// get_packet_status_lora.rx_buf[4] = 162;
 /// assert_eq!(get_packet_status_lora.rssi_pkt(), -92);
 /// assert_eq!(get_packet_status_lora.snr_pkt(), -1);
 /// assert_eq!(get_packet_status_lora.signal_rssi_pkt(), -81);
+/// assert_eq!(get_packet_status_lora.rssi_pkt_deci_db(), -920);
+/// assert_eq!(get_packet_status_lora.snr_pkt_deci_db(), -10);
+/// assert_eq!(get_packet_status_lora.signal_rssi_pkt_deci_db(), -810);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GetPacketStatusLora {
     pub tx_buf: [u8; 5],
     pub rx_buf: [u8; 5],
@@ -1282,14 +4819,6 @@ impl GetPacketStatusLora {
         }
     }
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 5,
-        }
-    }
-    #[inline(always)]
     pub const fn rssi_pkt(&self) -> i8 {
         -((self.rx_buf[2] / 2) as i8)
     }
@@ -1301,6 +4830,104 @@ impl GetPacketStatusLora {
     pub const fn signal_rssi_pkt(&self) -> i8 {
         -((self.rx_buf[4] / 2) as i8)
     }
+    /// [`rssi_pkt`](Self::rssi_pkt) in tenths of dBm, without truncation.
+    #[inline(always)]
+    pub const fn rssi_pkt_deci_db(&self) -> i16 {
+        -(self.rx_buf[2] as i16 * 5)
+    }
+    /// [`snr_pkt`](Self::snr_pkt) in tenths of dB; the chip reports quarter
+    /// dB steps, truncated toward zero.
+    #[inline(always)]
+    pub const fn snr_pkt_deci_db(&self) -> i16 {
+        self.rx_buf[3] as i8 as i16 * 5 / 2
+    }
+    /// [`signal_rssi_pkt`](Self::signal_rssi_pkt) in tenths of dBm, without
+    /// truncation.
+    #[inline(always)]
+    pub const fn signal_rssi_pkt_deci_db(&self) -> i16 {
+        -(self.rx_buf[4] as i16 * 5)
+    }
+}
+impl Response for GetPacketStatusLora {
+    type Output = PacketStatusLora;
+
+    #[inline(always)]
+    fn parse(&self) -> PacketStatusLora {
+        PacketStatusLora {
+            rssi_pkt_deci_db: self.rssi_pkt_deci_db(),
+            snr_pkt_deci_db: self.snr_pkt_deci_db(),
+            signal_rssi_pkt_deci_db: self.signal_rssi_pkt_deci_db(),
+        }
+    }
+}
+impl_command!(GetPacketStatusLora: 5);
+impl Default for GetPacketStatusLora {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+/// Response of [`GetPacketStatusLora`], in tenths of dB(m).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PacketStatusLora {
+    pub rssi_pkt_deci_db: i16,
+    pub snr_pkt_deci_db: i16,
+    pub signal_rssi_pkt_deci_db: i16,
+}
+
+/// # GetRssiInst command
+/// Returns the instantaneous RSSI during reception.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::GetRssiInst;
+///
+/// let mut get_rssi_inst = GetRssiInst::new();
+/// assert_eq!(get_rssi_inst.tx_buf, [0x15, 0, 0]);
+/// assert_eq!(get_rssi_inst.descriptor().transfer_length(), 3);
+/// get_rssi_inst.rx_buf[2] = 171;
+/// assert_eq!(get_rssi_inst.rssi_inst(), -85);
+/// assert_eq!(get_rssi_inst.rssi_inst_deci_db(), -855);
+/// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GetRssiInst {
+    pub tx_buf: [u8; 3],
+    pub rx_buf: [u8; 3],
+}
+impl GetRssiInst {
+    const OPCODE: u8 = 0x15;
+
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            tx_buf: [Self::OPCODE, 0, 0],
+            rx_buf: [0; 3],
+        }
+    }
+    #[inline(always)]
+    pub const fn rssi_inst(&self) -> i8 {
+        -((self.rx_buf[2] / 2) as i8)
+    }
+    /// [`rssi_inst`](Self::rssi_inst) in tenths of dBm, without truncation.
+    #[inline(always)]
+    pub const fn rssi_inst_deci_db(&self) -> i16 {
+        -(self.rx_buf[2] as i16 * 5)
+    }
+}
+impl Response for GetRssiInst {
+    type Output = i16;
+
+    #[inline(always)]
+    fn parse(&self) -> i16 {
+        self.rssi_inst_deci_db()
+    }
+}
+impl_command!(GetRssiInst: 3);
+impl Default for GetRssiInst {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// # GetStatsLora command
@@ -1313,7 +4940,7 @@ impl GetPacketStatusLora {
 /// let mut get_stats_lora: GetStatsLora = GetStatsLora::new();
 /// assert_eq!(get_stats_lora.tx_buf, [0x10, 0, 0, 0, 0, 0, 0, 0]);
 /// assert_eq!(get_stats_lora.rx_buf, [0; 8]);
-/// assert_eq!(get_stats_lora.descriptor().transfer_length, 8);
+/// assert_eq!(get_stats_lora.descriptor().transfer_length(), 8);
 /// get_stats_lora.rx_buf[2] = 0x51;
 /// get_stats_lora.rx_buf[3] = 0x18;
 /// get_stats_lora.rx_buf[4] = 0x03;
@@ -1324,6 +4951,9 @@ impl GetPacketStatusLora {
 /// assert_eq!(get_stats_lora.nb_pkt_crc_error(), 0x0315);
 /// assert_eq!(get_stats_lora.nb_pkt_header_err(), 0x5581);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GetStatsLora {
     pub tx_buf: [u8; 8],
     pub rx_buf: [u8; 8],
@@ -1339,14 +4969,6 @@ impl GetStatsLora {
         }
     }
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 8,
-        }
-    }
-    #[inline(always)]
     pub const fn nb_pkt_received(&self) -> u16 {
         (self.rx_buf[2] as u16) << 8 | (self.rx_buf[3]) as u16
     }
@@ -1359,6 +4981,31 @@ impl GetStatsLora {
         (self.rx_buf[6] as u16) << 8 | (self.rx_buf[7]) as u16
     }
 }
+impl Response for GetStatsLora {
+    type Output = LoraStats;
+
+    #[inline(always)]
+    fn parse(&self) -> LoraStats {
+        LoraStats {
+            nb_pkt_received: self.nb_pkt_received(),
+            nb_pkt_crc_error: self.nb_pkt_crc_error(),
+            nb_pkt_header_err: self.nb_pkt_header_err(),
+        }
+    }
+}
+impl_command!(GetStatsLora: 8);
+impl Default for GetStatsLora {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+/// Response of [`GetStatsLora`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LoraStats {
+    pub nb_pkt_received: u16,
+    pub nb_pkt_crc_error: u16,
+    pub nb_pkt_header_err: u16,
+}
 
 /// # ResetStats command
 /// Resets the number of packets received counters.
@@ -1370,8 +5017,11 @@ impl GetStatsLora {
 /// const RESET_STATS: ResetStats = ResetStats::new();
 /// assert_eq!(RESET_STATS.tx_buf, [0x00, 0, 0, 0, 0, 0, 0]);
 /// assert_eq!(RESET_STATS.rx_buf, [0; 7]);
-/// assert_eq!(RESET_STATS.descriptor().transfer_length, 7);
+/// assert_eq!(RESET_STATS.descriptor().transfer_length(), 7);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ResetStats {
     pub tx_buf: [u8; 7],
     pub rx_buf: [u8; 7],
@@ -1386,13 +5036,12 @@ impl ResetStats {
             rx_buf: [0; 7],
         }
     }
-    #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 7,
-        }
+}
+impl_command!(ResetStats: 7);
+impl_write_only!(ResetStats);
+impl Default for ResetStats {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -1406,11 +5055,14 @@ impl ResetStats {
 /// let mut get_device_errors: GetDeviceErrors = GetDeviceErrors::new();
 /// assert_eq!(get_device_errors.tx_buf, [0x17, 0, 0, 0]);
 /// assert_eq!(get_device_errors.rx_buf, [0; 4]);
-/// assert_eq!(get_device_errors.descriptor().transfer_length, 4);
+/// assert_eq!(get_device_errors.descriptor().transfer_length(), 4);
 /// get_device_errors.rx_buf[2] = 0x01;
 /// get_device_errors.rx_buf[3] = 0x58;
 /// assert_eq!(get_device_errors.op_error(), OpError::new().with_pa_ramp_err(true)
 ///    .with_pll_lock_err(true).with_img_calib_err(true).with_adc_calib_err(true).with_xosc_start_err(false));
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct GetDeviceErrors {
     pub tx_buf: [u8; 4],
     pub rx_buf: [u8; 4],
@@ -1426,16 +5078,22 @@ impl GetDeviceErrors {
         }
     }
     #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 4,
-        }
+    pub const fn op_error(&self) -> OpError {
+        OpError::from_be_bytes([self.rx_buf[2], self.rx_buf[3]])
     }
+}
+impl Response for GetDeviceErrors {
+    type Output = OpError;
+
     #[inline(always)]
-    pub const fn op_error(&self) -> OpError {
-        OpError::from_bits((self.rx_buf[2] as u16) << 8 | self.rx_buf[3] as u16)
+    fn parse(&self) -> OpError {
+        self.op_error()
+    }
+}
+impl_command!(GetDeviceErrors: 4);
+impl Default for GetDeviceErrors {
+    fn default() -> Self {
+        Self::new()
     }
 }
 #[bitfield(u16)]
@@ -1462,6 +5120,33 @@ pub struct OpError {
     #[bits(7)]
     __: u8,
 }
+impl OpError {
+    /// Every defined error bit set.
+    ///
+    /// ## Example
+    /// ```
+    /// use sx126x_spi_buffers::commands::OpError;
+    ///
+    /// const CALIB_ERRS: OpError = OpError::new().with_rc64k_calib_err(true).with_rc13m_calib_err(true)
+    ///     .union(OpError::new().with_pll_calib_err(true).with_adc_calib_err(true).with_img_calib_err(true));
+    /// assert_eq!(OpError::ALL.into_bits(), 0x017F);
+    /// assert_eq!(CALIB_ERRS.into_bits(), 0x001F);
+    /// assert_eq!((!CALIB_ERRS).into_bits(), 0x0160);
+    /// assert_eq!(OpError::ALL & CALIB_ERRS, CALIB_ERRS);
+    /// assert_eq!(OpError::ALL.to_be_bytes(), [0x01, 0x7F]);
+    /// assert_eq!(OpError::from_be_bytes([0x01, 0x00]), OpError::new().with_pa_ramp_err(true));
+    /// ```
+    pub const ALL: Self = Self::new()
+        .with_rc64k_calib_err(true)
+        .with_rc13m_calib_err(true)
+        .with_pll_calib_err(true)
+        .with_adc_calib_err(true)
+        .with_img_calib_err(true)
+        .with_xosc_start_err(true)
+        .with_pll_lock_err(true)
+        .with_pa_ramp_err(true);
+}
+impl_flag_ops!(OpError);
 
 /// # ClearDeviceErrors command
 /// Clears the error flags.
@@ -1473,8 +5158,11 @@ pub struct OpError {
 /// const CLEAR_DEVICE_ERRORS: ClearDeviceErrors = ClearDeviceErrors::new();
 /// assert_eq!(CLEAR_DEVICE_ERRORS.tx_buf, [0x07, 0, 0]);
 /// assert_eq!(CLEAR_DEVICE_ERRORS.rx_buf, [0; 3]);
-/// assert_eq!(CLEAR_DEVICE_ERRORS.descriptor().transfer_length, 3);
+/// assert_eq!(CLEAR_DEVICE_ERRORS.descriptor().transfer_length(), 3);
 /// ```
+#[repr(C)]
+#[cfg_attr(feature = "cache-line-aligned", repr(align(32)))]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct ClearDeviceErrors {
     pub tx_buf: [u8; 3],
     pub rx_buf: [u8; 3],
@@ -1489,13 +5177,12 @@ impl ClearDeviceErrors {
             rx_buf: [0; 3],
         }
     }
-    #[inline(always)]
-    pub const fn descriptor(&mut self) -> SpiDescriptor {
-        SpiDescriptor {
-            tx_buf_ptr: self.tx_buf.as_ptr(),
-            rx_buf_ptr: self.rx_buf.as_mut_ptr(),
-            transfer_length: 3,
-        }
+}
+impl_command!(ClearDeviceErrors: 3);
+impl_write_only!(ClearDeviceErrors);
+impl Default for ClearDeviceErrors {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -1541,8 +5228,13 @@ mod tests {
 
     #[test]
     fn test_set_packet_params() {
-        static SET_PACKET_PARAMS: SetPacketParams =
-            SetPacketParams::new(8, HeaderType::VariableLength, 3, false, InvertIq::Standard);
+        static SET_PACKET_PARAMS: SetPacketParams = SetPacketParams::new(
+            8,
+            HeaderType::VariableLength,
+            3,
+            CrcType::Off,
+            InvertIq::Standard,
+        );
         assert_eq!(SET_PACKET_PARAMS.tx_buf, [0x8C, 0, 8, 0, 3, 0, 0]);
     }
 
@@ -1559,8 +5251,15 @@ mod tests {
 
     #[test]
     fn test_write_sync_word() {
-        static WRITE_SYNC_WORD: WriteRegister = WriteRegister::new(LoraSyncWordMsb(0x14));
-        assert_eq!(WRITE_SYNC_WORD.tx_buf, [0x0D, 0x07, 0x40, 0x14]);
+        let write_sync_word = WriteRegister::new(LoraSyncWordMsb(0x14));
+        assert_eq!(write_sync_word.tx_buf, [0x0D, 0x07, 0x40, 0x14]);
+    }
+
+    #[test]
+    fn test_write_register_block() {
+        static WRITE_REGISTER_BLOCK: WriteRegisterBlock<5> =
+            WriteRegisterBlock::from_slice(0x06CD, &[0x12, 0x34]);
+        assert_eq!(WRITE_REGISTER_BLOCK.tx_buf, [0x0D, 0x06, 0xCD, 0x12, 0x34]);
     }
 
     #[test]
@@ -1583,10 +5282,150 @@ mod tests {
 
     #[test]
     fn test_write_buffer() {
-        static WRITE_BUFFER: WriteBuffer<5> = WriteBuffer::new(0x00, [0x00, 0x00, 0x00]);
+        static WRITE_BUFFER: WriteBuffer<5> = WriteBuffer::from_slice(0x00, &[0x00, 0x00, 0x00]);
         assert_eq!(WRITE_BUFFER.tx_buf, [0x0E, 0, 0, 0, 0]);
     }
 
+    #[test]
+    fn test_command_write_buffer() {
+        let mut write_buffer: WriteBuffer<6> = WriteBuffer::from_slice(0x00, &[1, 2, 3, 4]);
+        write_buffer.set_data_length(2).unwrap();
+        assert_eq!(<WriteBuffer<6> as Command>::LENGTH, 6);
+        assert_eq!(Command::transfer_length(&write_buffer), 4);
+        assert_eq!(Command::tx_buf(&write_buffer), [0x0E, 0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    #[cfg(feature = "cache-line-aligned")]
+    fn test_cache_line_aligned() {
+        assert_eq!(core::mem::align_of::<SetSleep>(), 32);
+        assert_eq!(core::mem::size_of::<SetSleep>(), 32);
+        assert_eq!(core::mem::size_of::<ReadVersion>(), 64);
+    }
+
+    #[test]
+    #[cfg(feature = "scratch-rx")]
+    fn test_scratch_rx() {
+        let mut set_standby = TxOnly::new([0x80, 0]);
+        let mut set_rf_frequency = SetRfFrequency::from_hz(868_100_000);
+        let tx_only = set_standby.descriptor();
+        let dropped = set_rf_frequency.descriptor().tx_only();
+        assert!(tx_only.is_tx_only() && dropped.is_tx_only());
+        assert!(!tx_only.rx_buf_ptr().is_null());
+        assert_eq!(tx_only.rx_buf_ptr(), dropped.rx_buf_ptr());
+        assert!(!set_rf_frequency.descriptor().is_tx_only());
+
+        let mut too_long = TxOnly::new([0; SCRATCH_RX_LENGTH + 1]);
+        assert!(too_long.descriptor().rx_buf_ptr().is_null());
+    }
+
+    #[test]
+    fn test_default() {
+        let get_irq_status: [GetIrqStatus; 2] = Default::default();
+        assert_eq!(get_irq_status[1].tx_buf, [0x12, 0, 0, 0]);
+        assert_eq!(GetStatsLora::default(), GetStatsLora::new());
+        assert_eq!(ResetStats::default().tx_buf, [0x00, 0, 0, 0, 0, 0, 0]);
+    }
+
+    /// Runtime inputs, e.g. bytes received from the radio, over their whole
+    /// range: none of them may panic.
+    #[test]
+    fn test_no_panic() {
+        let mut read_buffer: ReadBuffer<8> = ReadBuffer::new(0);
+        let mut write_buffer: WriteBuffer<8> = WriteBuffer::try_new(0, &[]).unwrap();
+        for data_length in 0..=u16::MAX {
+            let accepted = data_length <= 5;
+            assert_eq!(read_buffer.set_data_length(data_length).is_ok(), accepted);
+            assert!(read_buffer.data().len() <= 5);
+            assert!(read_buffer.decode::<[u8; 4]>().is_ok() || read_buffer.data().len() != 4);
+            assert_eq!(
+                write_buffer.set_data_length(data_length).is_ok(),
+                data_length <= 6
+            );
+            assert!(write_buffer.data().len() <= 6);
+
+            let irq = Irq::from_bits(data_length);
+            assert_eq!(irq.iter().count() as u32, irq.count());
+            assert_eq!(irq.union(!irq).intersection(Irq::ALL), Irq::ALL);
+        }
+        for value in 0..=u8::MAX {
+            let _ = (Sf::from_bits_truncate(value), Sf::try_from(value));
+            let _ = (Bw::from_bits_truncate(value).hz(), Bw::try_from(value));
+            let _ = (
+                Cr::from_bits_truncate(value).denominator(),
+                Cr::try_from(value),
+            );
+            let _ = (
+                PacketType::from_bits_truncate(value),
+                RampTime::from_bits_truncate(value),
+            );
+            let _ = (
+                StatusChipMode::extract(value),
+                StatusCommandStatus::extract(value),
+            );
+            let _ = Opcode::try_from(value);
+
+            let mut get_packet_status_lora = GetPacketStatusLora::new();
+            get_packet_status_lora.rx_buf = [value; 5];
+            let _ = get_packet_status_lora.parse();
+            let mut get_rx_buffer_status = GetRxBufferStatus::new();
+            get_rx_buffer_status.rx_buf = [value; 4];
+            let _ = get_rx_buffer_status.parse();
+        }
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_packet_params_crc_flag() {
+        for crc in [false, true] {
+            assert_eq!(
+                SetPacketParams::new_with_crc_flag(
+                    8,
+                    HeaderType::FixedLength,
+                    16,
+                    crc,
+                    InvertIq::Inverted
+                ),
+                SetPacketParams::new(
+                    8,
+                    HeaderType::FixedLength,
+                    16,
+                    crc.into(),
+                    InvertIq::Inverted
+                ),
+            );
+        }
+    }
+
+    #[test]
+    fn test_opcodes() {
+        let frames: [(&[u8], Opcode); 10] = [
+            (&SetSleep::new(SleepConfig::new()).tx_buf, Opcode::SetSleep),
+            (&SetCad::new().tx_buf, Opcode::SetCad),
+            (&ReadVersion::new().tx_buf, Opcode::ReadRegister),
+            (&ReadBuffer::<4>::new(0).tx_buf, Opcode::ReadBuffer),
+            (
+                &SetDio3AsTcxoCtrl::new(TcxoVoltage::V1_8, 64).tx_buf,
+                Opcode::SetDio3AsTcxoCtrl,
+            ),
+            (
+                &SetLoraSymbNumTimeout::new(5).tx_buf,
+                Opcode::SetLoraSymbNumTimeout,
+            ),
+            (&GetStatsLora::new().tx_buf, Opcode::GetStats),
+            (&ResetStats::new().tx_buf, Opcode::ResetStats),
+            (&Calibrate::new(CalibParam::ALL).tx_buf, Opcode::Calibrate),
+            (
+                &CalibrateImage::from_mhz(902, 928).tx_buf,
+                Opcode::CalibrateImage,
+            ),
+        ];
+        for (frame, opcode) in frames {
+            assert_eq!(Opcode::from_frame(frame), Some(opcode));
+        }
+        assert_eq!(Opcode::from_frame(&[]), None);
+    }
+
     #[test]
     fn test_set_tx() {
         static SET_TX: SetTx = SetTx::new(0x00);
@@ -0,0 +1,85 @@
+//! Software CRC-16 matching the GFSK packet engine.
+//!
+//! The chip computes the GFSK CRC MSB first over the payload (and the
+//! length and address bytes when present), with the seed of [`CrcInitial`]
+//! and the polynomial of [`CrcPolynomial`], and optionally inverts the
+//! result. [`Crc16`] does the same on the host, e.g. to talk to radios that
+//! compute the CRC in software.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::crc::Crc16;
+//! use sx126x_spi_buffers::registers::{CrcInitial, CrcPolynomial};
+//!
+//! assert_eq!(Crc16::CCITT.checksum(b"123456789"), !0xE5CC);
+//! assert_eq!(Crc16::IBM.checksum(b"123456789"), 0xAEE7);
+//!
+//! let crc = Crc16::from_registers(CrcInitial(0xFFFF), CrcPolynomial(0x1021));
+//! assert_eq!(crc.checksum(b"123456789"), 0x29B1);
+//! ```
+//!
+//! [`CrcInitial`]: crate::registers::CrcInitial
+//! [`CrcPolynomial`]: crate::registers::CrcPolynomial
+
+use super::registers::{CrcInitial, CrcPolynomial};
+
+/// # CRC-16
+/// Non-reflected CRC-16 with a configurable polynomial and seed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Crc16 {
+    polynomial: u16,
+    initial: u16,
+    inverted: bool,
+}
+impl Crc16 {
+    /// Datasheet CCITT preset: seed 0x1D0F, polynomial 0x1021, inverted.
+    pub const CCITT: Self = Self::new(0x1021, 0x1D0F).with_inverted(true);
+    /// Datasheet IBM preset: seed 0xFFFF, polynomial 0x8005.
+    pub const IBM: Self = Self::new(0x8005, 0xFFFF);
+
+    #[inline(always)]
+    pub const fn new(polynomial: u16, initial: u16) -> Self {
+        Self {
+            polynomial,
+            initial,
+            inverted: false,
+        }
+    }
+    /// Same settings as the chip with these register values.
+    #[inline(always)]
+    pub const fn from_registers(initial: CrcInitial, polynomial: CrcPolynomial) -> Self {
+        Self::new(polynomial.0, initial.0)
+    }
+    /// Inverts the result, as the `*_INV` CRC types of SetPacketParams do.
+    #[inline(always)]
+    pub const fn with_inverted(mut self, inverted: bool) -> Self {
+        self.inverted = inverted;
+        self
+    }
+    #[inline(always)]
+    pub const fn polynomial(&self) -> u16 {
+        self.polynomial
+    }
+    #[inline(always)]
+    pub const fn initial(&self) -> u16 {
+        self.initial
+    }
+    #[inline(always)]
+    pub const fn checksum(&self, data: &[u8]) -> u16 {
+        let mut crc = self.initial;
+        let mut i = 0;
+        while i < data.len() {
+            crc ^= (data[i] as u16) << 8;
+            let mut bit = 0;
+            while bit < 8 {
+                crc = match crc & 0x8000 {
+                    0 => crc << 1,
+                    _ => crc << 1 ^ self.polynomial,
+                };
+                bit += 1;
+            }
+            i += 1;
+        }
+        if self.inverted { !crc } else { crc }
+    }
+}
@@ -0,0 +1,130 @@
+//! Duty-cycle limits per sub-band, e.g. the ETSI EN 300 220 limits of the
+//! 868 MHz band.
+//!
+//! After a transmission of duration T on a sub-band limited to 1/N of the
+//! time, [`DutyCycle`] keeps the sub-band closed for (N - 1)·T, the band
+//! off-time rule of LoRaWAN. The caller supplies the time, in ms from any
+//! origin, and the time on air, e.g. from
+//! [`time_on_air_us`](crate::commands::time_on_air_us).
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::commands::{time_on_air_us, Bw, Cr, HeaderType, Sf};
+//! use sx126x_spi_buffers::duty_cycle::{DutyCycle, ETSI_868};
+//!
+//! let mut duty_cycle = DutyCycle::new(ETSI_868);
+//! assert!(duty_cycle.may_transmit(868_100_000, 0));
+//!
+//! // 41.216 ms at 1%: closed for 4.121 s after the start of the transmission
+//! let toa = time_on_air_us(Sf::Sf7, Bw::Bw125, Cr::Cr4_5, 8, 10, HeaderType::VariableLength, true);
+//! duty_cycle.record(868_100_000, 1_000, toa).unwrap();
+//! assert_eq!(duty_cycle.wait_ms(868_100_000, 1_000), Some(4_122));
+//! assert_eq!(duty_cycle.wait_ms(868_300_000, 3_000), Some(2_122));
+//! assert!(duty_cycle.may_transmit(869_525_000, 1_000)); // other sub-band
+//! assert!(duty_cycle.may_transmit(868_100_000, 5_122));
+//! assert_eq!(duty_cycle.wait_ms(915_000_000, 0), None);
+//! ```
+
+/// Errors of [`DutyCycle::record`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The frequency is in none of the sub-bands.
+    NoSubBand,
+}
+
+/// A frequency range limited to `1 / divisor` of the time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SubBand {
+    pub min_hz: u32,
+    pub max_hz: u32,
+    /// 100 for 1%, 1000 for 0.1%.
+    pub divisor: u32,
+}
+impl SubBand {
+    #[inline(always)]
+    pub const fn new(min_hz: u32, max_hz: u32, divisor: u32) -> Self {
+        Self {
+            min_hz,
+            max_hz,
+            divisor,
+        }
+    }
+    #[inline(always)]
+    pub const fn contains(&self, frequency_hz: u32) -> bool {
+        self.min_hz <= frequency_hz && frequency_hz <= self.max_hz
+    }
+}
+
+/// Sub-bands of ETSI EN 300 220 for devices without listen before talk
+/// nor adaptive frequency agility, as in the LoRaWAN Regional Parameters.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::duty_cycle::{DutyCycle, ETSI_868};
+///
+/// // 10 ms at 0.1% below 865 MHz, at 1% above
+/// let mut duty_cycle = DutyCycle::new(ETSI_868);
+/// duty_cycle.record(864_000_000, 0, 10_000).unwrap();
+/// duty_cycle.record(866_000_000, 0, 10_000).unwrap();
+/// assert_eq!(duty_cycle.wait_ms(864_000_000, 0), Some(10_000));
+/// assert_eq!(duty_cycle.wait_ms(866_000_000, 0), Some(1_000));
+/// ```
+pub const ETSI_868: [SubBand; 6] = [
+    SubBand::new(863_000_000, 865_000_000, 1000),
+    SubBand::new(865_000_000, 868_000_000, 100),
+    SubBand::new(868_000_000, 868_600_000, 100),
+    SubBand::new(868_700_000, 869_200_000, 1000),
+    SubBand::new(869_400_000, 869_650_000, 10),
+    SubBand::new(869_700_000, 870_000_000, 100),
+];
+
+/// # Duty-cycle tracker
+/// Time from which each of `N` sub-bands may be used again.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DutyCycle<const N: usize> {
+    sub_bands: [SubBand; N],
+    available_at_ms: [u64; N],
+}
+impl<const N: usize> DutyCycle<N> {
+    #[inline(always)]
+    pub const fn new(sub_bands: [SubBand; N]) -> Self {
+        Self {
+            sub_bands,
+            available_at_ms: [0; N],
+        }
+    }
+    #[inline(always)]
+    pub const fn sub_bands(&self) -> &[SubBand; N] {
+        &self.sub_bands
+    }
+    /// Records a transmission on `frequency_hz` started at `now_ms` and
+    /// lasting `time_on_air_us`.
+    pub fn record(
+        &mut self,
+        frequency_hz: u32,
+        now_ms: u64,
+        time_on_air_us: u32,
+    ) -> Result<(), Error> {
+        let index = self.index(frequency_hz).ok_or(Error::NoSubBand)?;
+        let closed_us = time_on_air_us as u64 * self.sub_bands[index].divisor as u64;
+        self.available_at_ms[index] = now_ms + closed_us.div_ceil(1000);
+        Ok(())
+    }
+    /// Time to wait before transmitting on `frequency_hz`, 0 if it is
+    /// allowed at `now_ms`; `None` if the frequency is in none of the
+    /// sub-bands.
+    pub fn wait_ms(&self, frequency_hz: u32, now_ms: u64) -> Option<u64> {
+        let index = self.index(frequency_hz)?;
+        Some(self.available_at_ms[index].saturating_sub(now_ms))
+    }
+    /// Whether a transmission on `frequency_hz` is allowed at `now_ms`.
+    pub fn may_transmit(&self, frequency_hz: u32, now_ms: u64) -> bool {
+        self.wait_ms(frequency_hz, now_ms) == Some(0)
+    }
+
+    fn index(&self, frequency_hz: u32) -> Option<usize> {
+        self.sub_bands
+            .iter()
+            .position(|sub_band| sub_band.contains(frequency_hz))
+    }
+}
@@ -0,0 +1,218 @@
+//! Datasheet-ordered bring-up sequence and the snapshot restoring it after
+//! cold-start sleep.
+
+use super::commands::{
+    Bw, Command, Cr, CrcType, HeaderType, InvertIq, Irq, PacketType, RampTime,
+    SetBufferBaseAddress, SetDio2AsRfSwitchCtrl, SetDioIrqParams, SetModulationParamsLora,
+    SetPaConfig, SetPacketParams, SetPacketType, SetRfFrequency, SetStandby, SetTxParams, Sf,
+    SpiDescriptor, StdbyConfig,
+};
+
+/// # Init sequence
+/// Every command needed to bring the radio up, sent in the order of the
+/// datasheet's operational flow: STDBY_RC, packet type, RF frequency, PA
+/// and TX parameters, buffer base addresses, modulation and packet
+/// parameters, then DIO configuration.
+///
+/// [`new`](Self::new) starts from a LoRa SF7/125 kHz/4:5 configuration with
+/// the SX1262 +22 dBm PA setting; replace parts with the `with_*` methods.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{SetModulationParamsLora, Sf, Bw, Cr};
+/// use sx126x_spi_buffers::init::InitSequence;
+///
+/// let mut init = InitSequence::new(0x3640_0000)
+///     .with_modulation_params(SetModulationParamsLora::new(Sf::Sf9, Bw::Bw125, Cr::Cr4_5, false))
+///     .with_dio2_as_rf_switch(true);
+/// assert_eq!(init.set_rf_frequency.tx_buf, [0x86, 0x36, 0x40, 0, 0]);
+/// let opcodes = init.descriptors().map(|desc| unsafe { *desc.tx_buf_ptr() });
+/// assert_eq!(opcodes, [0x80, 0x8A, 0x86, 0x95, 0x8E, 0x8F, 0x8B, 0x8C, 0x08, 0x9D]);
+/// assert_eq!(init.set_modulation_params.tx_buf[1], 0x09);
+/// assert_eq!(init.set_dio2_as_rf_switch_ctrl.tx_buf, [0x9D, 1]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InitSequence {
+    pub set_standby: SetStandby,
+    pub set_packet_type: SetPacketType,
+    pub set_rf_frequency: SetRfFrequency,
+    pub set_pa_config: SetPaConfig,
+    pub set_tx_params: SetTxParams,
+    pub set_buffer_base_address: SetBufferBaseAddress,
+    pub set_modulation_params: SetModulationParamsLora,
+    pub set_packet_params: SetPacketParams,
+    pub set_dio_irq_params: SetDioIrqParams,
+    pub set_dio2_as_rf_switch_ctrl: SetDio2AsRfSwitchCtrl,
+}
+impl InitSequence {
+    /// Number of commands in the sequence.
+    pub const LENGTH: usize = 10;
+
+    #[inline(always)]
+    pub const fn new(rf_freq: u32) -> Self {
+        let irqs = Irq::new()
+            .with_tx_done(true)
+            .with_rx_done(true)
+            .with_header_err(true)
+            .with_crc_err(true)
+            .with_timeout(true);
+        Self {
+            set_standby: SetStandby::new(StdbyConfig::StdbyRc),
+            set_packet_type: SetPacketType::new(PacketType::Lora),
+            set_rf_frequency: SetRfFrequency::new(rf_freq),
+            set_pa_config: SetPaConfig::new(0x04, 0x07),
+            set_tx_params: SetTxParams::new(22, RampTime::Ramp200U),
+            set_buffer_base_address: SetBufferBaseAddress::new(0, 0),
+            set_modulation_params: SetModulationParamsLora::new(
+                Sf::Sf7,
+                Bw::Bw125,
+                Cr::Cr4_5,
+                false,
+            ),
+            set_packet_params: SetPacketParams::new(
+                8,
+                HeaderType::VariableLength,
+                255,
+                CrcType::On,
+                InvertIq::Standard,
+            ),
+            set_dio_irq_params: SetDioIrqParams::new(irqs, irqs, Irq::new(), Irq::new()),
+            set_dio2_as_rf_switch_ctrl: SetDio2AsRfSwitchCtrl::new(false),
+        }
+    }
+    #[inline(always)]
+    pub const fn with_packet_type(mut self, packet_type: PacketType) -> Self {
+        self.set_packet_type = SetPacketType::new(packet_type);
+        self
+    }
+    #[inline(always)]
+    pub const fn with_pa_config(mut self, set_pa_config: SetPaConfig) -> Self {
+        self.set_pa_config = set_pa_config;
+        self
+    }
+    #[inline(always)]
+    pub const fn with_tx_params(mut self, set_tx_params: SetTxParams) -> Self {
+        self.set_tx_params = set_tx_params;
+        self
+    }
+    #[inline(always)]
+    pub const fn with_buffer_base_address(
+        mut self,
+        tx_base_address: u8,
+        rx_base_address: u8,
+    ) -> Self {
+        self.set_buffer_base_address = SetBufferBaseAddress::new(tx_base_address, rx_base_address);
+        self
+    }
+    #[inline(always)]
+    pub const fn with_modulation_params(
+        mut self,
+        set_modulation_params: SetModulationParamsLora,
+    ) -> Self {
+        self.set_modulation_params = set_modulation_params;
+        self
+    }
+    #[inline(always)]
+    pub const fn with_packet_params(mut self, set_packet_params: SetPacketParams) -> Self {
+        self.set_packet_params = set_packet_params;
+        self
+    }
+    #[inline(always)]
+    pub const fn with_dio_irq_params(mut self, set_dio_irq_params: SetDioIrqParams) -> Self {
+        self.set_dio_irq_params = set_dio_irq_params;
+        self
+    }
+    /// Lets DIO2 drive the RF switch, high during TX.
+    #[inline(always)]
+    pub const fn with_dio2_as_rf_switch(mut self, enable: bool) -> Self {
+        self.set_dio2_as_rf_switch_ctrl = SetDio2AsRfSwitchCtrl::new(enable);
+        self
+    }
+    /// Descriptors of all commands, in the order they have to be sent.
+    #[inline(always)]
+    pub const fn descriptors(&mut self) -> [SpiDescriptor; Self::LENGTH] {
+        [
+            self.set_standby.descriptor(),
+            self.set_packet_type.descriptor(),
+            self.set_rf_frequency.descriptor(),
+            self.set_pa_config.descriptor(),
+            self.set_tx_params.descriptor(),
+            self.set_buffer_base_address.descriptor(),
+            self.set_modulation_params.descriptor(),
+            self.set_packet_params.descriptor(),
+            self.set_dio_irq_params.descriptor(),
+            self.set_dio2_as_rf_switch_ctrl.descriptor(),
+        ]
+    }
+}
+
+/// # Radio configuration snapshot
+/// Keeps the last applied value of every setting of an [`InitSequence`], so
+/// the configuration lost in cold-start sleep can be restored after wake-up.
+/// Pass every configuration command to [`record`](Self::record) when it is
+/// sent, then send the [`restore`](Self::restore) descriptors after waking.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{SetRfFrequency, SetTxParams, RampTime, GetStatus};
+/// use sx126x_spi_buffers::init::{InitSequence, RadioConfig};
+///
+/// let mut config = RadioConfig::new(InitSequence::new(0x3640_0000));
+/// assert!(config.record(&SetRfFrequency::new(0x3641_0000)));
+/// assert!(config.record(&SetTxParams::new(14, RampTime::Ramp40U)));
+/// assert!(!config.record(&GetStatus::new()));
+///
+/// let restore = config.restore();
+/// assert_eq!(unsafe { *restore[2].tx_buf_ptr().add(2) }, 0x41);
+/// assert_eq!(config.sequence().set_tx_params.tx_buf, [0x8E, 14, 0x02]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RadioConfig {
+    sequence: InitSequence,
+}
+impl RadioConfig {
+    /// Starts from the settings of `sequence`, usually the one used for
+    /// bring-up.
+    #[inline(always)]
+    pub const fn new(sequence: InitSequence) -> Self {
+        Self { sequence }
+    }
+    #[inline(always)]
+    pub const fn sequence(&self) -> &InitSequence {
+        &self.sequence
+    }
+    /// Stores the bytes of `command` if it is one of the settings of an
+    /// [`InitSequence`], and returns whether it was.
+    pub fn record<C: Command>(&mut self, command: &C) -> bool {
+        let tx_buf = command.tx_buf();
+        macro_rules! record {
+            ($($field:ident),*) => {
+                $(
+                    if tx_buf.first() == Some(&self.sequence.$field.tx_buf[0])
+                        && tx_buf.len() == self.sequence.$field.tx_buf.len()
+                    {
+                        self.sequence.$field.tx_buf.copy_from_slice(tx_buf);
+                        return true;
+                    }
+                )*
+            };
+        }
+        record!(
+            set_packet_type,
+            set_rf_frequency,
+            set_pa_config,
+            set_tx_params,
+            set_buffer_base_address,
+            set_modulation_params,
+            set_packet_params,
+            set_dio_irq_params,
+            set_dio2_as_rf_switch_ctrl
+        );
+        false
+    }
+    /// Descriptors restoring the recorded configuration, in datasheet order.
+    #[inline(always)]
+    pub const fn restore(&mut self) -> [SpiDescriptor; InitSequence::LENGTH] {
+        self.sequence.descriptors()
+    }
+}
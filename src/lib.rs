@@ -1,44 +1,106 @@
 //! <div class="warning">
-//! <strong>Requires Rust Nightly</strong>
+//! The default <code>nightly</code> feature requires Rust Nightly; without
+//! it the crate builds on stable Rust.
 //! </div>
 //!
+//! ## Panics
+//! Lengths that come from the radio at runtime, such as the received
+//! payload length, are checked and reported with a `Result` or an `Option`
+//! instead of panicking.
+//!
+//! ## Features
+//! - `cache-line-aligned`: aligns every command to a 32-byte cache line, see
+//!   [`SpiDescriptor`](commands::SpiDescriptor).
+//! - `critical-section`: interrupt-safe descriptor queues in [`queue`],
+//!   command slots in [`pool`] and async completion signals in [`signal`].
+//! - `embassy-time`: deadlines for async TX, RX and CAD in [`timeout`].
+//! - `embedded-hal`: [`blocking`] executor for any `embedded-hal` 1.0
+//!   `SpiDevice`, the typestate [`radio`] driver built on it, GPIO-driven
+//!   [`rf_switch`]es and [`arq`] retransmission.
+//! - `mock`: fake SPI target in [`mock`] that records every command, for
+//!   host tests of drivers built on this crate.
+//! - `nightly` (default): const [`registers`] traits, so register commands
+//!   can be built in `const` items, and array constructors such as
+//!   `WriteBuffer::new` that size the data from the command. Without it the
+//!   crate builds on stable Rust; use the `from_slice` constructors instead.
+//! - `scratch-rx`: TX-only descriptors point their rx side at one shared
+//!   scratch buffer instead of null, for SPI drivers that must receive as
+//!   many bytes as they send, see
+//!   [`SCRATCH_RX_LENGTH`](commands::SCRATCH_RX_LENGTH).
+//! - `trace`: hooks that see the bytes of every transfer, see [`trace`].
 #![no_std]
-#![allow(incomplete_features)]
-#![feature(generic_const_exprs)]
-#![feature(const_trait_impl)]
+#![cfg_attr(feature = "nightly", allow(incomplete_features))]
+#![cfg_attr(feature = "nightly", feature(generic_const_exprs))]
+#![cfg_attr(feature = "nightly", feature(const_trait_impl))]
 #![allow(static_mut_refs)]
 
+#[macro_use]
+mod nightly;
+
+pub mod adr;
+#[cfg(feature = "embedded-hal")]
+pub mod arq;
+#[cfg(feature = "embedded-hal")]
+pub mod blocking;
+pub mod busy;
+pub mod channels;
 pub mod commands;
+pub mod crc;
+pub mod duty_cycle;
+pub mod init;
+pub mod link;
+pub mod lorawan;
+#[cfg(feature = "mock")]
+pub mod mock;
+#[cfg(feature = "critical-section")]
+pub mod pool;
+#[cfg(feature = "critical-section")]
+pub mod queue;
+#[cfg(feature = "embedded-hal")]
+pub mod radio;
 pub mod registers;
+#[cfg(feature = "embedded-hal")]
+pub mod rf_switch;
+#[cfg(feature = "critical-section")]
+pub mod signal;
+pub mod spsc;
+#[cfg(feature = "embassy-time")]
+pub mod timeout;
+#[cfg(feature = "trace")]
+pub mod trace;
+pub mod transaction;
+pub mod whitening;
+pub mod workarounds;
 
 #[cfg(test)]
 mod tests {
     use super::commands::{self, SetSleep, SetStandby, SleepConfig, StdbyConfig, WriteBuffer};
-    use static_fifo_queue::Queue;
+    use super::spsc::SpscQueue;
 
     #[test]
     fn test_queue() {
-        let mut queue: Queue<commands::SpiDescriptor, 8> = Queue::new();
+        let mut queue: SpscQueue<8> = SpscQueue::new();
+        let (mut producer, mut consumer) = queue.split();
         static mut SET_SLEEP: SetSleep =
             commands::SetSleep::new(SleepConfig::new().with_warm_start(true));
         static mut SET_STANDY: SetStandby = commands::SetStandby::new(StdbyConfig::StdbyRc);
-        static mut WRITE_BUFFER: WriteBuffer<7> = commands::WriteBuffer::new(0, [2, 4, 7, 9, 3]);
+        static mut WRITE_BUFFER: WriteBuffer<7> =
+            commands::WriteBuffer::from_slice(0, &[2, 4, 7, 9, 3]);
         unsafe {
-            queue.enqueue(SET_SLEEP.descriptor());
-            queue.enqueue(SET_STANDY.descriptor());
-            queue.enqueue(WRITE_BUFFER.descriptor());
+            producer.enqueue(SET_SLEEP.descriptor()).unwrap();
+            producer.enqueue(SET_STANDY.descriptor()).unwrap();
+            producer.enqueue(WRITE_BUFFER.descriptor()).unwrap();
 
-            let mut desc = queue.dequeue().unwrap();
-            let mut tx_buf =
-                core::slice::from_raw_parts(desc.tx_buf_ptr, desc.transfer_length as usize);
+            let mut desc = consumer.dequeue().unwrap();
+            let mut tx_buf = desc.tx_slice();
             assert_eq!(tx_buf, [0x84, 1 << 2]);
 
-            desc = queue.dequeue().unwrap();
-            tx_buf = core::slice::from_raw_parts(desc.tx_buf_ptr, desc.transfer_length as usize);
+            desc = consumer.dequeue().unwrap();
+            tx_buf = desc.tx_slice();
             assert_eq!(tx_buf, [0x80, 0]);
 
-            desc = queue.dequeue().unwrap();
-            tx_buf = core::slice::from_raw_parts(desc.tx_buf_ptr, desc.transfer_length as usize);
+            desc = consumer.dequeue().unwrap();
+            tx_buf = desc.tx_slice();
             assert_eq!(tx_buf, [0x0E, 0, 2, 4, 7, 9, 3]);
         }
     }
@@ -0,0 +1,210 @@
+//! Point-to-point link layer: addressed, sequenced and acknowledged
+//! datagrams.
+//!
+//! Every frame starts with a 4-byte [`Header`] and ends with a CRC-16
+//! (CCITT, big-endian) over header and payload, so corrupted frames are
+//! rejected even with the LoRa CRC disabled. Data frames are answered with
+//! an empty ACK frame carrying the same sequence number; retransmissions
+//! are recognised by their sequence number and acknowledged again without
+//! being delivered twice.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::link::{Error, FrameKind, Link};
+//!
+//! let mut node_a = Link::new(0x0A);
+//! let mut node_b = Link::new(0x0B);
+//!
+//! let mut frame = [0; 32];
+//! let length = node_a.encode_data(0x0B, b"hello", &mut frame).unwrap();
+//! assert_eq!(length, 4 + 5 + 2);
+//!
+//! let (header, payload) = node_b.decode(&frame[..length]).unwrap();
+//! assert_eq!((header.kind, header.source, payload), (FrameKind::Data, 0x0A, &b"hello"[..]));
+//! assert!(node_b.accept(&header));
+//! assert!(!node_b.accept(&header)); // retransmission
+//!
+//! let mut ack = [0; 8];
+//! let ack_length = node_b.encode_ack(&header, &mut ack).unwrap();
+//! assert!(node_a.is_ack_for(&ack[..ack_length], header.sequence));
+//! assert_eq!(node_b.decode(&ack[..ack_length]), Err(Error::NotForUs));
+//! ```
+
+use super::crc::Crc16;
+
+/// Destination address accepted by every node.
+pub const BROADCAST: u8 = 0xFF;
+pub const HEADER_LENGTH: usize = 4;
+pub const CRC_LENGTH: usize = 2;
+/// Longest payload that fits in a 255-byte LoRa packet.
+pub const MAX_PAYLOAD_LENGTH: usize = 255 - HEADER_LENGTH - CRC_LENGTH;
+
+/// CRC-16/CCITT-FALSE.
+const CRC: Crc16 = Crc16::new(0x1021, 0xFFFF);
+
+/// Errors of [`Link`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The frame does not fit in the buffer.
+    BufferTooSmall,
+    /// The payload is longer than [`MAX_PAYLOAD_LENGTH`].
+    PayloadTooLong,
+    /// The frame is shorter than a header and CRC, or has an unknown kind.
+    Malformed,
+    /// The CRC does not match.
+    Crc,
+    /// The frame is addressed to another node.
+    NotForUs,
+}
+
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrameKind {
+    Data = 0x00,
+    Ack = 0x01,
+}
+
+/// First bytes of every frame: kind, destination, source, sequence number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Header {
+    pub kind: FrameKind,
+    pub destination: u8,
+    pub source: u8,
+    pub sequence: u8,
+}
+impl Header {
+    #[inline(always)]
+    pub const fn to_bytes(&self) -> [u8; HEADER_LENGTH] {
+        [
+            self.kind as u8,
+            self.destination,
+            self.source,
+            self.sequence,
+        ]
+    }
+    #[inline(always)]
+    pub const fn from_bytes(bytes: [u8; HEADER_LENGTH]) -> Option<Self> {
+        let kind = match bytes[0] {
+            0x00 => FrameKind::Data,
+            0x01 => FrameKind::Ack,
+            _ => return None,
+        };
+        Some(Self {
+            kind,
+            destination: bytes[1],
+            source: bytes[2],
+            sequence: bytes[3],
+        })
+    }
+}
+
+/// # Link
+/// One node of the link, with its own address and sequence counter. Frames
+/// are encoded into and decoded from plain byte slices, to be sent with
+/// [`WriteBuffer`](crate::commands::WriteBuffer) and received with
+/// [`ReadBuffer`](crate::commands::ReadBuffer) or the [`radio`](crate::radio)
+/// driver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Link {
+    address: u8,
+    sequence: u8,
+    /// Source and sequence number of the last accepted data frame.
+    last_accepted: Option<(u8, u8)>,
+}
+impl Link {
+    #[inline(always)]
+    pub const fn new(address: u8) -> Self {
+        Self {
+            address,
+            sequence: 0,
+            last_accepted: None,
+        }
+    }
+    #[inline(always)]
+    pub const fn address(&self) -> u8 {
+        self.address
+    }
+    /// Sequence number of the next data frame.
+    #[inline(always)]
+    pub const fn sequence(&self) -> u8 {
+        self.sequence
+    }
+    /// Encodes a data frame into `buf` and returns its length. Each call
+    /// uses a new sequence number; retransmit the same bytes to retry.
+    pub fn encode_data(
+        &mut self,
+        destination: u8,
+        payload: &[u8],
+        buf: &mut [u8],
+    ) -> Result<usize, Error> {
+        let header = Header {
+            kind: FrameKind::Data,
+            destination,
+            source: self.address,
+            sequence: self.sequence,
+        };
+        let length = encode(&header, payload, buf)?;
+        self.sequence = self.sequence.wrapping_add(1);
+        Ok(length)
+    }
+    /// Encodes the ACK of the data frame with header `header` into `buf`.
+    pub fn encode_ack(&self, header: &Header, buf: &mut [u8]) -> Result<usize, Error> {
+        let ack = Header {
+            kind: FrameKind::Ack,
+            destination: header.source,
+            source: self.address,
+            sequence: header.sequence,
+        };
+        encode(&ack, &[], buf)
+    }
+    /// Checks the CRC and the destination of `frame` and splits it into
+    /// header and payload.
+    pub fn decode<'a>(&self, frame: &'a [u8]) -> Result<(Header, &'a [u8]), Error> {
+        if frame.len() < HEADER_LENGTH + CRC_LENGTH {
+            return Err(Error::Malformed);
+        }
+        let (data, crc) = frame.split_at(frame.len() - CRC_LENGTH);
+        if CRC.checksum(data).to_be_bytes() != crc {
+            return Err(Error::Crc);
+        }
+        let (header, payload) = data.split_at(HEADER_LENGTH);
+        let header = Header::from_bytes([header[0], header[1], header[2], header[3]])
+            .ok_or(Error::Malformed)?;
+        if header.destination != self.address && header.destination != BROADCAST {
+            return Err(Error::NotForUs);
+        }
+        Ok((header, payload))
+    }
+    /// Records a decoded data frame and returns `false` if it repeats the
+    /// last one accepted, i.e. the sender missed the ACK. Acknowledge the
+    /// frame either way.
+    pub fn accept(&mut self, header: &Header) -> bool {
+        let id = (header.source, header.sequence);
+        if self.last_accepted == Some(id) {
+            return false;
+        }
+        self.last_accepted = Some(id);
+        true
+    }
+    /// Whether `frame` acknowledges our data frame number `sequence`, e.g.
+    /// as the `is_ack` callback of [`Arq::send`](crate::arq::Arq::send).
+    pub fn is_ack_for(&self, frame: &[u8], sequence: u8) -> bool {
+        matches!(
+            self.decode(frame),
+            Ok((header, _)) if header.kind == FrameKind::Ack && header.sequence == sequence
+        )
+    }
+}
+
+fn encode(header: &Header, payload: &[u8], buf: &mut [u8]) -> Result<usize, Error> {
+    if payload.len() > MAX_PAYLOAD_LENGTH {
+        return Err(Error::PayloadTooLong);
+    }
+    let length = HEADER_LENGTH + payload.len() + CRC_LENGTH;
+    let frame = buf.get_mut(..length).ok_or(Error::BufferTooSmall)?;
+    frame[..HEADER_LENGTH].copy_from_slice(&header.to_bytes());
+    frame[HEADER_LENGTH..length - CRC_LENGTH].copy_from_slice(payload);
+    let crc = CRC.checksum(&frame[..length - CRC_LENGTH]);
+    frame[length - CRC_LENGTH..].copy_from_slice(&crc.to_be_bytes());
+    Ok(length)
+}
@@ -0,0 +1,127 @@
+//! LoRaWAN regional PHY presets, after the LoRaWAN Regional Parameters.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::lorawan::{self, Region};
+//!
+//! let dr0 = Region::Eu868.modulation_params(0).unwrap();
+//! assert_eq!(dr0.tx_buf, [0x8B, 0x0C, 0x04, 0x01, 1]);
+//! let dr4 = Region::Us915.modulation_params(4).unwrap();
+//! assert_eq!(dr4.tx_buf, [0x8B, 0x08, 0x06, 0x01, 0]);
+//! assert!(Region::Eu868.modulation_params(7).is_none()); // FSK
+//!
+//! assert_eq!(lorawan::uplink_packet_params(12).tx_buf, [0x8C, 0, 8, 0, 12, 1, 0]);
+//! assert_eq!(lorawan::downlink_packet_params(12).tx_buf, [0x8C, 0, 8, 0, 12, 0, 1]);
+//! ```
+
+use super::commands::{
+    Bw, Cr, CrcType, HeaderType, InvertIq, SetModulationParamsLora, SetPacketParams, Sf,
+};
+use super::registers::LoraSyncWord;
+
+/// Sync word of public LoRaWAN networks.
+pub const SYNC_WORD: LoraSyncWord = LoraSyncWord(0x3444);
+/// Preamble length of every LoRaWAN LoRa frame.
+pub const PREAMBLE_LENGTH: u16 = 8;
+/// Longest uplink in regions with a dwell time limit, see
+/// [`Region::max_payload_length`].
+pub const MAX_DWELL_TIME_US: u32 = 400_000;
+
+/// A LoRaWAN region.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Region {
+    Eu868,
+    Us915,
+    Cn470,
+    Au915,
+    Eu433,
+    Kr920,
+    As923,
+    In865,
+}
+impl Region {
+    /// Modulation parameters of data rate `dr`, with CR 4/5 and LDRO set as
+    /// required. `None` for FSK, LR-FHSS and RFU data rates.
+    #[inline(always)]
+    pub const fn modulation_params(self, dr: u8) -> Option<SetModulationParamsLora> {
+        let (sf, bw) = match (self, dr) {
+            (_, 0..=5) if !matches!(self, Self::Us915) => {
+                (Sf::from_bits_truncate(12 - dr), Bw::Bw125)
+            }
+            (Self::Eu868 | Self::Eu433 | Self::As923, 6) => (Sf::Sf7, Bw::Bw250),
+            (Self::Us915, 0..=3) => (Sf::from_bits_truncate(10 - dr), Bw::Bw125),
+            (Self::Us915, 4) | (Self::Au915, 6) => (Sf::Sf8, Bw::Bw500),
+            (Self::Us915 | Self::Au915, 8..=13) => (Sf::from_bits_truncate(20 - dr), Bw::Bw500),
+            _ => return None,
+        };
+        Some(SetModulationParamsLora::new_auto_ldro(sf, bw, Cr::Cr4_5))
+    }
+    /// Maximum MAC payload size M at data rate `dr`, from the tables of
+    /// the Regional Parameters (RP002); the PHY payload is 5 bytes longer
+    /// and the application payload N at most M - 8. `dwell_time_limited` is
+    /// the UplinkDwellTime of AS923 and AU915, which limits the time on air
+    /// to [`MAX_DWELL_TIME_US`]. US915 and AU915 DR8 to DR13 are downlink
+    /// data rates. `None` where
+    /// [`modulation_params`](Self::modulation_params) is, or where RP002
+    /// defines no size.
+    ///
+    /// ```
+    /// use sx126x_spi_buffers::lorawan::Region;
+    ///
+    /// assert_eq!(Region::Eu868.max_payload_length(0, false), Some(59));
+    /// assert_eq!(Region::Eu868.max_payload_length(5, false), Some(230));
+    /// assert_eq!(Region::Us915.max_payload_length(0, false), Some(19));
+    /// assert_eq!(Region::Us915.max_payload_length(8, false), Some(61));
+    /// assert_eq!(Region::As923.max_payload_length(2, false), Some(123));
+    /// assert_eq!(Region::As923.max_payload_length(2, true), Some(19));
+    /// assert_eq!(Region::As923.max_payload_length(0, true), None);
+    /// ```
+    #[inline(always)]
+    pub const fn max_payload_length(self, dr: u8, dwell_time_limited: bool) -> Option<u8> {
+        if self.modulation_params(dr).is_none() {
+            return None;
+        }
+        // M per data rate, 0 where undefined.
+        let table: &[u8] = match (self, dwell_time_limited) {
+            (Self::Eu868 | Self::Eu433 | Self::Kr920 | Self::In865, _) => {
+                &[59, 59, 59, 123, 230, 230, 230, 230]
+            }
+            (Self::Us915, _) => &[19, 61, 133, 250, 250, 0, 0, 0, 61, 137, 250, 250, 250, 250],
+            (Self::Au915, false) => &[
+                59, 59, 59, 123, 250, 250, 250, 0, 61, 137, 250, 250, 250, 250,
+            ],
+            (Self::Au915, true) => &[0, 0, 19, 61, 133, 250, 250, 0, 61, 137, 250, 250, 250, 250],
+            (Self::As923, false) => &[59, 59, 123, 123, 250, 250, 250, 250],
+            (Self::As923, true) => &[0, 0, 19, 61, 133, 250, 250, 250],
+            (Self::Cn470, _) => &[0, 31, 94, 192, 250, 250, 250, 250],
+        };
+        match dr as usize {
+            dr if dr < table.len() && table[dr] != 0 => Some(table[dr]),
+            _ => None,
+        }
+    }
+}
+
+/// Packet parameters of an uplink: explicit header, CRC, standard IQ.
+#[inline(always)]
+pub const fn uplink_packet_params(payload_length: u8) -> SetPacketParams {
+    SetPacketParams::new(
+        PREAMBLE_LENGTH,
+        HeaderType::VariableLength,
+        payload_length,
+        CrcType::On,
+        InvertIq::Standard,
+    )
+}
+
+/// Packet parameters of a downlink: explicit header, no CRC, inverted IQ.
+#[inline(always)]
+pub const fn downlink_packet_params(payload_length: u8) -> SetPacketParams {
+    SetPacketParams::new(
+        PREAMBLE_LENGTH,
+        HeaderType::VariableLength,
+        payload_length,
+        CrcType::Off,
+        InvertIq::Inverted,
+    )
+}
@@ -0,0 +1,259 @@
+//! Fake SX126x SPI target for host tests of drivers built on this crate.
+//!
+//! [`CaptureSpi`] implements `embedded-hal`'s [`SpiDevice`]: it records the
+//! bytes clocked out in every NSS frame and clocks in canned responses
+//! selected by opcode. [`IdlePin`] and [`NoDelay`] stand in for the BUSY
+//! pin and the delay.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::blocking::BlockingExecutor;
+//! use sx126x_spi_buffers::commands::{GetStatus, SetStandby, StdbyConfig};
+//! use sx126x_spi_buffers::mock::{CaptureSpi, IdlePin, NoDelay};
+//!
+//! let spi: CaptureSpi<8, 64> = CaptureSpi::new().with_response(0xC0, &[0xA2, 0x22]);
+//! let mut executor = BlockingExecutor::new(spi, IdlePin, NoDelay);
+//!
+//! executor.run(&mut SetStandby::new(StdbyConfig::StdbyRc)).unwrap();
+//! let mut get_status = GetStatus::new();
+//! executor.run(&mut get_status).unwrap();
+//! assert_eq!(get_status.rx_buf, [0xA2, 0x22]);
+//!
+//! let (spi, _, _) = executor.release();
+//! spi.assert_opcodes(&[0x80, 0xC0]);
+//! spi.assert_frames(&[&[0x80, 0x00], &[0xC0, 0x00]]);
+//! ```
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::{self, InputPin};
+use embedded_hal::spi::{self, Operation, SpiDevice};
+
+/// Maximum number of canned responses of a [`CaptureSpi`].
+pub const MAX_RESPONSES: usize = 16;
+
+/// Errors of [`CaptureSpi`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// More than `N` frames were transferred.
+    FramesFull,
+    /// More than `B` bytes were clocked out in total.
+    BytesFull,
+}
+impl spi::Error for Error {
+    fn kind(&self) -> spi::ErrorKind {
+        spi::ErrorKind::Other
+    }
+}
+
+/// # CaptureSpi
+/// Records up to `N` frames and `B` bytes in total. The bytes clocked in
+/// during a frame come from the response registered for its first byte,
+/// indexed by position in the frame, and are zero past its end or when no
+/// response is registered.
+pub struct CaptureSpi<const N: usize, const B: usize> {
+    bytes: [u8; B],
+    frames: [(usize, usize); N],
+    frame_count: usize,
+    responses: [(u8, &'static [u8]); MAX_RESPONSES],
+    response_count: usize,
+}
+impl<const N: usize, const B: usize> CaptureSpi<N, B> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            bytes: [0; B],
+            frames: [(0, 0); N],
+            frame_count: 0,
+            responses: [(0, &[]); MAX_RESPONSES],
+            response_count: 0,
+        }
+    }
+    /// Clocks in `response` in every frame starting with `opcode`, replacing
+    /// an earlier response for the same opcode.
+    ///
+    /// # Panics
+    /// If more than [`MAX_RESPONSES`] opcodes have a response.
+    pub fn with_response(mut self, opcode: u8, response: &'static [u8]) -> Self {
+        match self.responses[..self.response_count]
+            .iter_mut()
+            .find(|(op, _)| *op == opcode)
+        {
+            Some(entry) => entry.1 = response,
+            None => {
+                self.responses[self.response_count] = (opcode, response);
+                self.response_count += 1;
+            }
+        }
+        self
+    }
+    /// Number of recorded frames.
+    pub fn len(&self) -> usize {
+        self.frame_count
+    }
+    pub fn is_empty(&self) -> bool {
+        self.frame_count == 0
+    }
+    /// Bytes clocked out in frame `index`.
+    pub fn frame(&self, index: usize) -> Option<&[u8]> {
+        self.frames[..self.frame_count]
+            .get(index)
+            .map(|&(start, length)| &self.bytes[start..start + length])
+    }
+    pub fn frames(&self) -> impl Iterator<Item = &[u8]> {
+        (0..self.frame_count).filter_map(|index| self.frame(index))
+    }
+    /// First byte of every non-empty frame.
+    pub fn opcodes(&self) -> impl Iterator<Item = u8> {
+        self.frames().filter_map(|frame| frame.first().copied())
+    }
+    /// Forgets all recorded frames, keeping the responses.
+    pub fn clear(&mut self) {
+        self.frame_count = 0;
+    }
+    /// # Panics
+    /// If the recorded frames differ from `expected`.
+    pub fn assert_frames(&self, expected: &[&[u8]]) {
+        assert_eq!(self.len(), expected.len(), "number of frames");
+        for (index, (frame, expected)) in self.frames().zip(expected).enumerate() {
+            assert_eq!(frame, *expected, "frame {index}");
+        }
+    }
+    /// # Panics
+    /// If the opcodes of the recorded frames differ from `expected`.
+    pub fn assert_opcodes(&self, expected: &[u8]) {
+        assert_eq!(self.opcodes().count(), expected.len(), "number of commands");
+        for (index, (opcode, expected)) in self.opcodes().zip(expected).enumerate() {
+            assert_eq!(opcode, *expected, "opcode of command {index}");
+        }
+    }
+
+    fn response(&self, opcode: u8) -> &'static [u8] {
+        self.responses[..self.response_count]
+            .iter()
+            .find(|(op, _)| *op == opcode)
+            .map_or(&[], |(_, response)| response)
+    }
+    /// Appends `tx` to the frame being recorded.
+    fn push(&mut self, tx: &[u8]) -> Result<(), Error> {
+        let (start, length) = self.frames[self.frame_count];
+        let end = start + length;
+        if end + tx.len() > B {
+            return Err(Error::BytesFull);
+        }
+        self.bytes[end..end + tx.len()].copy_from_slice(tx);
+        self.frames[self.frame_count].1 += tx.len();
+        Ok(())
+    }
+    /// Fills `rx` from the response to the frame being recorded, starting
+    /// at `position` in the frame.
+    fn fill(&self, position: usize, rx: &mut [u8]) {
+        let (start, length) = self.frames[self.frame_count];
+        let response = match length {
+            0 => &[],
+            _ => self.response(self.bytes[start]),
+        };
+        for (offset, byte) in rx.iter_mut().enumerate() {
+            *byte = response.get(position + offset).copied().unwrap_or(0);
+        }
+    }
+}
+
+impl<const N: usize, const B: usize> Default for CaptureSpi<N, B> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize, const B: usize> spi::ErrorType for CaptureSpi<N, B> {
+    type Error = Error;
+}
+
+impl<const N: usize, const B: usize> SpiDevice for CaptureSpi<N, B> {
+    fn transaction(&mut self, operations: &mut [Operation<'_, u8>]) -> Result<(), Error> {
+        if self.frame_count == N {
+            return Err(Error::FramesFull);
+        }
+        let start = match self.frame_count {
+            0 => 0,
+            count => self.frames[count - 1].0 + self.frames[count - 1].1,
+        };
+        self.frames[self.frame_count] = (start, 0);
+        for operation in operations {
+            let position = self.frames[self.frame_count].1;
+            match operation {
+                Operation::Write(tx) => self.push(tx)?,
+                Operation::Transfer(rx, tx) => {
+                    self.push(tx)?;
+                    self.fill(position, rx);
+                }
+                Operation::TransferInPlace(buf) => {
+                    self.push(buf)?;
+                    self.fill(position, buf);
+                }
+                // Dummy bytes are clocked out but not recorded.
+                Operation::Read(rx) => self.fill(position, rx),
+                Operation::DelayNs(_) => {}
+            }
+        }
+        self.frame_count += 1;
+        Ok(())
+    }
+}
+
+/// BUSY pin that is always low.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct IdlePin;
+
+impl digital::ErrorType for IdlePin {
+    type Error = core::convert::Infallible;
+}
+
+impl InputPin for IdlePin {
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+}
+
+/// Delay that returns immediately.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct NoDelay;
+
+impl DelayNs for NoDelay {
+    fn delay_ns(&mut self, _ns: u32) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blocking::BlockingExecutor;
+    use crate::commands::WriteBufferHeader;
+
+    #[test]
+    fn test_scatter_and_read_buffer() {
+        let spi: CaptureSpi<4, 32> =
+            CaptureSpi::new().with_response(0x1E, &[0xA2, 0xA2, 0xA2, 1, 2, 3]);
+        let mut executor = BlockingExecutor::new(spi, IdlePin, NoDelay);
+
+        let mut header = WriteBufferHeader::new(0x10);
+        let mut desc = header.scatter_descriptor(b"ping");
+        unsafe { executor.execute(&mut desc) }.unwrap();
+        let mut buf = [0; 3];
+        executor.read_buffer(0x80, &mut buf).unwrap();
+        assert_eq!(buf, [1, 2, 3]);
+
+        let (spi, _, _) = executor.release();
+        spi.assert_frames(&[b"\x0E\x10ping", &[0x1E, 0x80, 0]]);
+    }
+
+    #[test]
+    fn test_full() {
+        let mut spi: CaptureSpi<1, 4> = CaptureSpi::new();
+        assert_eq!(spi.write(&[0; 5]), Err(Error::BytesFull));
+        spi.write(&[0x80, 0]).unwrap();
+        assert_eq!(spi.write(&[0x80, 0]), Err(Error::FramesFull));
+        spi.assert_opcodes(&[0x80]);
+    }
+}
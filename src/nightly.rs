@@ -0,0 +1,66 @@
+//! Items that are `const` only with the `nightly` feature.
+//!
+//! `impl const` and `const` trait bounds are rejected by stable compilers
+//! even in code disabled by `cfg`, so they are only written in the bodies
+//! of the macros below, which are never expanded on stable.
+
+/// Implements [`Register`](crate::registers::Register) or
+/// [`Register16`](crate::registers::Register16), as a const impl with the
+/// `nightly` feature.
+#[cfg(feature = "nightly")]
+macro_rules! impl_register {
+    ($trait:ident for $register:ty { $($item:tt)* }) => {
+        impl const $trait for $register { $($item)* }
+    };
+}
+#[cfg(not(feature = "nightly"))]
+macro_rules! impl_register {
+    ($trait:ident for $register:ty { $($item:tt)* }) => {
+        impl $trait for $register { $($item)* }
+    };
+}
+
+/// Methods generic over a register trait, `const fn` with the `nightly`
+/// feature. The bound goes either on the impl block or on the one method.
+#[cfg(feature = "nightly")]
+macro_rules! register_fns {
+    (
+        impl<$r:ident: $bound:ident> $type:ty {
+            $($(#[$attr:meta])* pub fn $name:ident($($arg:tt)*) -> $ret:ty $body:block)*
+        }
+    ) => {
+        impl<$r: const $bound> $type {
+            $($(#[$attr])* pub const fn $name($($arg)*) -> $ret $body)*
+        }
+    };
+    (
+        impl $type:ty {
+            $(#[$attr:meta])* pub fn $name:ident<$r:ident: $bound:ident>($($arg:tt)*) -> $ret:ty $body:block
+        }
+    ) => {
+        impl $type {
+            $(#[$attr])* pub const fn $name<$r: const $bound>($($arg)*) -> $ret $body
+        }
+    };
+}
+#[cfg(not(feature = "nightly"))]
+macro_rules! register_fns {
+    (
+        impl<$r:ident: $bound:ident> $type:ty {
+            $($(#[$attr:meta])* pub fn $name:ident($($arg:tt)*) -> $ret:ty $body:block)*
+        }
+    ) => {
+        impl<$r: $bound> $type {
+            $($(#[$attr])* pub fn $name($($arg)*) -> $ret $body)*
+        }
+    };
+    (
+        impl $type:ty {
+            $(#[$attr:meta])* pub fn $name:ident<$r:ident: $bound:ident>($($arg:tt)*) -> $ret:ty $body:block
+        }
+    ) => {
+        impl $type {
+            $(#[$attr])* pub fn $name<$r: $bound>($($arg)*) -> $ret $body
+        }
+    };
+}
@@ -0,0 +1,168 @@
+//! Statically allocated command slots.
+
+use super::commands::{Command, SpiDescriptor};
+use core::cell::{RefCell, UnsafeCell};
+use core::mem::MaybeUninit;
+use critical_section::Mutex;
+
+/// # CommandPool
+/// `N` slots for commands of type `C` in a single `static`. Each slot is
+/// handed out as a `&'static mut C` (or straight away as a descriptor) and
+/// returned with [`free`](Self::free), which consumes the reference, or the
+/// unsafe [`release`](Self::release) once the transfer has completed,
+/// replacing one `static mut` per in-flight command.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{SetStandby, StdbyConfig};
+/// use sx126x_spi_buffers::pool::CommandPool;
+///
+/// static POOL: CommandPool<SetStandby, 2> = CommandPool::new();
+///
+/// let first = POOL.descriptor(SetStandby::new(StdbyConfig::StdbyRc)).unwrap();
+/// let second = POOL.descriptor(SetStandby::new(StdbyConfig::StdbyXosc)).unwrap();
+/// assert!(POOL.descriptor(SetStandby::new(StdbyConfig::StdbyRc)).is_none());
+/// assert_eq!(unsafe { *second.tx_buf_ptr().add(1) }, 0x01);
+///
+/// // DMA-complete ISR, the transfer of `first` is done
+/// assert!(unsafe { POOL.release(&first) });
+/// assert_eq!(POOL.available(), 1);
+/// ```
+pub struct CommandPool<C, const N: usize> {
+    slots: [UnsafeCell<MaybeUninit<C>>; N],
+    used: Mutex<RefCell<[bool; N]>>,
+}
+
+// SAFETY: a slot is only reachable through the unique `&'static mut C`
+// handed out while its `used` flag is set, and flags are only changed inside
+// a critical section.
+unsafe impl<C: Send, const N: usize> Sync for CommandPool<C, N> {}
+
+impl<C, const N: usize> CommandPool<C, N> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            slots: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            used: Mutex::new(RefCell::new([false; N])),
+        }
+    }
+    /// Moves `command` into a free slot, handing it back if the pool is
+    /// exhausted.
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc(&'static self, command: C) -> Result<&'static mut C, C> {
+        let index = critical_section::with(|cs| {
+            let mut used = self.used.borrow_ref_mut(cs);
+            let index = used.iter().position(|used| !used)?;
+            used[index] = true;
+            Some(index)
+        });
+        match index {
+            // SAFETY: the slot was free and is now reserved for this caller.
+            Some(index) => Ok(unsafe { (*self.slots[index].get()).write(command) }),
+            None => Err(command),
+        }
+    }
+    /// Returns a slot obtained from [`alloc`](Self::alloc) to the pool.
+    /// Returns `false` if `command` does not belong to this pool.
+    pub fn free(&self, command: &'static mut C) -> bool {
+        self.free_containing(command as *mut C as *const u8)
+    }
+    /// Number of free slots.
+    pub fn available(&self) -> usize {
+        critical_section::with(|cs| {
+            self.used
+                .borrow_ref(cs)
+                .iter()
+                .filter(|used| !**used)
+                .count()
+        })
+    }
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    fn free_containing(&self, ptr: *const u8) -> bool {
+        let base = self.slots.as_ptr() as usize;
+        let offset = (ptr as usize).wrapping_sub(base);
+        if offset >= N * size_of::<C>() {
+            return false;
+        }
+        let index = offset / size_of::<C>();
+        critical_section::with(|cs| {
+            let mut used = self.used.borrow_ref_mut(cs);
+            if !used[index] {
+                return false;
+            }
+            // SAFETY: the slot is in use, so it holds an initialized command
+            // whose only reference has been given back to the pool.
+            unsafe { (*self.slots[index].get()).assume_init_drop() };
+            used[index] = false;
+            true
+        })
+    }
+}
+
+impl<C: Command, const N: usize> CommandPool<C, N> {
+    /// Allocates a slot for `command` and returns its descriptor, or `None`
+    /// if the pool is exhausted.
+    pub fn descriptor(&'static self, command: C) -> Option<SpiDescriptor> {
+        self.alloc(command).ok().map(SpiDescriptor::new)
+    }
+    /// Frees the slot a descriptor from [`descriptor`](Self::descriptor)
+    /// points into. Returns `false` if it does not belong to this pool.
+    ///
+    /// # Safety
+    /// The transfer of `desc` must have completed, and neither `desc`, its
+    /// copies nor any reference to the command may be used afterwards: the
+    /// command is dropped and the slot can be handed out again.
+    pub unsafe fn release(&self, desc: &SpiDescriptor) -> bool {
+        self.free_containing(desc.tx_buf_ptr())
+    }
+}
+
+impl<C, const N: usize> Default for CommandPool<C, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{SetTx, WriteBuffer};
+
+    #[test]
+    fn test_command_pool_reuse() {
+        static POOL: CommandPool<WriteBuffer<5>, 2> = CommandPool::new();
+        static OTHER: CommandPool<SetTx, 1> = CommandPool::new();
+        let first = POOL
+            .alloc(WriteBuffer::from_slice(0, &[1, 2, 3]))
+            .ok()
+            .unwrap();
+        let desc = POOL
+            .descriptor(WriteBuffer::from_slice(3, &[4, 5, 6]))
+            .unwrap();
+        assert!(POOL.alloc(WriteBuffer::from_slice(0, &[0; 3])).is_err());
+        assert_eq!(desc.transfer_length(), 5);
+
+        let other = OTHER.descriptor(SetTx::new(0)).unwrap();
+        // SAFETY: nothing is in flight and `desc` is not used once released.
+        unsafe {
+            assert!(!POOL.release(&other));
+            assert!(POOL.release(&desc));
+            assert!(!POOL.release(&desc));
+        }
+        assert_eq!(POOL.available(), 1);
+
+        let second = POOL
+            .alloc(WriteBuffer::from_slice(6, &[7, 8, 9]))
+            .ok()
+            .unwrap();
+        assert_eq!(second.tx_buf, [0x0E, 6, 7, 8, 9]);
+        assert_eq!(first.tx_buf, [0x0E, 0, 1, 2, 3]);
+        assert!(POOL.free(first));
+        assert!(POOL.free(second));
+        assert_eq!(POOL.available(), 2);
+    }
+}
@@ -0,0 +1,271 @@
+//! Descriptor queues shared between the main context and interrupt handlers.
+
+use super::commands::SpiDescriptor;
+use core::cell::RefCell;
+use critical_section::Mutex;
+
+/// # DescriptorQueue
+/// Fixed-capacity FIFO of [`SpiDescriptor`]s protected by a critical
+/// section, so a single `static` can be filled from the main context and
+/// drained from the DMA or DIO1 interrupt without `static mut`.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{SetStandby, StdbyConfig, SpiDescriptor};
+/// use sx126x_spi_buffers::queue::{DescriptorQueue, QueueStats};
+///
+/// static QUEUE: DescriptorQueue<4> = DescriptorQueue::new();
+///
+/// let set_standby: &'static mut SetStandby =
+///     Box::leak(Box::new(SetStandby::new(StdbyConfig::StdbyRc)));
+/// let desc = SpiDescriptor::new(set_standby);
+/// QUEUE.enqueue(desc).unwrap();
+/// assert_eq!(QUEUE.len(), 1);
+/// assert_eq!(QUEUE.dequeue(), Some(desc));
+/// assert!(QUEUE.is_empty());
+/// assert_eq!(QUEUE.stats(), QueueStats { high_water_mark: 1, dropped: 0 });
+/// ```
+pub struct DescriptorQueue<const N: usize> {
+    inner: Mutex<RefCell<Ring<N>>>,
+}
+impl<const N: usize> DescriptorQueue<N> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new(Ring::new())),
+        }
+    }
+    /// Appends a descriptor, handing it back if the queue is full. Rejected
+    /// descriptors are counted in [`QueueStats::dropped`].
+    pub fn enqueue(&self, desc: SpiDescriptor) -> Result<(), SpiDescriptor> {
+        critical_section::with(|cs| self.inner.borrow_ref_mut(cs).push(desc))
+    }
+    pub fn dequeue(&self) -> Option<SpiDescriptor> {
+        critical_section::with(|cs| self.inner.borrow_ref_mut(cs).pop())
+    }
+    pub fn len(&self) -> usize {
+        critical_section::with(|cs| self.inner.borrow_ref(cs).len)
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Removes all descriptors. Statistics are kept.
+    pub fn clear(&self) {
+        critical_section::with(|cs| self.inner.borrow_ref_mut(cs).clear())
+    }
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+    pub fn stats(&self) -> QueueStats {
+        critical_section::with(|cs| self.inner.borrow_ref(cs).stats)
+    }
+    pub fn reset_stats(&self) {
+        critical_section::with(|cs| self.inner.borrow_ref_mut(cs).stats = QueueStats::new())
+    }
+}
+
+impl<const N: usize> Default for DescriptorQueue<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// # PriorityDescriptorQueue
+/// Like [`DescriptorQueue`] but with `L` priority levels of `N` descriptors
+/// each. Level 0 is the most urgent; [`dequeue`](Self::dequeue) always
+/// drains lower levels first and keeps FIFO order within a level, so e.g.
+/// ClearIrqStatus or SetStandby can overtake bulk WriteBuffer transfers.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{ClearIrqStatus, WriteBuffer};
+/// use sx126x_spi_buffers::queue::PriorityDescriptorQueue;
+///
+/// const URGENT: usize = 0;
+/// const BULK: usize = 1;
+/// static QUEUE: PriorityDescriptorQueue<2, 4> = PriorityDescriptorQueue::new();
+///
+/// let mut write_buffer: WriteBuffer<7> = WriteBuffer::from_slice(0, &[1, 2, 3, 4, 5]);
+/// let mut clear_irq_status = ClearIrqStatus::all();
+/// QUEUE.enqueue(write_buffer.descriptor(), BULK).unwrap();
+/// QUEUE.enqueue(clear_irq_status.descriptor(), URGENT).unwrap();
+/// assert_eq!(QUEUE.dequeue(), Some(clear_irq_status.descriptor()));
+/// assert_eq!(QUEUE.dequeue(), Some(write_buffer.descriptor()));
+/// ```
+pub struct PriorityDescriptorQueue<const L: usize, const N: usize> {
+    inner: Mutex<RefCell<[Ring<N>; L]>>,
+}
+impl<const L: usize, const N: usize> PriorityDescriptorQueue<L, N> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            inner: Mutex::new(RefCell::new([const { Ring::new() }; L])),
+        }
+    }
+    /// Appends a descriptor at `level`, handing it back if that level is
+    /// full or `level >= L`. Only the former counts as dropped.
+    pub fn enqueue(&self, desc: SpiDescriptor, level: usize) -> Result<(), SpiDescriptor> {
+        critical_section::with(|cs| match self.inner.borrow_ref_mut(cs).get_mut(level) {
+            Some(ring) => ring.push(desc),
+            None => Err(desc),
+        })
+    }
+    /// Removes the oldest descriptor of the most urgent non-empty level.
+    pub fn dequeue(&self) -> Option<SpiDescriptor> {
+        critical_section::with(|cs| {
+            self.inner
+                .borrow_ref_mut(cs)
+                .iter_mut()
+                .find_map(|ring| ring.pop())
+        })
+    }
+    pub fn len(&self) -> usize {
+        critical_section::with(|cs| self.inner.borrow_ref(cs).iter().map(|ring| ring.len).sum())
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Removes all descriptors. Statistics are kept.
+    pub fn clear(&self) {
+        critical_section::with(|cs| {
+            self.inner
+                .borrow_ref_mut(cs)
+                .iter_mut()
+                .for_each(|ring| ring.clear())
+        })
+    }
+    /// Statistics of a single level, or `None` if `level >= L`.
+    pub fn stats(&self, level: usize) -> Option<QueueStats> {
+        critical_section::with(|cs| self.inner.borrow_ref(cs).get(level).map(|ring| ring.stats))
+    }
+    pub fn reset_stats(&self) {
+        critical_section::with(|cs| {
+            self.inner
+                .borrow_ref_mut(cs)
+                .iter_mut()
+                .for_each(|ring| ring.stats = QueueStats::new())
+        })
+    }
+}
+
+impl<const L: usize, const N: usize> Default for PriorityDescriptorQueue<L, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Usage counters of a queue, for sizing it in firmware.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QueueStats {
+    /// Largest number of descriptors queued at once.
+    pub high_water_mark: usize,
+    /// Number of descriptors rejected because the queue was full.
+    pub dropped: u32,
+}
+impl QueueStats {
+    #[inline(always)]
+    const fn new() -> Self {
+        Self {
+            high_water_mark: 0,
+            dropped: 0,
+        }
+    }
+}
+
+struct Ring<const N: usize> {
+    buf: [Option<SpiDescriptor>; N],
+    head: usize,
+    len: usize,
+    stats: QueueStats,
+}
+impl<const N: usize> Ring<N> {
+    const fn new() -> Self {
+        Self {
+            buf: [None; N],
+            head: 0,
+            len: 0,
+            stats: QueueStats::new(),
+        }
+    }
+    fn push(&mut self, desc: SpiDescriptor) -> Result<(), SpiDescriptor> {
+        if self.len == N {
+            self.stats.dropped = self.stats.dropped.saturating_add(1);
+            return Err(desc);
+        }
+        self.buf[(self.head + self.len) % N] = Some(desc);
+        self.len += 1;
+        self.stats.high_water_mark = self.stats.high_water_mark.max(self.len);
+        Ok(())
+    }
+    fn clear(&mut self) {
+        self.buf = [None; N];
+        self.head = 0;
+        self.len = 0;
+    }
+    fn pop(&mut self) -> Option<SpiDescriptor> {
+        if self.len == 0 {
+            return None;
+        }
+        let desc = self.buf[self.head].take();
+        self.head = (self.head + 1) % N;
+        self.len -= 1;
+        desc
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{SetStandby, SetTx, StdbyConfig};
+
+    #[test]
+    fn test_descriptor_queue_wraps() {
+        let queue: DescriptorQueue<2> = DescriptorQueue::new();
+        let mut set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+        let mut set_tx = SetTx::new(0);
+        assert_eq!(queue.enqueue(set_standby.descriptor()), Ok(()));
+        assert_eq!(queue.enqueue(set_tx.descriptor()), Ok(()));
+        assert_eq!(
+            queue.enqueue(set_standby.descriptor()),
+            Err(set_standby.descriptor())
+        );
+        assert_eq!(queue.dequeue(), Some(set_standby.descriptor()));
+        assert_eq!(queue.enqueue(set_standby.descriptor()), Ok(()));
+        assert_eq!(queue.dequeue(), Some(set_tx.descriptor()));
+        assert_eq!(queue.dequeue(), Some(set_standby.descriptor()));
+        assert_eq!(queue.dequeue(), None);
+        assert_eq!(
+            queue.stats(),
+            QueueStats {
+                high_water_mark: 2,
+                dropped: 1
+            }
+        );
+        queue.reset_stats();
+        assert_eq!(queue.stats(), QueueStats::default());
+    }
+
+    #[test]
+    fn test_priority_queue_levels() {
+        let queue: PriorityDescriptorQueue<3, 1> = PriorityDescriptorQueue::new();
+        let mut set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+        let mut set_tx = SetTx::new(0);
+        assert_eq!(queue.enqueue(set_tx.descriptor(), 2), Ok(()));
+        assert_eq!(
+            queue.enqueue(set_tx.descriptor(), 2),
+            Err(set_tx.descriptor())
+        );
+        assert_eq!(
+            queue.enqueue(set_tx.descriptor(), 3),
+            Err(set_tx.descriptor())
+        );
+        assert_eq!(queue.enqueue(set_standby.descriptor(), 1), Ok(()));
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.dequeue(), Some(set_standby.descriptor()));
+        assert_eq!(queue.dequeue(), Some(set_tx.descriptor()));
+        assert!(queue.is_empty());
+        assert_eq!(queue.stats(2).map(|stats| stats.dropped), Some(1));
+        assert_eq!(queue.stats(3), None);
+    }
+}
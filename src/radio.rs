@@ -0,0 +1,882 @@
+//! Typestate driver on top of the [`BlockingExecutor`].
+//!
+//! The chip mode is part of the [`Radio`] type, so only the transitions
+//! and commands the SX126x accepts in that mode are available. Configuration
+//! commands, for instance, can only be sent in STDBY_RC or STDBY_XOSC:
+//!
+//! ```compile_fail
+//! # use embedded_hal_mock::eh1::{delay::NoopDelay, digital::Mock as PinMock, spi::Mock as SpiMock};
+//! # use sx126x_spi_buffers::{blocking::BlockingExecutor, radio::Radio};
+//! use sx126x_spi_buffers::commands::{SetModulationParamsLora, Sf, Bw, Cr};
+//!
+//! # let executor = BlockingExecutor::new(SpiMock::new(&[]), PinMock::new(&[]), NoopDelay::new());
+//! let radio = Radio::new(executor).tx(0).ok().unwrap();
+//! let mut modulation = SetModulationParamsLora::new(Sf::Sf7, Bw::Bw125, Cr::Cr4_5, false);
+//! radio.configure(&mut modulation); // no `configure` in TX
+//! ```
+
+use super::blocking::{self, BlockingExecutor};
+use super::commands::{
+    CadExitMode, CadSymbolNum, ClearIrqStatus, Command, GetIrqStatus, GetRssiInst,
+    GetRxBufferStatus, GetStatus, Irq, SetBufferBaseAddress, SetCad, SetCadParams, SetDioIrqParams,
+    SetFs, SetPacketParams, SetRfFrequency, SetRx, SetSleep, SetStandby, SetTx, SleepConfig,
+    StdbyConfig, WriteBufferHeader, ticks_from_ms,
+};
+use super::rf_switch::{Dio2, RfPath, RfSwitch};
+use core::marker::PhantomData;
+use embedded_hal::delay::DelayNs;
+use embedded_hal::digital::InputPin;
+use embedded_hal::spi::SpiDevice;
+
+/// Chip modes used as the state parameter of [`Radio`].
+pub mod state {
+    pub struct Sleep;
+    pub struct StandbyRc;
+    pub struct StandbyXosc;
+    pub struct Fs;
+    pub struct Tx;
+    pub struct Rx;
+    pub struct Cad;
+}
+use state::*;
+
+mod sealed {
+    pub trait Sealed {}
+}
+/// A chip mode.
+pub trait State: sealed::Sealed {}
+/// Modes in which the chip is awake and answers status commands.
+pub trait Awake: State {}
+/// Modes from which TX, RX and CAD can be started.
+pub trait Idle: Awake {}
+/// Modes in which configuration commands are accepted.
+pub trait Standby: Idle {}
+
+macro_rules! impl_state {
+    ($($state:ty: $($marker:ident),*;)*) => {
+        $(
+            impl sealed::Sealed for $state {}
+            impl State for $state {}
+            $(impl $marker for $state {})*
+        )*
+    };
+}
+impl_state! {
+    Sleep: ;
+    StandbyRc: Awake, Idle, Standby;
+    StandbyXosc: Awake, Idle, Standby;
+    Fs: Awake, Idle;
+    Tx: Awake;
+    Rx: Awake;
+    Cad: Awake;
+}
+
+type RadioError<SPI, BUSY> = blocking::Error<
+    <SPI as embedded_hal::spi::ErrorType>::Error,
+    <BUSY as embedded_hal::digital::ErrorType>::Error,
+>;
+/// Result of a mode transition; on failure the radio is handed back in its
+/// previous mode.
+pub type Transition<SPI, BUSY, D, S, T, SW = Dio2> =
+    Result<Radio<SPI, BUSY, D, T, SW>, (Radio<SPI, BUSY, D, S, SW>, RadioError<SPI, BUSY>)>;
+
+/// Errors of [`Radio::transmit`] and [`Radio::receive`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error<SpiE, PinE> {
+    Executor(blocking::Error<SpiE, PinE>),
+    /// The Timeout IRQ was raised.
+    Timeout,
+    /// A packet was received with a wrong CRC.
+    CrcErr,
+    /// A LoRa header was received with a wrong CRC.
+    HeaderErr,
+    /// Driving the RF switch failed.
+    RfSwitch(PinE),
+    /// The payload is longer than 255 bytes.
+    PayloadTooLong,
+    /// [`Radio::transmit`] was called before
+    /// [`Radio::set_packet_params`], so the payload length is unknown.
+    NoPacketParams,
+}
+impl<SpiE, PinE> From<blocking::Error<SpiE, PinE>> for Error<SpiE, PinE> {
+    fn from(error: blocking::Error<SpiE, PinE>) -> Self {
+        Self::Executor(error)
+    }
+}
+
+/// TX or RX timeout of [`Radio::transmit`] and [`Radio::receive`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Timeout {
+    /// Wait until the packet has been sent or received.
+    Disabled,
+    /// Steps of 15.625 µs, at most
+    /// [`MAX_TIMEOUT_TICKS`](crate::commands::MAX_TIMEOUT_TICKS).
+    Ticks(u32),
+}
+impl Timeout {
+    /// Timeout parameter of SetTx and SetRx.
+    #[inline(always)]
+    pub const fn ticks(&self) -> u32 {
+        match self {
+            Self::Disabled => 0,
+            Self::Ticks(ticks) => *ticks,
+        }
+    }
+    /// Timeout of `ms` milliseconds, `None` if too long, see
+    /// [`ticks_from_ms`].
+    #[inline(always)]
+    pub const fn from_ms(ms: u32) -> Option<Self> {
+        match ticks_from_ms(ms) {
+            Some(ticks) => Some(Self::Ticks(ticks)),
+            None => None,
+        }
+    }
+}
+
+/// Outcome of [`Radio::listen_before_talk`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Channel {
+    /// No LoRa activity was detected.
+    Clear,
+    /// CadDetected was raised.
+    Busy,
+}
+
+/// RSSI measured on one channel by [`Radio::survey`], in tenths of dBm.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ChannelRssi {
+    pub frequency_hz: u32,
+    /// Mean of the samples.
+    pub average_deci_db: i16,
+    /// Strongest sample.
+    pub peak_deci_db: i16,
+}
+
+/// # Radio
+/// An SX126x in mode `S`. Mode changes consume the radio and return it in
+/// the new mode.
+///
+/// ## Example
+/// ```
+/// use embedded_hal_mock::eh1::delay::NoopDelay;
+/// use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+/// use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+/// use sx126x_spi_buffers::blocking::BlockingExecutor;
+/// use sx126x_spi_buffers::commands::{SetRfFrequency, Irq};
+/// use sx126x_spi_buffers::radio::Radio;
+///
+/// let spi = SpiMock::new(&[
+///     SpiTransaction::transaction_start(),
+///     SpiTransaction::transfer(vec![0x86, 0x36, 0x40, 0, 0], vec![0; 5]),
+///     SpiTransaction::transaction_end(),
+///     SpiTransaction::transaction_start(),
+///     SpiTransaction::transfer(vec![0x83, 0, 0, 0], vec![0; 4]),
+///     SpiTransaction::transaction_end(),
+///     SpiTransaction::transaction_start(),
+///     SpiTransaction::transfer(vec![0x12, 0, 0, 0], vec![0, 0, 0, 0x01]),
+///     SpiTransaction::transaction_end(),
+/// ]);
+/// let busy = PinMock::new(&vec![PinTransaction::get(State::Low); 3]);
+/// let executor = BlockingExecutor::new(spi, busy, NoopDelay::new());
+///
+/// let mut radio = Radio::new(executor);
+/// radio.configure(&mut SetRfFrequency::new(0x3640_0000)).unwrap();
+/// let mut radio = radio.tx(0).map_err(|(_, e)| e).unwrap();
+/// assert_eq!(radio.irq_status().unwrap(), Irq::new().with_tx_done(true));
+/// let radio = radio.done();
+///
+/// let (mut spi, mut busy, _) = radio.release().release();
+/// spi.done();
+/// busy.done();
+/// ```
+pub struct Radio<SPI, BUSY, D, S, SW = Dio2> {
+    executor: BlockingExecutor<SPI, BUSY, D>,
+    packet_params: Option<SetPacketParams>,
+    rf_switch: SW,
+    state: PhantomData<S>,
+}
+
+impl<SPI, BUSY, D> Radio<SPI, BUSY, D, StandbyRc>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin,
+    D: DelayNs,
+{
+    /// Wraps a chip that has just been reset, which puts it in STDBY_RC.
+    pub fn new(executor: BlockingExecutor<SPI, BUSY, D>) -> Self {
+        Self {
+            executor,
+            packet_params: None,
+            rf_switch: Dio2,
+            state: PhantomData,
+        }
+    }
+}
+
+impl<SPI, BUSY, D, S: State, SW> Radio<SPI, BUSY, D, S, SW>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin,
+    D: DelayNs,
+{
+    pub fn release(self) -> BlockingExecutor<SPI, BUSY, D> {
+        self.executor
+    }
+    /// Releases the executor and the RF switch.
+    pub fn into_parts(self) -> (BlockingExecutor<SPI, BUSY, D>, SW) {
+        (self.executor, self.rf_switch)
+    }
+    /// Replaces the RF switch, [`Dio2`] by default, e.g. with a
+    /// [`GpioSwitch`](crate::rf_switch::GpioSwitch).
+    pub fn with_rf_switch<T>(self, rf_switch: T) -> Radio<SPI, BUSY, D, S, T> {
+        Radio {
+            executor: self.executor,
+            packet_params: self.packet_params,
+            rf_switch,
+            state: PhantomData,
+        }
+    }
+    /// The RF switch, e.g. to set it around a manual [`tx`](Radio::tx).
+    pub fn rf_switch_mut(&mut self) -> &mut SW {
+        &mut self.rf_switch
+    }
+    /// Blocks for `us` microseconds, e.g. to back off before a retry.
+    pub fn delay_us(&mut self, us: u32) {
+        self.executor.delay_us(us);
+    }
+
+    fn into_state<T: State>(self) -> Radio<SPI, BUSY, D, T, SW> {
+        Radio {
+            executor: self.executor,
+            packet_params: self.packet_params,
+            rf_switch: self.rf_switch,
+            state: PhantomData,
+        }
+    }
+    fn transition<C: Command, T: State>(
+        mut self,
+        mut command: C,
+    ) -> Transition<SPI, BUSY, D, S, T, SW> {
+        match self.executor.run(&mut command) {
+            Ok(()) => Ok(self.into_state()),
+            Err(error) => Err((self, error)),
+        }
+    }
+}
+
+impl<SPI, BUSY, D, SW> Radio<SPI, BUSY, D, Sleep, SW>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin,
+    D: DelayNs,
+{
+    /// Wakes the chip with a SetStandby(STDBY_RC).
+    pub fn wake(self) -> Transition<SPI, BUSY, D, Sleep, StandbyRc, SW> {
+        self.transition(SetStandby::new(StdbyConfig::StdbyRc))
+    }
+}
+
+impl<SPI, BUSY, D, S: Awake, SW> Radio<SPI, BUSY, D, S, SW>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin,
+    D: DelayNs,
+{
+    pub fn status(&mut self) -> Result<GetStatus, RadioError<SPI, BUSY>> {
+        let mut get_status = GetStatus::new();
+        self.executor.run(&mut get_status)?;
+        Ok(get_status)
+    }
+    pub fn irq_status(&mut self) -> Result<Irq, RadioError<SPI, BUSY>> {
+        let mut get_irq_status = GetIrqStatus::new();
+        self.executor.run(&mut get_irq_status)?;
+        Ok(get_irq_status.irq_status())
+    }
+    pub fn clear_irq_status(&mut self, irq: Irq) -> Result<(), RadioError<SPI, BUSY>> {
+        self.executor.run(&mut ClearIrqStatus::new(irq))
+    }
+    /// Sends SetStandby(STDBY_RC), aborting any ongoing operation.
+    pub fn standby_rc(self) -> Transition<SPI, BUSY, D, S, StandbyRc, SW> {
+        self.transition(SetStandby::new(StdbyConfig::StdbyRc))
+    }
+    /// Sends SetStandby(STDBY_XOSC), aborting any ongoing operation.
+    pub fn standby_xosc(self) -> Transition<SPI, BUSY, D, S, StandbyXosc, SW> {
+        self.transition(SetStandby::new(StdbyConfig::StdbyXosc))
+    }
+}
+
+impl<SPI, BUSY, D, S: Idle, SW> Radio<SPI, BUSY, D, S, SW>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin,
+    D: DelayNs,
+{
+    /// Sends SetFs.
+    pub fn fs(self) -> Transition<SPI, BUSY, D, S, Fs, SW> {
+        self.transition(SetFs::new())
+    }
+    /// Sends SetTx; `timeout` is in steps of 15.625 µs, 0 disables it.
+    pub fn tx(self, timeout: u32) -> Transition<SPI, BUSY, D, S, Tx, SW> {
+        self.transition(SetTx::new(timeout))
+    }
+    /// Sends SetRx; `timeout` is in steps of 15.625 µs, 0 for single mode
+    /// without timeout and 0xFFFFFF for continuous mode.
+    pub fn rx(self, timeout: u32) -> Transition<SPI, BUSY, D, S, Rx, SW> {
+        self.transition(SetRx::new(timeout))
+    }
+    /// Sends SetCad.
+    pub fn cad(self) -> Transition<SPI, BUSY, D, S, Cad, SW> {
+        self.transition(SetCad::new())
+    }
+}
+
+impl<SPI, BUSY, D, S: Standby, SW> Radio<SPI, BUSY, D, S, SW>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin,
+    D: DelayNs,
+{
+    /// Sends a configuration command, e.g. SetPacketType or
+    /// SetModulationParamsLora.
+    pub fn configure<C: Command>(&mut self, command: &mut C) -> Result<(), RadioError<SPI, BUSY>> {
+        self.executor.run(command)
+    }
+    /// Sends SetPacketParams and keeps a copy, so [`transmit`](Radio::transmit)
+    /// can update the payload length.
+    pub fn set_packet_params(
+        &mut self,
+        mut packet_params: SetPacketParams,
+    ) -> Result<(), RadioError<SPI, BUSY>> {
+        self.executor.run(&mut packet_params)?;
+        self.packet_params = Some(packet_params);
+        Ok(())
+    }
+    /// Sends SetSleep.
+    pub fn sleep(self, sleep_config: SleepConfig) -> Transition<SPI, BUSY, D, S, Sleep, SW> {
+        self.transition(SetSleep::new(sleep_config))
+    }
+}
+
+/// IRQs polled by [`Radio::transmit`] and [`Radio::receive`].
+const POLLED_IRQS: Irq = Irq::new()
+    .with_tx_done(true)
+    .with_rx_done(true)
+    .with_header_err(true)
+    .with_crc_err(true)
+    .with_timeout(true);
+/// IRQs polled by [`Radio::listen_before_talk`].
+const CAD_IRQS: Irq = Irq::new().with_cad_done(true).with_cad_detected(true);
+/// Interval between two reads of the IRQ status.
+const POLL_INTERVAL_US: u32 = 100;
+
+impl<SPI, BUSY, D, SW> Radio<SPI, BUSY, D, StandbyRc, SW>
+where
+    SPI: SpiDevice,
+    BUSY: InputPin,
+    D: DelayNs,
+    SW: RfSwitch<BUSY::Error>,
+{
+    /// Sends `data` and waits for TxDone. Runs WriteBuffer, SetDioIrqParams,
+    /// ClearIrqStatus and SetTx, then polls GetIrqStatus; the payload length
+    /// of the packet params set with
+    /// [`set_packet_params`](Self::set_packet_params) is updated first, so
+    /// they must have been set. The TX and RX buffers both start at address
+    /// 0. The RF switch is set to TX from SetTx until TxDone.
+    ///
+    /// ## Example
+    /// ```
+    /// use embedded_hal_mock::eh1::delay::NoopDelay;
+    /// use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    /// use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+    /// use sx126x_spi_buffers::blocking::BlockingExecutor;
+    /// use sx126x_spi_buffers::commands::{CrcType, HeaderType, InvertIq, SetPacketParams};
+    /// use sx126x_spi_buffers::radio::{Error, Radio, Timeout};
+    ///
+    /// let spi = SpiMock::new(&[
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x8C, 0, 8, 0, 4, 1, 0], vec![0; 7]),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x8F, 0, 0], vec![0; 3]),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x0E, 0], vec![0; 2]),
+    ///     SpiTransaction::write_vec(b"ping".to_vec()),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x08, 0x02, 0x63, 0x02, 0x63, 0, 0, 0, 0], vec![0; 9]),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x02, 0x43, 0xFF], vec![0; 3]),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x83, 0, 0, 0], vec![0; 4]),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x12, 0, 0, 0], vec![0, 0, 0, 0x01]),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x02, 0x43, 0xFF], vec![0; 3]),
+    ///     SpiTransaction::transaction_end(),
+    /// ]);
+    /// let busy = PinMock::new(&vec![PinTransaction::get(State::Low); 8]);
+    /// let mut radio = Radio::new(BlockingExecutor::new(spi, busy, NoopDelay::new()));
+    /// assert_eq!(radio.transmit(b"ping", Timeout::Disabled), Err(Error::NoPacketParams));
+    ///
+    /// let packet_params = SetPacketParams::new(8, HeaderType::VariableLength, 4, CrcType::On, InvertIq::Standard);
+    /// radio.set_packet_params(packet_params).unwrap();
+    /// assert_eq!(radio.transmit(&[0; 256], Timeout::Disabled), Err(Error::PayloadTooLong));
+    /// radio.transmit(b"ping", Timeout::Disabled).unwrap();
+    ///
+    /// let (mut spi, mut busy, _) = radio.release().release();
+    /// spi.done();
+    /// busy.done();
+    /// ```
+    pub fn transmit(
+        &mut self,
+        data: &[u8],
+        timeout: Timeout,
+    ) -> Result<(), Error<SPI::Error, BUSY::Error>> {
+        let packet_params = self.packet_params.as_ref().ok_or(Error::NoPacketParams)?;
+        let payload_length = u8::try_from(data.len()).map_err(|_| Error::PayloadTooLong)?;
+        if packet_params.payload_length() != payload_length {
+            // Only cached once the chip has them, so a failed transfer is
+            // retried by the next call.
+            let mut packet_params = packet_params.clone();
+            packet_params.set_payload_length(payload_length);
+            self.executor.run(&mut packet_params)?;
+            self.packet_params = Some(packet_params);
+        }
+        self.executor.run(&mut SetBufferBaseAddress::new(0, 0))?;
+        let mut header = WriteBufferHeader::new(0);
+        let mut desc = header.scatter_descriptor(data);
+        // SAFETY: `header` and `data` are borrowed for the whole transfer.
+        unsafe { self.executor.execute(&mut desc)? };
+        self.switched(RfPath::Tx, |radio| {
+            radio.start(POLLED_IRQS, SetTx::new(timeout.ticks()))?;
+            radio.wait_irq().map(|_| ())
+        })
+    }
+    /// Receives a packet into `buf` and returns its length. Runs
+    /// SetDioIrqParams, ClearIrqStatus and SetRx, polls GetIrqStatus, then
+    /// reads the packet with GetRxBufferStatus and ReadBuffer. Packets longer
+    /// than `buf` are truncated. The RF switch is set to RX until the end of
+    /// the reception.
+    pub fn receive(
+        &mut self,
+        buf: &mut [u8],
+        timeout: Timeout,
+    ) -> Result<usize, Error<SPI::Error, BUSY::Error>> {
+        self.executor.run(&mut SetBufferBaseAddress::new(0, 0))?;
+        self.switched(RfPath::Rx, |radio| {
+            radio.start(POLLED_IRQS, SetRx::new(timeout.ticks()))?;
+            radio.wait_irq()
+        })?;
+        let mut get_rx_buffer_status = GetRxBufferStatus::new();
+        self.executor.run(&mut get_rx_buffer_status)?;
+        let length = buf
+            .len()
+            .min(get_rx_buffer_status.payload_length_rx() as usize);
+        self.executor.read_buffer(
+            get_rx_buffer_status.rx_start_buffer_pointer(),
+            &mut buf[..length],
+        )?;
+        Ok(length)
+    }
+
+    /// Performs CAD with the given search length and detection thresholds
+    /// and reports whether the channel is in use, e.g. to gate a
+    /// [`transmit`](Self::transmit). Runs SetCadParams, SetDioIrqParams,
+    /// ClearIrqStatus and SetCad, then polls GetIrqStatus until CadDone, with
+    /// the RF switch set to RX.
+    ///
+    /// ## Example
+    /// ```
+    /// use embedded_hal_mock::eh1::delay::NoopDelay;
+    /// use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    /// use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+    /// use sx126x_spi_buffers::blocking::BlockingExecutor;
+    /// use sx126x_spi_buffers::commands::CadSymbolNum;
+    /// use sx126x_spi_buffers::radio::{Channel, Radio};
+    ///
+    /// let spi = SpiMock::new(&[
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x88, 0x02, 22, 10, 0, 0, 0, 0], vec![0; 8]),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x08, 0x01, 0x80, 0x01, 0x80, 0, 0, 0, 0], vec![0; 9]),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x02, 0x43, 0xFF], vec![0; 3]),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0xC5], vec![0]),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x12, 0, 0, 0], vec![0, 0, 0x01, 0x80]),
+    ///     SpiTransaction::transaction_end(),
+    ///     SpiTransaction::transaction_start(),
+    ///     SpiTransaction::transfer(vec![0x02, 0x43, 0xFF], vec![0; 3]),
+    ///     SpiTransaction::transaction_end(),
+    /// ]);
+    /// let busy = PinMock::new(&vec![PinTransaction::get(State::Low); 6]);
+    /// let mut radio = Radio::new(BlockingExecutor::new(spi, busy, NoopDelay::new()));
+    /// assert_eq!(radio.listen_before_talk(CadSymbolNum::Symbols4, 22, 10), Ok(Channel::Busy));
+    ///
+    /// let (mut spi, mut busy, _) = radio.release().release();
+    /// spi.done();
+    /// busy.done();
+    /// ```
+    pub fn listen_before_talk(
+        &mut self,
+        cad_symbol_num: CadSymbolNum,
+        cad_det_peak: u8,
+        cad_det_min: u8,
+    ) -> Result<Channel, Error<SPI::Error, BUSY::Error>> {
+        self.executor.run(&mut SetCadParams::new(
+            cad_symbol_num,
+            cad_det_peak,
+            cad_det_min,
+            CadExitMode::CadOnly,
+            0,
+        ))?;
+        let irq = self.switched(RfPath::Rx, |radio| {
+            radio.start(CAD_IRQS, SetCad::new())?;
+            loop {
+                let mut get_irq_status = GetIrqStatus::new();
+                radio.executor.run(&mut get_irq_status)?;
+                let irq = get_irq_status.irq_status();
+                if irq.cad_done() {
+                    return Ok(irq);
+                }
+                radio.executor.delay_us(POLL_INTERVAL_US);
+            }
+        })?;
+        self.executor.run(&mut ClearIrqStatus::all())?;
+        Ok(match irq.cad_detected() {
+            true => Channel::Busy,
+            false => Channel::Clear,
+        })
+    }
+
+    /// Measures the RSSI of each of `frequencies_hz`, e.g. to pick the
+    /// quietest channel. For each channel, runs SetRfFrequency and SetRx in
+    /// continuous mode, samples GetRssiInst `samples` times every
+    /// `interval_us` (the first sample after `interval_us` of settling),
+    /// then returns to STDBY_RC with SetStandby. The RF switch is set to RX
+    /// for the whole survey.
+    ///
+    /// ## Example
+    /// ```
+    /// use embedded_hal_mock::eh1::delay::NoopDelay;
+    /// use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    /// use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+    /// use sx126x_spi_buffers::blocking::BlockingExecutor;
+    /// use sx126x_spi_buffers::radio::{ChannelRssi, Radio};
+    ///
+    /// let mut transactions = Vec::new();
+    /// for (frequency, rssi) in [([0x36, 0x41, 0x99, 0x9A], [220, 200]), ([0x36, 0x44, 0xCC, 0xCD], [180, 181])] {
+    ///     transactions.extend([
+    ///         SpiTransaction::transaction_start(),
+    ///         SpiTransaction::transfer([&[0x86][..], &frequency].concat(), vec![0; 5]),
+    ///         SpiTransaction::transaction_end(),
+    ///         SpiTransaction::transaction_start(),
+    ///         SpiTransaction::transfer(vec![0x82, 0xFF, 0xFF, 0xFF], vec![0; 4]),
+    ///         SpiTransaction::transaction_end(),
+    ///     ]);
+    ///     for rssi in rssi {
+    ///         transactions.extend([
+    ///             SpiTransaction::transaction_start(),
+    ///             SpiTransaction::transfer(vec![0x15, 0, 0], vec![0, 0, rssi]),
+    ///             SpiTransaction::transaction_end(),
+    ///         ]);
+    ///     }
+    ///     transactions.extend([
+    ///         SpiTransaction::transaction_start(),
+    ///         SpiTransaction::transfer(vec![0x80, 0], vec![0; 2]),
+    ///         SpiTransaction::transaction_end(),
+    ///     ]);
+    /// }
+    /// let spi = SpiMock::new(&transactions);
+    /// let busy = PinMock::new(&vec![PinTransaction::get(State::Low); 10]);
+    /// let mut radio = Radio::new(BlockingExecutor::new(spi, busy, NoopDelay::new()));
+    ///
+    /// let report = radio.survey(&[868_100_000, 868_300_000], 2, 1_000).unwrap();
+    /// assert_eq!(report[0], ChannelRssi { frequency_hz: 868_100_000, average_deci_db: -1050, peak_deci_db: -1000 });
+    /// assert_eq!(report[1], ChannelRssi { frequency_hz: 868_300_000, average_deci_db: -902, peak_deci_db: -900 });
+    /// let quietest = report.iter().min_by_key(|channel| channel.average_deci_db).unwrap();
+    /// assert_eq!(quietest.frequency_hz, 868_100_000);
+    ///
+    /// let (mut spi, mut busy, _) = radio.release().release();
+    /// spi.done();
+    /// busy.done();
+    /// ```
+    pub fn survey<const N: usize>(
+        &mut self,
+        frequencies_hz: &[u32; N],
+        samples: u8,
+        interval_us: u32,
+    ) -> Result<[ChannelRssi; N], Error<SPI::Error, BUSY::Error>> {
+        let samples = samples.max(1);
+        let mut report = [ChannelRssi::default(); N];
+        self.switched(RfPath::Rx, |radio| {
+            for (channel, &frequency_hz) in report.iter_mut().zip(frequencies_hz) {
+                radio
+                    .executor
+                    .run(&mut SetRfFrequency::from_hz(frequency_hz))?;
+                radio.executor.run(&mut SetRx::new(0xFFFFFF))?;
+                let mut sum = 0i32;
+                let mut peak = i16::MIN;
+                for _ in 0..samples {
+                    radio.executor.delay_us(interval_us);
+                    let mut get_rssi_inst = GetRssiInst::new();
+                    radio.executor.run(&mut get_rssi_inst)?;
+                    let rssi = get_rssi_inst.rssi_inst_deci_db();
+                    sum += rssi as i32;
+                    peak = peak.max(rssi);
+                }
+                radio
+                    .executor
+                    .run(&mut SetStandby::new(StdbyConfig::StdbyRc))?;
+                *channel = ChannelRssi {
+                    frequency_hz,
+                    average_deci_db: (sum / samples as i32) as i16,
+                    peak_deci_db: peak,
+                };
+            }
+            Ok(())
+        })?;
+        Ok(report)
+    }
+
+    /// Runs `operation` with the RF switch set to `path`, then switches it
+    /// off.
+    fn switched<T>(
+        &mut self,
+        path: RfPath,
+        operation: impl FnOnce(&mut Self) -> Result<T, Error<SPI::Error, BUSY::Error>>,
+    ) -> Result<T, Error<SPI::Error, BUSY::Error>> {
+        self.rf_switch.set(path).map_err(Error::RfSwitch)?;
+        self.executor.delay_us(self.rf_switch.pre_delay_us());
+        let result = operation(self);
+        self.executor.delay_us(self.rf_switch.post_delay_us());
+        let off = self.rf_switch.set(RfPath::Off).map_err(Error::RfSwitch);
+        let value = result?;
+        off.map(|_| value)
+    }
+    fn start<C: Command>(
+        &mut self,
+        irqs: Irq,
+        mut command: C,
+    ) -> Result<(), RadioError<SPI, BUSY>> {
+        self.executor.run(&mut SetDioIrqParams::new(
+            irqs,
+            irqs,
+            Irq::new(),
+            Irq::new(),
+        ))?;
+        self.executor.run(&mut ClearIrqStatus::all())?;
+        self.executor.run(&mut command)
+    }
+    /// Polls until TX or RX has finished, then clears the IRQs.
+    fn wait_irq(&mut self) -> Result<Irq, Error<SPI::Error, BUSY::Error>> {
+        let irq = loop {
+            let mut get_irq_status = GetIrqStatus::new();
+            self.executor.run(&mut get_irq_status)?;
+            let irq = get_irq_status.irq_status();
+            if irq.tx_done() || irq.rx_done() || irq.header_err() || irq.timeout() {
+                break irq;
+            }
+            self.executor.delay_us(POLL_INTERVAL_US);
+        };
+        self.executor.run(&mut ClearIrqStatus::all())?;
+        if irq.timeout() {
+            Err(Error::Timeout)
+        } else if irq.header_err() {
+            Err(Error::HeaderErr)
+        } else if irq.crc_err() {
+            Err(Error::CrcErr)
+        } else {
+            Ok(irq)
+        }
+    }
+}
+
+macro_rules! impl_done {
+    ($($state:ty: $irq:literal;)*) => {
+        $(
+            impl<SPI, BUSY, D, SW> Radio<SPI, BUSY, D, $state, SW>
+            where
+                SPI: SpiDevice,
+                BUSY: InputPin,
+                D: DelayNs,
+            {
+                #[doc = concat!("Returns to STDBY_RC without a command, once ", $irq, " has been")]
+                /// raised and the chip has fallen back on its own.
+                pub fn done(self) -> Radio<SPI, BUSY, D, StandbyRc, SW> {
+                    self.into_state()
+                }
+            }
+        )*
+    };
+}
+impl_done! {
+    Tx: "TxDone or Timeout";
+    Rx: "RxDone or Timeout";
+    Cad: "CadDone";
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    extern crate std;
+
+    use super::*;
+    use embedded_hal_mock::eh1::delay::NoopDelay;
+    use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+    use embedded_hal_mock::eh1::spi::{Mock as SpiMock, Transaction as SpiTransaction};
+    use std::{vec, vec::Vec};
+
+    /// Expectations of one command transferred in its own NSS frame, also
+    /// used by the tests of the modules built on [`Radio`].
+    pub(crate) fn command(tx: Vec<u8>, rx: Vec<u8>) -> [SpiTransaction<u8>; 3] {
+        [
+            SpiTransaction::transaction_start(),
+            SpiTransaction::transfer(tx, rx),
+            SpiTransaction::transaction_end(),
+        ]
+    }
+    fn start_rx() -> Vec<SpiTransaction<u8>> {
+        [
+            command(vec![0x8F, 0, 0], vec![0; 3]),
+            command(vec![0x08, 0x02, 0x63, 0x02, 0x63, 0, 0, 0, 0], vec![0; 9]),
+            command(vec![0x02, 0x43, 0xFF], vec![0; 3]),
+            command(vec![0x82, 0, 0x10, 0], vec![0; 4]),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_receive() {
+        let mut expectations = start_rx();
+        expectations.extend(command(vec![0x12, 0, 0, 0], vec![0; 4]));
+        expectations.extend(command(vec![0x12, 0, 0, 0], vec![0, 0, 0, 0x02]));
+        expectations.extend(command(vec![0x02, 0x43, 0xFF], vec![0; 3]));
+        expectations.extend(command(vec![0x13, 0, 0, 0], vec![0, 0, 5, 0x80]));
+        expectations.extend([
+            SpiTransaction::transaction_start(),
+            SpiTransaction::write_vec(vec![0x1E, 0x80, 0]),
+            SpiTransaction::read_vec(vec![1, 2, 3]),
+            SpiTransaction::transaction_end(),
+        ]);
+        let mut spi = SpiMock::new(&expectations);
+        let mut busy = PinMock::new(&vec![PinTransaction::get(State::Low); 9]);
+        let mut radio = Radio::new(BlockingExecutor::new(
+            spi.clone(),
+            busy.clone(),
+            NoopDelay::new(),
+        ));
+
+        let mut buf = [0; 3];
+        assert_eq!(radio.receive(&mut buf, Timeout::Ticks(0x1000)), Ok(3));
+        assert_eq!(buf, [1, 2, 3]);
+        spi.done();
+        busy.done();
+    }
+
+    #[test]
+    fn test_receive_crc_err() {
+        let mut expectations = start_rx();
+        expectations.extend(command(vec![0x12, 0, 0, 0], vec![0, 0, 0, 0x42]));
+        expectations.extend(command(vec![0x02, 0x43, 0xFF], vec![0; 3]));
+        let mut spi = SpiMock::new(&expectations);
+        let mut busy = PinMock::new(&vec![PinTransaction::get(State::Low); 6]);
+        let mut radio = Radio::new(BlockingExecutor::new(
+            spi.clone(),
+            busy.clone(),
+            NoopDelay::new(),
+        ));
+
+        assert_eq!(
+            radio.receive(&mut [0; 8], Timeout::Ticks(0x1000)),
+            Err(Error::CrcErr)
+        );
+        spi.done();
+        busy.done();
+    }
+
+    #[test]
+    fn test_transmit_packet_params_error() {
+        use crate::commands::{CrcType, HeaderType, InvertIq};
+        use embedded_hal_mock::eh1::MockError;
+        use std::io::ErrorKind;
+
+        let busy_err =
+            || PinTransaction::get(State::Low).with_error(MockError::Io(ErrorKind::Other));
+        let mut expectations = command(vec![0x8C, 0, 8, 0, 4, 1, 0], vec![0; 7]).to_vec();
+        expectations.extend(command(vec![0x8C, 0, 8, 0, 3, 1, 0], vec![0; 7]));
+        let mut spi = SpiMock::new(&expectations);
+        let mut busy = PinMock::new(&[
+            PinTransaction::get(State::Low),
+            busy_err(),
+            PinTransaction::get(State::Low),
+            busy_err(),
+        ]);
+        let mut radio = Radio::new(BlockingExecutor::new(
+            spi.clone(),
+            busy.clone(),
+            NoopDelay::new(),
+        ));
+        radio
+            .set_packet_params(SetPacketParams::new(
+                8,
+                HeaderType::VariableLength,
+                4,
+                CrcType::On,
+                InvertIq::Standard,
+            ))
+            .unwrap();
+
+        // SetPacketParams is not sent, so the chip keeps a length of 4.
+        assert!(radio.transmit(&[1, 2, 3], Timeout::Disabled).is_err());
+        assert_eq!(radio.packet_params.as_ref().unwrap().payload_length(), 4);
+        // Retried, then SetBufferBaseAddress fails.
+        assert!(radio.transmit(&[1, 2, 3], Timeout::Disabled).is_err());
+        assert_eq!(radio.packet_params.as_ref().unwrap().payload_length(), 3);
+        spi.done();
+        busy.done();
+    }
+
+    #[test]
+    fn test_gpio_switch_off_after_error() {
+        use crate::rf_switch::GpioSwitch;
+
+        let mut expectations = start_rx();
+        expectations.extend(command(vec![0x12, 0, 0, 0], vec![0, 0, 0, 0x42]));
+        expectations.extend(command(vec![0x02, 0x43, 0xFF], vec![0; 3]));
+        let mut spi = SpiMock::new(&expectations);
+        let mut busy = PinMock::new(&vec![PinTransaction::get(State::Low); 6]);
+        let mut tx = PinMock::new(&[
+            PinTransaction::set(State::Low),
+            PinTransaction::set(State::Low),
+        ]);
+        let mut rx = PinMock::new(&[
+            PinTransaction::set(State::High),
+            PinTransaction::set(State::Low),
+        ]);
+        let mut radio = Radio::new(BlockingExecutor::new(
+            spi.clone(),
+            busy.clone(),
+            NoopDelay::new(),
+        ))
+        .with_rf_switch(GpioSwitch::new(tx.clone(), rx.clone()).with_delays(10, 10));
+
+        assert_eq!(
+            radio.receive(&mut [0; 8], Timeout::Ticks(0x1000)),
+            Err(Error::CrcErr)
+        );
+        spi.done();
+        busy.done();
+        tx.done();
+        rx.done();
+    }
+}
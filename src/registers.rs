@@ -1,34 +1,1108 @@
 //! Register definitions
 
-#[const_trait]
+use super::commands::{Bw, InvertIq, ReadRegisterBlock, chip_time_us};
+use bitfield_struct::bitfield;
+
+/// Addresses of the documented SX126x registers, for use with
+/// [`WriteRegisterBlock`](crate::commands::WriteRegisterBlock) and
+/// [`ReadRegisterBlock`](crate::commands::ReadRegisterBlock) where no typed
+/// register exists.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::addresses, commands::ReadRegisterBlock};
+///
+/// let read_event_mask: ReadRegisterBlock<5> = ReadRegisterBlock::new(addresses::EVENT_MASK);
+/// assert_eq!(read_event_mask.tx_buf, [0x1D, 0x09, 0x44, 0, 0]);
+/// ```
+pub mod addresses {
+    /// Start of the 16-byte silicon version string.
+    pub const VERSION_STRING: u16 = 0x0320;
+    pub const DIO_OUTPUT_ENABLE: u16 = 0x0580;
+    pub const DIO_INPUT_ENABLE: u16 = 0x0583;
+    pub const DIO_PULL_UP_CONTROL: u16 = 0x0584;
+    pub const DIO_PULL_DOWN_CONTROL: u16 = 0x0585;
+    pub const WHITENING_INITIAL_MSB: u16 = 0x06B8;
+    pub const WHITENING_INITIAL_LSB: u16 = 0x06B9;
+    pub const CRC_INITIAL_MSB: u16 = 0x06BC;
+    pub const CRC_INITIAL_LSB: u16 = 0x06BD;
+    pub const CRC_POLYNOMIAL_MSB: u16 = 0x06BE;
+    pub const CRC_POLYNOMIAL_LSB: u16 = 0x06BF;
+    pub const SYNC_WORD_0: u16 = 0x06C0;
+    pub const SYNC_WORD_1: u16 = 0x06C1;
+    pub const SYNC_WORD_2: u16 = 0x06C2;
+    pub const SYNC_WORD_3: u16 = 0x06C3;
+    pub const SYNC_WORD_4: u16 = 0x06C4;
+    pub const SYNC_WORD_5: u16 = 0x06C5;
+    pub const SYNC_WORD_6: u16 = 0x06C6;
+    pub const SYNC_WORD_7: u16 = 0x06C7;
+    pub const NODE_ADDRESS: u16 = 0x06CD;
+    pub const BROADCAST_ADDRESS: u16 = 0x06CE;
+    pub const IQ_POLARITY_SETUP: u16 = 0x0736;
+    /// Start of the 3-byte LoRa frequency error estimate.
+    pub const FREQ_ERROR: u16 = 0x076B;
+    pub const LORA_SYNC_WORD_MSB: u16 = 0x0740;
+    pub const LORA_SYNC_WORD_LSB: u16 = 0x0741;
+    pub const RANDOM_NUMBER_GEN_0: u16 = 0x0819;
+    pub const RANDOM_NUMBER_GEN_1: u16 = 0x081A;
+    pub const RANDOM_NUMBER_GEN_2: u16 = 0x081B;
+    pub const RANDOM_NUMBER_GEN_3: u16 = 0x081C;
+    pub const TX_MODULATION: u16 = 0x0889;
+    pub const RX_GAIN: u16 = 0x08AC;
+    pub const TX_CLAMP_CONFIG: u16 = 0x08D8;
+    pub const OCP_CONFIGURATION: u16 = 0x08E7;
+    /// Stops the RTC, used by the implicit header timeout workaround.
+    pub const RTC_CONTROL: u16 = 0x0902;
+    pub const XTA_TRIM: u16 = 0x0911;
+    pub const XTB_TRIM: u16 = 0x0912;
+    pub const DIO3_OUTPUT_VOLTAGE_CONTROL: u16 = 0x0920;
+    /// Clearing bit 1 completes the implicit header timeout workaround.
+    pub const EVENT_MASK: u16 = 0x0944;
+}
+
+#[cfg_attr(feature = "nightly", const_trait)]
 pub trait Register: Copy {
     const ADDRESS: u16;
     fn bits(&self) -> u8;
     fn from_bits(bits: u8) -> Self;
 }
 
+/// # Raw register
+/// A single register byte at an address only known at runtime, for
+/// experimenting with undocumented registers through
+/// [`WriteRegister::new_raw`](crate::commands::WriteRegister::new_raw) and
+/// [`ReadRegister::new_raw`](crate::commands::ReadRegister::new_raw).
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::RawRegister, commands::{ReadRegister, WriteRegister}};
+///
+/// let write_raw = WriteRegister::new_raw(RawRegister { address: 0x08E2, value: 0x02 });
+/// assert_eq!(write_raw.tx_buf, [0x0D, 0x08, 0xE2, 0x02]);
+///
+/// let mut read_raw: ReadRegister<RawRegister> = ReadRegister::new_raw(0x08E5);
+/// assert_eq!(read_raw.tx_buf, [0x1D, 0x08, 0xE5, 0, 0]);
+/// read_raw.rx_buf[4] = 0x80;
+/// assert_eq!(read_raw.raw_register(), RawRegister { address: 0x08E5, value: 0x80 });
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RawRegister {
+    pub address: u16,
+    pub value: u8,
+}
+
+/// A register pair at `ADDRESS` (MSB) and `ADDRESS + 1` (LSB) accessed as a
+/// single 16-bit value, see
+/// [`WriteRegister16`](crate::commands::WriteRegister16) and
+/// [`ReadRegister16`](crate::commands::ReadRegister16).
+#[cfg_attr(feature = "nightly", const_trait)]
+pub trait Register16: Copy {
+    const ADDRESS: u16;
+    fn bits(&self) -> u16;
+    fn from_bits(bits: u16) -> Self;
+}
+
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct LoraSyncWordMsb(pub u8);
 
-impl const Register for LoraSyncWordMsb {
-    const ADDRESS: u16 = 0x0740;
-    fn bits(&self) -> u8 {
-        self.0
-    }
-    fn from_bits(bits: u8) -> Self {
-        Self(bits)
+impl_register! {
+    Register for LoraSyncWordMsb {
+        const ADDRESS: u16 = addresses::LORA_SYNC_WORD_MSB;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
     }
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct LoraSyncWordLsb(pub u8);
 
-impl const Register for LoraSyncWordLsb {
-    const ADDRESS: u16 = 0x0741;
-    fn bits(&self) -> u8 {
-        self.0
+impl_register! {
+    Register for LoraSyncWordLsb {
+        const ADDRESS: u16 = addresses::LORA_SYNC_WORD_LSB;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # LoRa sync word
+/// 16-bit LoRa sync word spanning [`LoraSyncWordMsb`] and [`LoraSyncWordLsb`].
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::LoraSyncWord, commands::WriteRegister16};
+///
+/// assert_eq!(LoraSyncWord::from_sx127x(0x12), LoraSyncWord::PRIVATE);
+/// assert_eq!(LoraSyncWord::from_sx127x(0x34), LoraSyncWord::PUBLIC);
+/// let write_sync_word = WriteRegister16::new(LoraSyncWord::from_sx127x(0x12));
+/// assert_eq!(write_sync_word.tx_buf, [0x0D, 0x07, 0x40, 0x14, 0x24]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct LoraSyncWord(pub u16);
+
+impl LoraSyncWord {
+    /// Private network sync word, the reset value (SX127x 0x12).
+    pub const PRIVATE: Self = Self(0x1424);
+    /// LoRaWAN public network sync word (SX127x 0x34).
+    pub const PUBLIC: Self = Self(0x3444);
+
+    /// Maps a single-byte SX127x sync word to its SX126x equivalent: each
+    /// nibble becomes the upper nibble of one byte, the lower nibbles are 4.
+    #[inline(always)]
+    pub const fn from_sx127x(sync_word: u8) -> Self {
+        Self(((sync_word & 0xF0) as u16 | 0x04) << 8 | ((sync_word & 0x0F) as u16) << 4 | 0x04)
+    }
+}
+
+impl_register! {
+    Register16 for LoraSyncWord {
+        const ADDRESS: u16 = addresses::LORA_SYNC_WORD_MSB;
+        fn bits(&self) -> u16 {
+            self.0
+        }
+        fn from_bits(bits: u16) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # OCP configuration register
+/// Over-current protection threshold in 2.5 mA steps.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::registers::OcpConfiguration;
+///
+/// assert_eq!(OcpConfiguration::SX1262_DEFAULT.current_limit(), 0x38);
+/// assert_eq!(OcpConfiguration::from_milliamps(60), OcpConfiguration::SX1261_DEFAULT);
+/// assert_eq!(OcpConfiguration::SX1262_DEFAULT.milliamps(), 140);
+/// assert_eq!(OcpConfiguration::from_milliamps(500).milliamps(), 157);
+/// ```
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct OcpConfiguration {
+    #[bits(6)]
+    pub current_limit: u8,
+    #[bits(2)]
+    __: u8,
+}
+
+impl OcpConfiguration {
+    /// 60 mA, reset value on SX1261.
+    pub const SX1261_DEFAULT: Self = Self::new().with_current_limit(0x18);
+    /// 140 mA, reset value on SX1262.
+    pub const SX1262_DEFAULT: Self = Self::new().with_current_limit(0x38);
+
+    /// Rounds down to the nearest 2.5 mA step, saturating at 157.5 mA.
+    #[inline(always)]
+    pub const fn from_milliamps(milliamps: u16) -> Self {
+        let steps = milliamps as u32 * 2 / 5;
+        if steps > 0x3F {
+            Self::new().with_current_limit(0x3F)
+        } else {
+            Self::new().with_current_limit(steps as u8)
+        }
+    }
+    /// Threshold in mA, rounded down.
+    #[inline(always)]
+    pub const fn milliamps(&self) -> u16 {
+        self.current_limit() as u16 * 5 / 2
+    }
+}
+
+impl_register! {
+    Register for OcpConfiguration {
+        const ADDRESS: u16 = addresses::OCP_CONFIGURATION;
+        fn bits(&self) -> u8 {
+            self.into_bits()
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self::from_bits(bits)
+        }
+    }
+}
+
+/// # RX gain register
+/// Selects the LNA gain. Not retained in sleep, so boosted gain has to be
+/// written again after every wake-up. Only bit 1 differs between the two
+/// documented settings; the other bits must be kept.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::RxGain, commands::WriteRegister};
+///
+/// let rx_boosted = WriteRegister::new(RxGain::BOOSTED);
+/// assert_eq!(rx_boosted.tx_buf, [0x0D, 0x08, 0xAC, 0x96]);
+/// assert_eq!(RxGain::POWER_SAVING.with_boosted(true), RxGain::BOOSTED);
+/// ```
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct RxGain {
+    #[bits(1)]
+    __: bool,
+    #[bits(1)]
+    pub boosted: bool,
+    #[bits(6)]
+    __: u8,
+}
+
+impl RxGain {
+    /// Power saving gain, the reset value.
+    pub const POWER_SAVING: Self = Self::from_bits(0x94);
+    /// Boosted gain, roughly +2 dB sensitivity for ~0.5 mA extra current.
+    pub const BOOSTED: Self = Self::from_bits(0x96);
+}
+
+impl_register! {
+    Register for RxGain {
+        const ADDRESS: u16 = addresses::RX_GAIN;
+        fn bits(&self) -> u8 {
+            self.into_bits()
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self::from_bits(bits)
+        }
+    }
+}
+
+/// # TX clamp config register
+/// SX1262 PA clamping threshold. The datasheet workaround for antenna
+/// mismatch sets bits 4:1 on top of the value read back from the chip.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::TxClampConfig, commands::{ReadRegister, WriteRegister}};
+///
+/// let mut read_clamp: ReadRegister<TxClampConfig> = ReadRegister::new();
+/// read_clamp.rx_buf[4] = 0xC8;
+/// let write_clamp = WriteRegister::new(read_clamp.register().with_clamp_workaround());
+/// assert_eq!(write_clamp.tx_buf, [0x0D, 0x08, 0xD8, 0xDE]);
+/// ```
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct TxClampConfig {
+    #[bits(1)]
+    __: bool,
+    #[bits(4)]
+    pub clamp_threshold: u8,
+    #[bits(3)]
+    __: u8,
+}
+
+impl TxClampConfig {
+    #[inline(always)]
+    pub const fn with_clamp_workaround(self) -> Self {
+        self.with_clamp_threshold(0x0F)
+    }
+}
+
+impl_register! {
+    Register for TxClampConfig {
+        const ADDRESS: u16 = addresses::TX_CLAMP_CONFIG;
+        fn bits(&self) -> u8 {
+            self.into_bits()
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self::from_bits(bits)
+        }
+    }
+}
+
+/// # XTA trim register
+/// Load capacitance trim on the XTA pin, from 0x00 (11.3 pF) to 0x2F
+/// (33.4 pF) in ~0.47 pF steps. Only writable in STDBY_XOSC.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::{XtaTrim, XtbTrim}, commands::WriteRegister};
+///
+/// let xta_trim = WriteRegister::new(XtaTrim(0x12));
+/// let xtb_trim = WriteRegister::new(XtbTrim(0x12));
+/// assert_eq!(xta_trim.tx_buf, [0x0D, 0x09, 0x11, 0x12]);
+/// assert_eq!(xtb_trim.tx_buf, [0x0D, 0x09, 0x12, 0x12]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct XtaTrim(pub u8);
+
+impl_register! {
+    Register for XtaTrim {
+        const ADDRESS: u16 = addresses::XTA_TRIM;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # XTB trim register
+/// Load capacitance trim on the XTB pin, same encoding as [`XtaTrim`].
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct XtbTrim(pub u8);
+
+impl_register! {
+    Register for XtbTrim {
+        const ADDRESS: u16 = addresses::XTB_TRIM;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # Whitening initial value MSB register
+/// Bit 0 holds bit 8 of the 9-bit GFSK whitening seed (reset value 0x01FF).
+/// Bits 7:1 are reserved and must be written back unchanged.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::{WhiteningInitialMsb, WhiteningInitialLsb}, commands::WriteRegister};
+///
+/// let whitening_msb = WriteRegister::new(WhiteningInitialMsb(0x01));
+/// let whitening_lsb = WriteRegister::new(WhiteningInitialLsb(0xFF));
+/// assert_eq!(whitening_msb.tx_buf, [0x0D, 0x06, 0xB8, 0x01]);
+/// assert_eq!(whitening_lsb.tx_buf, [0x0D, 0x06, 0xB9, 0xFF]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WhiteningInitialMsb(pub u8);
+
+impl_register! {
+    Register for WhiteningInitialMsb {
+        const ADDRESS: u16 = addresses::WHITENING_INITIAL_MSB;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # Whitening initial value LSB register
+/// Bits 7:0 of the GFSK whitening seed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WhiteningInitialLsb(pub u8);
+
+impl_register! {
+    Register for WhiteningInitialLsb {
+        const ADDRESS: u16 = addresses::WHITENING_INITIAL_LSB;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
     }
-    fn from_bits(bits: u8) -> Self {
-        Self(bits)
+}
+
+/// # Whitening initial value
+/// 9-bit GFSK whitening seed spanning [`WhiteningInitialMsb`] and
+/// [`WhiteningInitialLsb`]. Bits 15:9 are reserved and must be written back
+/// unchanged.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::WhiteningInitial, commands::WriteRegister16};
+///
+/// let whitening_initial = WriteRegister16::new(WhiteningInitial(0x01FF));
+/// assert_eq!(whitening_initial.tx_buf, [0x0D, 0x06, 0xB8, 0x01, 0xFF]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct WhiteningInitial(pub u16);
+
+impl_register! {
+    Register16 for WhiteningInitial {
+        const ADDRESS: u16 = addresses::WHITENING_INITIAL_MSB;
+        fn bits(&self) -> u16 {
+            self.0
+        }
+        fn from_bits(bits: u16) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # CRC initial value MSB register
+/// Bits 15:8 of the GFSK CRC seed (reset value 0x1D0F). Datasheet presets
+/// are 0x1D0F for CCITT and 0xFFFF for IBM.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::{CrcInitialMsb, CrcInitialLsb}, commands::WriteRegister};
+///
+/// let crc_initial_msb = WriteRegister::new(CrcInitialMsb(0xFF));
+/// let crc_initial_lsb = WriteRegister::new(CrcInitialLsb(0xFF));
+/// assert_eq!(crc_initial_msb.tx_buf, [0x0D, 0x06, 0xBC, 0xFF]);
+/// assert_eq!(crc_initial_lsb.tx_buf, [0x0D, 0x06, 0xBD, 0xFF]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CrcInitialMsb(pub u8);
+
+impl_register! {
+    Register for CrcInitialMsb {
+        const ADDRESS: u16 = addresses::CRC_INITIAL_MSB;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # CRC initial value LSB register
+/// Bits 7:0 of the GFSK CRC seed.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CrcInitialLsb(pub u8);
+
+impl_register! {
+    Register for CrcInitialLsb {
+        const ADDRESS: u16 = addresses::CRC_INITIAL_LSB;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # CRC initial value
+/// 16-bit GFSK CRC seed spanning [`CrcInitialMsb`] and [`CrcInitialLsb`].
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::CrcInitial, commands::WriteRegister16};
+///
+/// let crc_initial = WriteRegister16::new(CrcInitial(0x1D0F));
+/// assert_eq!(crc_initial.tx_buf, [0x0D, 0x06, 0xBC, 0x1D, 0x0F]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CrcInitial(pub u16);
+
+impl_register! {
+    Register16 for CrcInitial {
+        const ADDRESS: u16 = addresses::CRC_INITIAL_MSB;
+        fn bits(&self) -> u16 {
+            self.0
+        }
+        fn from_bits(bits: u16) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # CRC polynomial MSB register
+/// Bits 15:8 of the GFSK CRC polynomial (reset value 0x1021). Datasheet
+/// presets are 0x1021 for CCITT and 0x8005 for IBM.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::{CrcPolynomialMsb, CrcPolynomialLsb}, commands::WriteRegister};
+///
+/// let crc_polynomial_msb = WriteRegister::new(CrcPolynomialMsb(0x80));
+/// let crc_polynomial_lsb = WriteRegister::new(CrcPolynomialLsb(0x05));
+/// assert_eq!(crc_polynomial_msb.tx_buf, [0x0D, 0x06, 0xBE, 0x80]);
+/// assert_eq!(crc_polynomial_lsb.tx_buf, [0x0D, 0x06, 0xBF, 0x05]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CrcPolynomialMsb(pub u8);
+
+impl_register! {
+    Register for CrcPolynomialMsb {
+        const ADDRESS: u16 = addresses::CRC_POLYNOMIAL_MSB;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # CRC polynomial LSB register
+/// Bits 7:0 of the GFSK CRC polynomial.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CrcPolynomialLsb(pub u8);
+
+impl_register! {
+    Register for CrcPolynomialLsb {
+        const ADDRESS: u16 = addresses::CRC_POLYNOMIAL_LSB;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # CRC polynomial
+/// 16-bit GFSK CRC polynomial spanning [`CrcPolynomialMsb`] and
+/// [`CrcPolynomialLsb`].
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::CrcPolynomial, commands::ReadRegister16};
+///
+/// let mut read_polynomial: ReadRegister16<CrcPolynomial> = ReadRegister16::new();
+/// read_polynomial.rx_buf[4..].copy_from_slice(&[0x10, 0x21]);
+/// assert_eq!(read_polynomial.register(), CrcPolynomial(0x1021));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct CrcPolynomial(pub u16);
+
+impl_register! {
+    Register16 for CrcPolynomial {
+        const ADDRESS: u16 = addresses::CRC_POLYNOMIAL_MSB;
+        fn bits(&self) -> u16 {
+            self.0
+        }
+        fn from_bits(bits: u16) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # Sync word registers
+/// `SyncWord0` (0x06C0) to `SyncWord7` (0x06C7) hold the GFSK sync word,
+/// first transmitted byte in `SyncWord0`. The number of bits used is set by
+/// the GFSK packet parameters. See
+/// [`WriteRegisterBlock::gfsk_sync_word`](crate::commands::WriteRegisterBlock::gfsk_sync_word)
+/// to write all eight in one transfer.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::SyncWord7, commands::WriteRegister};
+///
+/// let sync_word_7 = WriteRegister::new(SyncWord7(0x2D));
+/// assert_eq!(sync_word_7.tx_buf, [0x0D, 0x06, 0xC7, 0x2D]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SyncWord0(pub u8);
+
+impl_register! {
+    Register for SyncWord0 {
+        const ADDRESS: u16 = addresses::SYNC_WORD_0;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SyncWord1(pub u8);
+
+impl_register! {
+    Register for SyncWord1 {
+        const ADDRESS: u16 = addresses::SYNC_WORD_1;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SyncWord2(pub u8);
+
+impl_register! {
+    Register for SyncWord2 {
+        const ADDRESS: u16 = addresses::SYNC_WORD_2;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SyncWord3(pub u8);
+
+impl_register! {
+    Register for SyncWord3 {
+        const ADDRESS: u16 = addresses::SYNC_WORD_3;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SyncWord4(pub u8);
+
+impl_register! {
+    Register for SyncWord4 {
+        const ADDRESS: u16 = addresses::SYNC_WORD_4;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SyncWord5(pub u8);
+
+impl_register! {
+    Register for SyncWord5 {
+        const ADDRESS: u16 = addresses::SYNC_WORD_5;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SyncWord6(pub u8);
+
+impl_register! {
+    Register for SyncWord6 {
+        const ADDRESS: u16 = addresses::SYNC_WORD_6;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct SyncWord7(pub u8);
+
+impl_register! {
+    Register for SyncWord7 {
+        const ADDRESS: u16 = addresses::SYNC_WORD_7;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # Node address register
+/// GFSK node address compared against the first payload byte when address
+/// filtering is enabled in the packet parameters.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::{NodeAddress, BroadcastAddress}, commands::WriteRegister};
+///
+/// let node_address = WriteRegister::new(NodeAddress(0x2A));
+/// let broadcast_address = WriteRegister::new(BroadcastAddress(0xFF));
+/// assert_eq!(node_address.tx_buf, [0x0D, 0x06, 0xCD, 0x2A]);
+/// assert_eq!(broadcast_address.tx_buf, [0x0D, 0x06, 0xCE, 0xFF]);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NodeAddress(pub u8);
+
+impl_register! {
+    Register for NodeAddress {
+        const ADDRESS: u16 = addresses::NODE_ADDRESS;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # Broadcast address register
+/// GFSK broadcast address, accepted in addition to [`NodeAddress`] when
+/// node and broadcast filtering is enabled.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BroadcastAddress(pub u8);
+
+impl_register! {
+    Register for BroadcastAddress {
+        const ADDRESS: u16 = addresses::BROADCAST_ADDRESS;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # IQ polarity setup register
+/// Datasheet workaround for inverted IQ: bit 2 must be cleared when
+/// [`InvertIq::Inverted`] is used and set otherwise, applied after every
+/// SetPacketParams.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::IqPolaritySetup, commands::{InvertIq, ReadRegister, WriteRegister}};
+///
+/// let mut read_iq: ReadRegister<IqPolaritySetup> = ReadRegister::new();
+/// read_iq.rx_buf[4] = 0x0D;
+/// let write_iq = WriteRegister::new(read_iq.register().with_invert_iq(InvertIq::Inverted));
+/// assert_eq!(write_iq.tx_buf, [0x0D, 0x07, 0x36, 0x09]);
+/// assert!(IqPolaritySetup::from_bits(0x09).with_invert_iq(InvertIq::Standard).standard_iq());
+/// ```
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct IqPolaritySetup {
+    #[bits(2)]
+    __: u8,
+    #[bits(1)]
+    pub standard_iq: bool,
+    #[bits(5)]
+    __: u8,
+}
+
+impl IqPolaritySetup {
+    #[inline(always)]
+    pub const fn with_invert_iq(self, invert_iq: InvertIq) -> Self {
+        self.with_standard_iq(matches!(invert_iq, InvertIq::Standard))
+    }
+}
+
+impl_register! {
+    Register for IqPolaritySetup {
+        const ADDRESS: u16 = addresses::IQ_POLARITY_SETUP;
+        fn bits(&self) -> u8 {
+            self.into_bits()
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self::from_bits(bits)
+        }
+    }
+}
+
+/// # Frequency error indicator
+/// Frequency offset of the last LoRa packet received, a signed 20-bit value
+/// at [`FREQ_ERROR`](addresses::FREQ_ERROR), valid after RxDone. Positive
+/// when the transmitter is above the programmed frequency.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{Bw, SetRfFrequency};
+/// use sx126x_spi_buffers::registers::FrequencyError;
+///
+/// let mut read = FrequencyError::read();
+/// assert_eq!(read.tx_buf, [0x1D, 0x07, 0x6B, 0, 0, 0, 0]);
+/// read.rx_buf[4..].copy_from_slice(&[0x0E, 0x79, 0x60]);
+/// let frequency_error = FrequencyError::from_read(&read);
+/// assert_eq!(frequency_error, FrequencyError(-100_000));
+/// assert_eq!(frequency_error.hz(Bw::Bw125), -12_109);
+/// assert_eq!(frequency_error.ppm(Bw::Bw125, 868_000_000), -14);
+///
+/// // Follow the transmitter
+/// let set_rf_frequency = SetRfFrequency::from_hz(frequency_error.corrected_hz(Bw::Bw125, 868_000_000));
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct FrequencyError(pub i32);
+
+impl FrequencyError {
+    /// ReadRegister of the 3 bytes.
+    #[inline(always)]
+    pub const fn read() -> ReadRegisterBlock<7> {
+        ReadRegisterBlock::new(addresses::FREQ_ERROR)
+    }
+    #[inline(always)]
+    pub const fn from_bytes(bytes: [u8; 3]) -> Self {
+        let raw = ((bytes[0] & 0x0F) as i32) << 16 | (bytes[1] as i32) << 8 | bytes[2] as i32;
+        // Sign extension from 20 bits
+        Self(raw << 12 >> 12)
+    }
+    #[inline(always)]
+    pub const fn from_read(read: &ReadRegisterBlock<7>) -> Self {
+        Self::from_bytes([read.rx_buf[4], read.rx_buf[5], read.rx_buf[6]])
+    }
+    /// Offset in Hz, `1.55 * raw * BW / 1600` with BW in kHz. 0 for
+    /// reserved bandwidths.
+    #[inline(always)]
+    pub const fn hz(&self, bw: Bw) -> i32 {
+        let chip_time_us = chip_time_us(bw) as i64;
+        if chip_time_us == 0 {
+            return 0;
+        }
+        // 1.55 / 1600 * 10^3 / chip_time_us
+        (self.0 as i64 * 31 / (32 * chip_time_us)) as i32
+    }
+    /// Offset relative to `rf_hz`, in ppm rounded to the nearest; the
+    /// negation is the crystal error to correct with
+    /// [`SetRfFrequency::from_hz_ppm`](crate::commands::SetRfFrequency::from_hz_ppm).
+    #[inline(always)]
+    pub const fn ppm(&self, bw: Bw, rf_hz: u32) -> i16 {
+        if rf_hz == 0 {
+            return 0;
+        }
+        let rf_hz = rf_hz as i64;
+        let error = self.hz(bw) as i64 * 1_000_000;
+        let rounding = if error < 0 { -rf_hz / 2 } else { rf_hz / 2 };
+        ((error + rounding) / rf_hz) as i16
+    }
+    /// `rf_hz` moved onto the transmitter frequency.
+    #[inline(always)]
+    pub const fn corrected_hz(&self, bw: Bw, rf_hz: u32) -> u32 {
+        rf_hz.saturating_add_signed(self.hz(bw))
+    }
+}
+
+/// # TX modulation register
+/// Datasheet workaround for LoRa modulation quality: bit 2 must be cleared
+/// when transmitting at [`Bw::Bw500`] and set for every other bandwidth,
+/// before each SetTx.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::TxModulation, commands::{Bw, ReadRegister, WriteRegister}};
+///
+/// let mut read_tx_mod: ReadRegister<TxModulation> = ReadRegister::new();
+/// read_tx_mod.rx_buf[4] = 0x04;
+/// let write_tx_mod = WriteRegister::new(read_tx_mod.register().with_bandwidth(Bw::Bw500));
+/// assert_eq!(write_tx_mod.tx_buf, [0x0D, 0x08, 0x89, 0x00]);
+/// assert!(TxModulation::new().with_bandwidth(Bw::Bw125).narrow_bandwidth());
+/// ```
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct TxModulation {
+    #[bits(2)]
+    __: u8,
+    #[bits(1)]
+    pub narrow_bandwidth: bool,
+    #[bits(5)]
+    __: u8,
+}
+
+impl TxModulation {
+    #[inline(always)]
+    pub const fn with_bandwidth(self, bw: Bw) -> Self {
+        self.with_narrow_bandwidth(!matches!(bw, Bw::Bw500))
+    }
+}
+
+impl_register! {
+    Register for TxModulation {
+        const ADDRESS: u16 = addresses::TX_MODULATION;
+        fn bits(&self) -> u8 {
+            self.into_bits()
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self::from_bits(bits)
+        }
+    }
+}
+
+/// # Random number generator registers
+/// `RandomNumberGen0` (0x0819, MSB) to `RandomNumberGen3` (0x081C, LSB) hold
+/// a 32-bit value produced from receiver noise. See
+/// [`GetRandomNumber`](crate::commands::GetRandomNumber) for the complete
+/// read sequence.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RandomNumberGen0(pub u8);
+
+impl_register! {
+    Register for RandomNumberGen0 {
+        const ADDRESS: u16 = addresses::RANDOM_NUMBER_GEN_0;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RandomNumberGen1(pub u8);
+
+impl_register! {
+    Register for RandomNumberGen1 {
+        const ADDRESS: u16 = addresses::RANDOM_NUMBER_GEN_1;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RandomNumberGen2(pub u8);
+
+impl_register! {
+    Register for RandomNumberGen2 {
+        const ADDRESS: u16 = addresses::RANDOM_NUMBER_GEN_2;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RandomNumberGen3(pub u8);
+
+impl_register! {
+    Register for RandomNumberGen3 {
+        const ADDRESS: u16 = addresses::RANDOM_NUMBER_GEN_3;
+        fn bits(&self) -> u8 {
+            self.0
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self(bits)
+        }
+    }
+}
+
+/// # DIO output enable register
+/// Bit n enables the output driver of DIOn when the pin is used as a GPIO.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::{registers::{DioOutputEnable, DioPullUpControl}, commands::{ReadRegister, WriteRegister}};
+///
+/// let dio_output_enable = WriteRegister::new(DioOutputEnable::new().with_dio3(true));
+/// let dio_pull_up = WriteRegister::new(DioPullUpControl::new().with_dio1(true).with_dio2(true));
+/// assert_eq!(dio_output_enable.tx_buf, [0x0D, 0x05, 0x80, 0x08]);
+/// assert_eq!(dio_pull_up.tx_buf, [0x0D, 0x05, 0x84, 0x06]);
+///
+/// let mut read_pull_up: ReadRegister<DioPullUpControl> = ReadRegister::new();
+/// read_pull_up.rx_buf[4] = 0x04;
+/// assert_eq!(read_pull_up.register(), DioPullUpControl::new().with_dio2(true));
+/// ```
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct DioOutputEnable {
+    #[bits(1)]
+    __: bool,
+    #[bits(1)]
+    pub dio1: bool,
+    #[bits(1)]
+    pub dio2: bool,
+    #[bits(1)]
+    pub dio3: bool,
+    #[bits(4)]
+    __: u8,
+}
+
+impl_register! {
+    Register for DioOutputEnable {
+        const ADDRESS: u16 = addresses::DIO_OUTPUT_ENABLE;
+        fn bits(&self) -> u8 {
+            self.into_bits()
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self::from_bits(bits)
+        }
+    }
+}
+/// # DIO input enable register
+/// Bit n enables the input buffer of DIOn.
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct DioInputEnable {
+    #[bits(1)]
+    __: bool,
+    #[bits(1)]
+    pub dio1: bool,
+    #[bits(1)]
+    pub dio2: bool,
+    #[bits(1)]
+    pub dio3: bool,
+    #[bits(4)]
+    __: u8,
+}
+
+impl_register! {
+    Register for DioInputEnable {
+        const ADDRESS: u16 = addresses::DIO_INPUT_ENABLE;
+        fn bits(&self) -> u8 {
+            self.into_bits()
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self::from_bits(bits)
+        }
+    }
+}
+/// # DIO pull-up control register
+/// Bit n enables the internal pull-up on DIOn.
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct DioPullUpControl {
+    #[bits(1)]
+    __: bool,
+    #[bits(1)]
+    pub dio1: bool,
+    #[bits(1)]
+    pub dio2: bool,
+    #[bits(1)]
+    pub dio3: bool,
+    #[bits(4)]
+    __: u8,
+}
+
+impl_register! {
+    Register for DioPullUpControl {
+        const ADDRESS: u16 = addresses::DIO_PULL_UP_CONTROL;
+        fn bits(&self) -> u8 {
+            self.into_bits()
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self::from_bits(bits)
+        }
+    }
+}
+/// # DIO pull-down control register
+/// Bit n enables the internal pull-down on DIOn.
+#[bitfield(u8)]
+#[derive(PartialEq, Eq)]
+pub struct DioPullDownControl {
+    #[bits(1)]
+    __: bool,
+    #[bits(1)]
+    pub dio1: bool,
+    #[bits(1)]
+    pub dio2: bool,
+    #[bits(1)]
+    pub dio3: bool,
+    #[bits(4)]
+    __: u8,
+}
+
+impl_register! {
+    Register for DioPullDownControl {
+        const ADDRESS: u16 = addresses::DIO_PULL_DOWN_CONTROL;
+        fn bits(&self) -> u8 {
+            self.into_bits()
+        }
+        fn from_bits(bits: u8) -> Self {
+            Self::from_bits(bits)
+        }
     }
 }
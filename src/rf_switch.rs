@@ -0,0 +1,134 @@
+//! Control of the external RF switch between antenna, PA and LNA.
+//!
+//! Most modules let the chip drive the switch from DIO2, enabled once with
+//! SetDio2AsRfSwitchCtrl; see [`Dio2`]. Boards that wire the switch to MCU
+//! pins instead use [`GpioSwitch`], which the [`Radio`](crate::radio::Radio)
+//! driver sets around every transmission, reception and CAD.
+
+use super::commands::SetDio2AsRfSwitchCtrl;
+use embedded_hal::digital::OutputPin;
+
+/// Signal path selected by an [`RfSwitch`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RfPath {
+    /// Both paths disconnected, to save power.
+    Off,
+    Tx,
+    Rx,
+}
+
+/// An RF switch driven by the host. `E` is the error of the pins driving
+/// it; the [`Radio`](crate::radio::Radio) driver requires the error type of
+/// its BUSY pin.
+pub trait RfSwitch<E> {
+    fn set(&mut self, path: RfPath) -> Result<(), E>;
+    /// Settling time after selecting a path, before the operation starts.
+    fn pre_delay_us(&self) -> u32 {
+        0
+    }
+    /// Time the path is kept after the operation, before switching off.
+    fn post_delay_us(&self) -> u32 {
+        0
+    }
+}
+
+/// # DIO2 switch
+/// Switch driven by the chip itself: DIO2 is high in TX and low otherwise.
+/// The host has nothing to do once [`command`](Self::command) has been sent,
+/// or the [`InitSequence`](crate::init::InitSequence) built
+/// [`with_dio2_as_rf_switch`](crate::init::InitSequence::with_dio2_as_rf_switch).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Dio2;
+impl Dio2 {
+    /// SetDio2AsRfSwitchCtrl enabling the control.
+    #[inline(always)]
+    pub const fn command() -> SetDio2AsRfSwitchCtrl {
+        SetDio2AsRfSwitchCtrl::new(true)
+    }
+}
+impl<E> RfSwitch<E> for Dio2 {
+    #[inline(always)]
+    fn set(&mut self, _path: RfPath) -> Result<(), E> {
+        Ok(())
+    }
+}
+
+/// # GPIO switch
+/// Switch with one control pin per path, driven high to select it.
+///
+/// ## Example
+/// ```
+/// use embedded_hal_mock::eh1::digital::{Mock as PinMock, State, Transaction as PinTransaction};
+/// use sx126x_spi_buffers::rf_switch::{GpioSwitch, RfPath, RfSwitch};
+///
+/// let tx = PinMock::new(&[PinTransaction::set(State::Low), PinTransaction::set(State::High)]);
+/// let rx = PinMock::new(&[PinTransaction::set(State::Low), PinTransaction::set(State::Low)]);
+/// let mut switch = GpioSwitch::new(tx, rx).with_delays(10, 5);
+/// switch.set(RfPath::Off).unwrap();
+/// switch.set(RfPath::Tx).unwrap();
+/// assert_eq!((switch.pre_delay_us(), switch.post_delay_us()), (10, 5));
+///
+/// let (mut tx, mut rx) = switch.release();
+/// tx.done();
+/// rx.done();
+/// ```
+pub struct GpioSwitch<TX, RX> {
+    tx: TX,
+    rx: RX,
+    pre_delay_us: u32,
+    post_delay_us: u32,
+}
+impl<TX, RX> GpioSwitch<TX, RX>
+where
+    TX: OutputPin,
+    RX: OutputPin<Error = TX::Error>,
+{
+    pub fn new(tx: TX, rx: RX) -> Self {
+        Self {
+            tx,
+            rx,
+            pre_delay_us: 0,
+            post_delay_us: 0,
+        }
+    }
+    /// Sets the settling time after selecting a path and the time the path
+    /// is kept after the operation.
+    pub fn with_delays(mut self, pre_delay_us: u32, post_delay_us: u32) -> Self {
+        self.pre_delay_us = pre_delay_us;
+        self.post_delay_us = post_delay_us;
+        self
+    }
+    pub fn release(self) -> (TX, RX) {
+        (self.tx, self.rx)
+    }
+}
+impl<TX, RX> RfSwitch<TX::Error> for GpioSwitch<TX, RX>
+where
+    TX: OutputPin,
+    RX: OutputPin<Error = TX::Error>,
+{
+    /// Drives the pin of the other path low before driving the selected one
+    /// high, so both paths are never connected at once.
+    fn set(&mut self, path: RfPath) -> Result<(), TX::Error> {
+        match path {
+            RfPath::Off => {
+                self.tx.set_low()?;
+                self.rx.set_low()
+            }
+            RfPath::Tx => {
+                self.rx.set_low()?;
+                self.tx.set_high()
+            }
+            RfPath::Rx => {
+                self.tx.set_low()?;
+                self.rx.set_high()
+            }
+        }
+    }
+    fn pre_delay_us(&self) -> u32 {
+        self.pre_delay_us
+    }
+    fn post_delay_us(&self) -> u32 {
+        self.post_delay_us
+    }
+}
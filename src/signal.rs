@@ -0,0 +1,100 @@
+//! Completion signalling between the transfer executor and async tasks.
+
+use core::cell::RefCell;
+use core::future::Future;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+use critical_section::Mutex;
+
+/// # TransferSignal
+/// Completion flag plus waker slot for a single in-flight transfer. Attach
+/// it to a descriptor with
+/// [`SpiDescriptor::with_signal`](crate::commands::SpiDescriptor::with_signal);
+/// [`SpiDescriptor::complete`](crate::commands::SpiDescriptor::complete),
+/// called from the DMA-complete ISR, sets it and wakes the awaiting task.
+///
+/// ## Example
+/// ```
+/// use core::{pin::pin, task::{Context, Poll, Waker}};
+/// use sx126x_spi_buffers::commands::{GetStatus, SpiDescriptor};
+/// use sx126x_spi_buffers::signal::TransferSignal;
+///
+/// static GET_STATUS_DONE: TransferSignal = TransferSignal::new();
+///
+/// let get_status: &'static mut GetStatus = Box::leak(Box::new(GetStatus::new()));
+/// let mut desc = SpiDescriptor::new(get_status).with_signal(&GET_STATUS_DONE);
+///
+/// let mut cx = Context::from_waker(Waker::noop());
+/// let mut wait = pin!(GET_STATUS_DONE.wait());
+/// assert_eq!(wait.as_mut().poll(&mut cx), Poll::Pending);
+/// desc.complete(); // DMA-complete ISR
+/// assert_eq!(wait.as_mut().poll(&mut cx), Poll::Ready(()));
+/// ```
+pub struct TransferSignal {
+    done: AtomicBool,
+    waker: Mutex<RefCell<Option<Waker>>>,
+}
+impl TransferSignal {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            done: AtomicBool::new(false),
+            waker: Mutex::new(RefCell::new(None)),
+        }
+    }
+    /// Clears the completion flag before the descriptor is queued again.
+    pub fn reset(&self) {
+        self.done.store(false, Ordering::Release);
+    }
+    /// Marks the transfer as complete and wakes the waiting task, if any.
+    pub fn signal(&self) {
+        let waker = critical_section::with(|cs| {
+            self.done.store(true, Ordering::Release);
+            self.waker.borrow_ref_mut(cs).take()
+        });
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+    pub fn is_done(&self) -> bool {
+        self.done.load(Ordering::Acquire)
+    }
+    /// Resolves once [`signal`](Self::signal) has been called.
+    pub fn wait(&self) -> Wait<'_> {
+        Wait { signal: self }
+    }
+}
+
+impl Default for TransferSignal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for TransferSignal {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("TransferSignal")
+            .field("done", &self.is_done())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Future returned by [`TransferSignal::wait`].
+pub struct Wait<'a> {
+    signal: &'a TransferSignal,
+}
+impl Future for Wait<'_> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        critical_section::with(|cs| {
+            if self.signal.done.load(Ordering::Acquire) {
+                Poll::Ready(())
+            } else {
+                *self.signal.waker.borrow_ref_mut(cs) = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        })
+    }
+}
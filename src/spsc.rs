@@ -0,0 +1,180 @@
+//! Lock-free single-producer/single-consumer descriptor queue.
+
+use super::commands::SpiDescriptor;
+use core::cell::UnsafeCell;
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+/// # SpscQueue
+/// Fixed-capacity FIFO of [`SpiDescriptor`]s for exactly one producer and
+/// one consumer, typically the main context and the DMA-complete ISR.
+/// [`split`](Self::split) hands out the two ends; each side only ever
+/// stores its own index, so only atomic loads and stores are needed and
+/// the queue also works on cores without compare-and-swap such as the
+/// Cortex-M0.
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{SetStandby, StdbyConfig};
+/// use sx126x_spi_buffers::spsc::SpscQueue;
+///
+/// let mut queue: SpscQueue<2> = SpscQueue::new();
+/// let (mut producer, mut consumer) = queue.split();
+///
+/// let mut set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+/// producer.enqueue(set_standby.descriptor()).unwrap();
+/// producer.enqueue(set_standby.descriptor()).unwrap();
+/// assert!(producer.enqueue(set_standby.descriptor()).is_err());
+/// assert_eq!(consumer.len(), 2);
+/// assert_eq!(consumer.dequeue(), Some(set_standby.descriptor()));
+/// ```
+pub struct SpscQueue<const N: usize> {
+    buf: [UnsafeCell<MaybeUninit<SpiDescriptor>>; N],
+    /// Number of descriptors dequeued modulo `2 * N`, written by the
+    /// consumer only.
+    head: AtomicUsize,
+    /// Number of descriptors enqueued modulo `2 * N`, written by the
+    /// producer only.
+    tail: AtomicUsize,
+}
+
+// SAFETY: a slot is written by the producer only while it is outside
+// `head..tail` and read by the consumer only while it is inside; the
+// Release/Acquire pairs on `head` and `tail` order those accesses.
+unsafe impl<const N: usize> Sync for SpscQueue<N> {}
+
+impl<const N: usize> SpscQueue<N> {
+    /// The indices run over `0..2 * N`, which tells a full queue from an
+    /// empty one and keeps the slot of an index the same after it wraps.
+    const SIZE_OK: () = assert!(
+        N > 0 && N <= usize::MAX / 2,
+        "N must be in 1..=usize::MAX / 2"
+    );
+
+    #[inline(always)]
+    pub const fn new() -> Self {
+        let () = Self::SIZE_OK;
+        Self {
+            buf: [const { UnsafeCell::new(MaybeUninit::uninit()) }; N],
+            head: AtomicUsize::new(0),
+            tail: AtomicUsize::new(0),
+        }
+    }
+    /// Splits the queue into its producer and consumer ends.
+    pub fn split(&mut self) -> (Producer<'_, N>, Consumer<'_, N>) {
+        (Producer { queue: self }, Consumer { queue: self })
+    }
+    pub fn len(&self) -> usize {
+        // Load `head` first: `tail` never falls behind an earlier `head`.
+        let head = self.head.load(Ordering::Acquire);
+        Self::distance(head, self.tail.load(Ordering::Acquire))
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    #[inline(always)]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    #[inline(always)]
+    const fn distance(head: usize, tail: usize) -> usize {
+        (tail + 2 * N - head) % (2 * N)
+    }
+    #[inline(always)]
+    const fn next(index: usize) -> usize {
+        (index + 1) % (2 * N)
+    }
+}
+
+impl<const N: usize> Default for SpscQueue<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Enqueueing end of a [`SpscQueue`].
+pub struct Producer<'a, const N: usize> {
+    queue: &'a SpscQueue<N>,
+}
+impl<const N: usize> Producer<'_, N> {
+    /// Appends a descriptor, handing it back if the queue is full.
+    pub fn enqueue(&mut self, desc: SpiDescriptor) -> Result<(), SpiDescriptor> {
+        let tail = self.queue.tail.load(Ordering::Relaxed);
+        if SpscQueue::<N>::distance(self.queue.head.load(Ordering::Acquire), tail) >= N {
+            return Err(desc);
+        }
+        // SAFETY: the slot is outside `head..tail`, so the consumer does not
+        // touch it until `tail` is published below.
+        unsafe { (*self.queue.buf[tail % N].get()).write(desc) };
+        self.queue
+            .tail
+            .store(SpscQueue::<N>::next(tail), Ordering::Release);
+        Ok(())
+    }
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+/// Dequeueing end of a [`SpscQueue`].
+pub struct Consumer<'a, const N: usize> {
+    queue: &'a SpscQueue<N>,
+}
+impl<const N: usize> Consumer<'_, N> {
+    pub fn dequeue(&mut self) -> Option<SpiDescriptor> {
+        let head = self.queue.head.load(Ordering::Relaxed);
+        if head == self.queue.tail.load(Ordering::Acquire) {
+            return None;
+        }
+        // SAFETY: the slot is inside `head..tail`, so the producer has
+        // initialized it and does not touch it until `head` moves past it.
+        let desc = unsafe { (*self.queue.buf[head % N].get()).assume_init() };
+        self.queue
+            .head
+            .store(SpscQueue::<N>::next(head), Ordering::Release);
+        Some(desc)
+    }
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::{SetStandby, StdbyConfig};
+
+    #[test]
+    fn test_spsc_wraparound() {
+        let mut queue: SpscQueue<3> = SpscQueue::new();
+        let (mut producer, mut consumer) = queue.split();
+        let mut set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+        let desc = set_standby.descriptor();
+        let mut id = 0;
+        for round in 0..10 {
+            // Start every round at another offset of the 2 * N index range.
+            for _ in 0..=round % 3 {
+                producer.enqueue(desc.with_id(id)).unwrap();
+                assert_eq!(consumer.dequeue().and_then(|desc| desc.id()), Some(id));
+                id += 1;
+            }
+            for i in 0..3 {
+                producer.enqueue(desc.with_id(id + i)).unwrap();
+            }
+            assert!(producer.enqueue(desc).is_err());
+            assert_eq!(consumer.len(), 3);
+            for i in 0..3 {
+                assert_eq!(consumer.dequeue().and_then(|desc| desc.id()), Some(id + i));
+            }
+            assert!(consumer.dequeue().is_none());
+            id += 3;
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Deadlines for asynchronous TX, RX and CAD operations, on `embassy-time`.
+//!
+//! A lost DIO1 edge or a chip that stopped answering would otherwise leave
+//! the task awaiting the end of an operation forever. [`with_timeout`] and
+//! [`with_deadline`] give up on the operation and hand back the [`Abort`]
+//! sequence that returns the chip to a known state.
+//!
+//! ## Example
+//! ```
+//! use core::future::{pending, ready};
+//! use embassy_futures::block_on;
+//! use embassy_time::Duration;
+//! use sx126x_spi_buffers::timeout::{self, Abort};
+//!
+//! // Typically the future signalled by the DIO1 interrupt after SetTx.
+//! assert_eq!(block_on(timeout::with_timeout(Duration::from_millis(10), ready(1))).ok(), Some(1));
+//!
+//! let mut abort: Abort = block_on(timeout::with_timeout(Duration::from_ticks(0), pending::<()>())).unwrap_err();
+//! assert_eq!(abort.set_standby.tx_buf, [0x80, 0x00]);
+//! assert_eq!(abort.clear_irq_status.tx_buf, [0x02, 0x43, 0xFF]);
+//! assert_eq!(abort.descriptors().map(|d| d.transfer_length()), [2, 3]);
+//! ```
+
+use super::commands::{ClearIrqStatus, SetStandby, SpiDescriptor, StdbyConfig};
+use core::future::Future;
+use embassy_time::{Duration, Instant};
+
+/// # Abort sequence
+/// Stops any ongoing operation with SetStandby(STDBY_RC) and clears every
+/// IRQ with ClearIrqStatus, so that a late IRQ is not mistaken for the end
+/// of the next operation. Send the descriptors in order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Abort {
+    pub set_standby: SetStandby,
+    pub clear_irq_status: ClearIrqStatus,
+}
+impl Abort {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            set_standby: SetStandby::new(StdbyConfig::StdbyRc),
+            clear_irq_status: ClearIrqStatus::all(),
+        }
+    }
+    #[inline(always)]
+    pub const fn descriptors(&mut self) -> [SpiDescriptor; 2] {
+        [
+            self.set_standby.descriptor(),
+            self.clear_irq_status.descriptor(),
+        ]
+    }
+}
+
+impl Default for Abort {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Duration of a SetTx or SetRx timeout of `ticks` steps of 15.625 µs,
+/// useful as the base of a [`with_timeout`] that backs up the chip's own
+/// timeout.
+#[inline(always)]
+pub const fn ticks_duration(ticks: u32) -> Duration {
+    Duration::from_micros(ticks as u64 * 15_625 / 1_000)
+}
+
+/// Awaits `operation`, or returns the [`Abort`] sequence to send if it has
+/// not completed within `timeout`.
+pub async fn with_timeout<F: Future>(timeout: Duration, operation: F) -> Result<F::Output, Abort> {
+    embassy_time::with_timeout(timeout, operation)
+        .await
+        .map_err(|_| Abort::new())
+}
+
+/// Awaits `operation`, or returns the [`Abort`] sequence to send if it has
+/// not completed by `deadline`.
+pub async fn with_deadline<F: Future>(deadline: Instant, operation: F) -> Result<F::Output, Abort> {
+    embassy_time::with_deadline(deadline, operation)
+        .await
+        .map_err(|_| Abort::new())
+}
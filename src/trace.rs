@@ -0,0 +1,57 @@
+//! Global hooks for logging every SPI transfer, e.g. over RTT during radio
+//! bring-up.
+//!
+//! ## Example
+//! ```
+//! use core::sync::atomic::{AtomicU8, Ordering};
+//! use sx126x_spi_buffers::commands::{GetStatus, SpiDescriptor};
+//! use sx126x_spi_buffers::trace;
+//!
+//! static LAST_OPCODE: AtomicU8 = AtomicU8::new(0);
+//!
+//! fn log_tx(_desc: &SpiDescriptor, bytes: &[u8]) {
+//!     LAST_OPCODE.store(bytes[0], Ordering::Relaxed); // e.g. rprintln!("> {:02X?}", bytes)
+//! }
+//!
+//! trace::set_tx_hook(log_tx);
+//! let mut get_status = GetStatus::new();
+//! let desc = get_status.descriptor();
+//! unsafe { desc.trace_tx() };
+//! assert_eq!(LAST_OPCODE.load(Ordering::Relaxed), 0xC0);
+//! ```
+
+use super::commands::SpiDescriptor;
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+/// Called with a descriptor and the bytes it clocks out or in.
+pub type TraceHook = fn(&SpiDescriptor, &[u8]);
+
+static TX_HOOK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+static RX_HOOK: AtomicPtr<()> = AtomicPtr::new(core::ptr::null_mut());
+
+/// Installs the hook run by [`SpiDescriptor::trace_tx`] before a transfer.
+pub fn set_tx_hook(hook: TraceHook) {
+    TX_HOOK.store(hook as *mut (), Ordering::Release);
+}
+/// Installs the hook run by [`SpiDescriptor::trace_rx`] after a transfer.
+pub fn set_rx_hook(hook: TraceHook) {
+    RX_HOOK.store(hook as *mut (), Ordering::Release);
+}
+/// Removes both hooks.
+pub fn clear_hooks() {
+    TX_HOOK.store(core::ptr::null_mut(), Ordering::Release);
+    RX_HOOK.store(core::ptr::null_mut(), Ordering::Release);
+}
+
+pub(crate) fn tx_hook() -> Option<TraceHook> {
+    load(&TX_HOOK)
+}
+pub(crate) fn rx_hook() -> Option<TraceHook> {
+    load(&RX_HOOK)
+}
+
+fn load(hook: &AtomicPtr<()>) -> Option<TraceHook> {
+    let ptr = hook.load(Ordering::Acquire);
+    // SAFETY: only null or a `TraceHook` cast to a pointer is ever stored.
+    (!ptr.is_null()).then(|| unsafe { core::mem::transmute::<*mut (), TraceHook>(ptr) })
+}
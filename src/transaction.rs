@@ -0,0 +1,100 @@
+//! Group descriptors into transactions with explicit NSS handling.
+
+use super::commands::SpiDescriptor;
+
+/// How NSS is driven across the descriptors of a [`Transaction`].
+///
+/// The SX126x decodes the opcode on the falling edge of NSS, so every
+/// command has to be framed on its own.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsMode {
+    /// NSS is asserted before and released after every descriptor; each
+    /// descriptor is a complete command.
+    PerDescriptor,
+    /// NSS is asserted before the first descriptor and held until the last
+    /// one completes; the chain forms a single command, e.g. a WriteBuffer
+    /// header followed by a separate payload.
+    Held,
+}
+
+/// NSS edges an executor has to generate around one descriptor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Framing {
+    /// Assert NSS (drive low) before the transfer.
+    pub assert_cs: bool,
+    /// Release NSS (drive high) after the transfer.
+    pub release_cs: bool,
+}
+
+/// # Transaction
+/// A batch of descriptors executed back to back with the given [`CsMode`].
+///
+/// ## Example
+/// ```
+/// use sx126x_spi_buffers::commands::{SetStandby, StdbyConfig, SetTx};
+/// use sx126x_spi_buffers::transaction::{Transaction, CsMode, Framing};
+///
+/// let mut set_standby = SetStandby::new(StdbyConfig::StdbyRc);
+/// let mut set_tx = SetTx::new(0);
+/// let descriptors = [set_standby.descriptor(), set_tx.descriptor()];
+///
+/// let transaction = Transaction::new(&descriptors, CsMode::PerDescriptor);
+/// assert!(transaction.iter().all(|(_, f)| f == Framing { assert_cs: true, release_cs: true }));
+///
+/// let transaction = Transaction::new(&descriptors, CsMode::Held);
+/// let framing: Vec<_> = transaction.iter().map(|(_, f)| f).collect();
+/// assert_eq!(framing, [
+///     Framing { assert_cs: true, release_cs: false },
+///     Framing { assert_cs: false, release_cs: true },
+/// ]);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Transaction<'a> {
+    descriptors: &'a [SpiDescriptor],
+    cs_mode: CsMode,
+}
+impl<'a> Transaction<'a> {
+    #[inline(always)]
+    pub const fn new(descriptors: &'a [SpiDescriptor], cs_mode: CsMode) -> Self {
+        Self {
+            descriptors,
+            cs_mode,
+        }
+    }
+    #[inline(always)]
+    pub const fn descriptors(&self) -> &'a [SpiDescriptor] {
+        self.descriptors
+    }
+    #[inline(always)]
+    pub const fn cs_mode(&self) -> CsMode {
+        self.cs_mode
+    }
+    /// NSS edges around the descriptor at `index`.
+    #[inline(always)]
+    pub const fn framing(&self, index: usize) -> Framing {
+        match self.cs_mode {
+            CsMode::PerDescriptor => Framing {
+                assert_cs: true,
+                release_cs: true,
+            },
+            CsMode::Held => Framing {
+                assert_cs: index == 0,
+                release_cs: index + 1 == self.descriptors.len(),
+            },
+        }
+    }
+    /// Iterates over the descriptors together with their framing.
+    pub fn iter(&self) -> impl Iterator<Item = (&'a SpiDescriptor, Framing)> + '_ {
+        self.descriptors
+            .iter()
+            .enumerate()
+            .map(|(i, desc)| (desc, self.framing(i)))
+    }
+    /// Total number of bytes clocked by the transaction.
+    pub fn transfer_length(&self) -> usize {
+        self.descriptors
+            .iter()
+            .map(|desc| desc.transfer_length() as usize + desc.payload_length() as usize)
+            .sum()
+    }
+}
@@ -0,0 +1,70 @@
+//! Software GFSK data whitening.
+//!
+//! The chip whitens GFSK payloads with the 9-bit LFSR x^9 + x^5 + 1 (PN9),
+//! seeded from [`WhiteningInitial`]. [`Whitening`] generates the same
+//! sequence on the host, e.g. to exchange packets with radios that whiten
+//! in software. Whitening is its own inverse: applying it twice with the
+//! same seed restores the data.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::registers::WhiteningInitial;
+//! use sx126x_spi_buffers::whitening::Whitening;
+//!
+//! let mut data = [0; 4];
+//! Whitening::new(WhiteningInitial(0x01FF)).apply(&mut data);
+//! assert_eq!(data, [0xFF, 0xE1, 0x1D, 0x9A]);
+//!
+//! Whitening::new(WhiteningInitial(0x01FF)).apply(&mut data);
+//! assert_eq!(data, [0; 4]);
+//! ```
+//!
+//! [`WhiteningInitial`]: crate::registers::WhiteningInitial
+
+use super::registers::WhiteningInitial;
+
+/// # Whitening
+/// PN9 sequence generator, one byte per payload byte.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Whitening {
+    lfsr: u16,
+}
+impl Whitening {
+    /// Reset value of [`WhiteningInitial`].
+    pub const DEFAULT_SEED: WhiteningInitial = WhiteningInitial(0x01FF);
+
+    /// Starts the sequence from the 9 low bits of `seed`.
+    #[inline(always)]
+    pub const fn new(seed: WhiteningInitial) -> Self {
+        Self {
+            lfsr: seed.0 & 0x01FF,
+        }
+    }
+    /// The next byte of the sequence, to XOR with the next payload byte.
+    #[inline(always)]
+    pub const fn next_byte(&mut self) -> u8 {
+        let byte = self.lfsr as u8;
+        let mut i = 0;
+        while i < 8 {
+            let feedback = (self.lfsr ^ self.lfsr >> 5) & 1;
+            self.lfsr = self.lfsr >> 1 | feedback << 8;
+            i += 1;
+        }
+        byte
+    }
+    /// Whitens or de-whitens `data` in place.
+    #[inline(always)]
+    pub const fn apply(&mut self, data: &mut [u8]) {
+        let mut i = 0;
+        while i < data.len() {
+            data[i] ^= self.next_byte();
+            i += 1;
+        }
+    }
+}
+
+impl Default for Whitening {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
@@ -0,0 +1,180 @@
+//! Register fix-ups from the "Known Limitations" chapter of the datasheet.
+//!
+//! Every fix-up is a read-modify-write of a single register: send the
+//! [`read_descriptor`](Fixup::read_descriptor), then the
+//! [`write_descriptor`](Fixup::write_descriptor), which is built from the
+//! value read back. [`applicable`] lists the fix-ups a configuration needs.
+//!
+//! ## Example
+//! ```
+//! use sx126x_spi_buffers::commands::{Bw, HeaderType, InvertIq};
+//! use sx126x_spi_buffers::workarounds::{self, Fixup, Workaround};
+//!
+//! let workarounds = workarounds::applicable(Bw::Bw500, HeaderType::VariableLength, InvertIq::Inverted);
+//! assert_eq!(
+//!     workarounds.as_slice(),
+//!     [
+//!         Workaround::TxClamp,
+//!         Workaround::TxModulation { narrow_bandwidth: false },
+//!         Workaround::IqPolarity { standard_iq: false },
+//!     ]
+//! );
+//!
+//! let mut fixup = Fixup::new(workarounds.as_slice()[1]);
+//! assert_eq!(fixup.read.tx_buf, [0x1D, 0x08, 0x89, 0, 0]);
+//! fixup.read.rx_buf[4] = 0x04;
+//! let desc = fixup.write_descriptor();
+//! assert_eq!(desc.transfer_length(), 4);
+//! assert_eq!(fixup.write.tx_buf, [0x0D, 0x08, 0x89, 0x00]);
+//! ```
+
+use super::commands::{Bw, HeaderType, InvertIq, ReadRegister, SpiDescriptor, WriteRegister};
+use super::registers::{
+    IqPolaritySetup, RawRegister, Register, TxClampConfig, TxModulation, addresses,
+};
+
+/// A single documented register fix-up.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Workaround {
+    /// Better resistance of the SX1262 PA to antenna mismatch. Apply once
+    /// after power-on and after every cold-start wake-up.
+    TxClamp,
+    /// LoRa modulation quality at 500 kHz. Apply before every SetTx.
+    TxModulation { narrow_bandwidth: bool },
+    /// Optimized reception with inverted IQ. Apply after every
+    /// SetPacketParams.
+    IqPolarity { standard_iq: bool },
+    /// First half of the implicit header timeout fix-up: stops the RTC.
+    /// Apply after every RX with a timeout in implicit header mode.
+    StopRtc,
+    /// Second half of the implicit header timeout fix-up: clears the
+    /// pending timeout event.
+    ClearTimeoutEvent,
+}
+impl Workaround {
+    /// Address of the register the fix-up rewrites.
+    #[inline(always)]
+    pub const fn address(self) -> u16 {
+        match self {
+            Self::TxClamp => TxClampConfig::ADDRESS,
+            Self::TxModulation { .. } => TxModulation::ADDRESS,
+            Self::IqPolarity { .. } => IqPolaritySetup::ADDRESS,
+            Self::StopRtc => addresses::RTC_CONTROL,
+            Self::ClearTimeoutEvent => addresses::EVENT_MASK,
+        }
+    }
+    /// Value to write back, given the value read from [`address`](Self::address).
+    #[inline(always)]
+    pub const fn modify(self, value: u8) -> u8 {
+        match self {
+            Self::TxClamp => TxClampConfig::from_bits(value)
+                .with_clamp_workaround()
+                .into_bits(),
+            Self::TxModulation { narrow_bandwidth } => TxModulation::from_bits(value)
+                .with_narrow_bandwidth(narrow_bandwidth)
+                .into_bits(),
+            Self::IqPolarity { standard_iq } => IqPolaritySetup::from_bits(value)
+                .with_standard_iq(standard_iq)
+                .into_bits(),
+            Self::StopRtc => 0x00,
+            Self::ClearTimeoutEvent => value | 0x02,
+        }
+    }
+}
+
+/// Fix-ups applicable to a LoRa configuration, in the order they are
+/// needed during bring-up.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Workarounds {
+    list: [Workaround; 5],
+    len: u8,
+}
+impl Workarounds {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            list: [Workaround::TxClamp; 5],
+            len: 0,
+        }
+    }
+    /// Only used by [`applicable`], which adds at most 5 fix-ups.
+    #[inline(always)]
+    const fn with(mut self, workaround: Workaround) -> Self {
+        self.list[self.len as usize] = workaround;
+        self.len += 1;
+        self
+    }
+    #[inline(always)]
+    pub const fn as_slice(&self) -> &[Workaround] {
+        self.list.split_at(self.len as usize).0
+    }
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len as usize
+    }
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+impl Default for Workarounds {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns the fix-ups a LoRa link with the given parameters needs. The TX
+/// clamp, TX modulation and IQ polarity fix-ups always apply, the latter two
+/// with the value matching `bw` and `invert_iq`. The implicit header timeout
+/// fix-ups are only listed for [`HeaderType::FixedLength`].
+pub const fn applicable(bw: Bw, header_type: HeaderType, invert_iq: InvertIq) -> Workarounds {
+    let workarounds = Workarounds::new()
+        .with(Workaround::TxClamp)
+        .with(Workaround::TxModulation {
+            narrow_bandwidth: !matches!(bw, Bw::Bw500),
+        })
+        .with(Workaround::IqPolarity {
+            standard_iq: matches!(invert_iq, InvertIq::Standard),
+        });
+    match header_type {
+        HeaderType::FixedLength => workarounds
+            .with(Workaround::StopRtc)
+            .with(Workaround::ClearTimeoutEvent),
+        HeaderType::VariableLength => workarounds,
+    }
+}
+
+/// # Fix-up sequence
+/// The ReadRegister and WriteRegister pair applying one [`Workaround`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fixup {
+    pub workaround: Workaround,
+    pub read: ReadRegister<RawRegister>,
+    pub write: WriteRegister,
+}
+impl Fixup {
+    #[inline(always)]
+    pub const fn new(workaround: Workaround) -> Self {
+        let address = workaround.address();
+        Self {
+            workaround,
+            read: ReadRegister::new_raw(address),
+            write: WriteRegister::new_raw(RawRegister { address, value: 0 }),
+        }
+    }
+    #[inline(always)]
+    pub const fn read_descriptor(&mut self) -> SpiDescriptor {
+        self.read.descriptor()
+    }
+    /// Builds the write from the value read back; only call this once the
+    /// read has completed.
+    #[inline(always)]
+    pub const fn write_descriptor(&mut self) -> SpiDescriptor {
+        let register = self.read.raw_register();
+        self.write = WriteRegister::new_raw(RawRegister {
+            address: register.address,
+            value: self.workaround.modify(register.value),
+        });
+        self.write.descriptor()
+    }
+}